@@ -90,6 +90,30 @@ pub struct CompileCommand {
     /// apart from file names and line numbers.
     #[arg(long = "timings", value_name = "OUTPUT_JSON")]
     pub timings: Option<Option<PathBuf>>,
+
+    /// Prints the time spent parsing, evaluating, laying out, and exporting
+    ///
+    /// Parsing of imported files happens during evaluation and is counted in
+    /// both phases.
+    #[arg(long = "phase-timings")]
+    pub phase_timings: bool,
+
+    /// Only checks the document for errors and warnings without exporting it
+    #[arg(long = "check", conflicts_with_all = ["format", "open"])]
+    pub check: bool,
+
+    /// Prints a report about the compiled document
+    #[arg(long = "report", value_name = "REPORT")]
+    pub report: Option<Report>,
+
+    /// The resolution in DPI below which placed images are reported with a
+    /// warning in the resources report
+    #[arg(long = "min-dpi", default_value_t = 300.0, requires = "report")]
+    pub min_dpi: f64,
+
+    /// Leaves out the notes created with `meta.pdf.note` when exporting to PDF
+    #[arg(long = "strip-notes")]
+    pub strip_notes: bool,
 }
 
 /// Initializes a new project from a template
@@ -157,14 +181,17 @@ pub struct SharedArgs {
     )]
     pub inputs: Vec<(String, String)>,
 
-    /// Adds additional directories to search for fonts
-    #[clap(
-        long = "font-path",
-        env = "TYPST_FONT_PATHS",
-        value_name = "DIR",
-        value_delimiter = ENV_PATH_SEP,
-    )]
-    pub font_paths: Vec<PathBuf>,
+    /// Selects the variant of the document visible through `sys.variant`
+    ///
+    /// When given multiple times, `typst compile` compiles the document once
+    /// per variant and adds the variant's name to each output file name, as
+    /// in `main-solutions.pdf`.
+    #[clap(long = "variant", value_name = "NAME", action = ArgAction::Append)]
+    pub variants: Vec<String>,
+
+    /// Font-related arguments
+    #[clap(flatten)]
+    pub font: FontArgs,
 
     /// The format to emit diagnostics in
     #[clap(
@@ -211,9 +238,9 @@ fn parse_input_pair(raw: &str) -> Result<(String, String), String> {
     Ok((key, val))
 }
 
-/// Lists all discovered fonts in system and custom font paths
-#[derive(Debug, Clone, Parser)]
-pub struct FontsCommand {
+/// Common arguments for finding fonts.
+#[derive(Debug, Clone, Args)]
+pub struct FontArgs {
     /// Adds additional directories to search for fonts
     #[clap(
         long = "font-path",
@@ -223,6 +250,23 @@ pub struct FontsCommand {
     )]
     pub font_paths: Vec<PathBuf>,
 
+    /// Caches font metadata in this directory instead of the user's cache
+    /// directory
+    #[clap(long = "font-cache-dir", env = "TYPST_FONT_CACHE_DIR", value_name = "DIR")]
+    pub font_cache_dir: Option<PathBuf>,
+
+    /// Disables caching font metadata between runs
+    #[clap(long)]
+    pub no_font_cache: bool,
+}
+
+/// Lists all discovered fonts in system and custom font paths
+#[derive(Debug, Clone, Parser)]
+pub struct FontsCommand {
+    /// Font-related arguments
+    #[clap(flatten)]
+    pub font: FontArgs,
+
     /// Also lists style variants of each font family
     #[arg(long)]
     pub variants: bool,
@@ -233,6 +277,15 @@ pub struct FontsCommand {
 pub enum DiagnosticFormat {
     Human,
     Short,
+    /// One JSON object per line, for consumption by editors and other tools.
+    ///
+    /// Each object has a `severity` (`"error"` or `"warning"`), a `message`,
+    /// a list of `hints`, an optional `file` and `range`, and a `trace` of
+    /// further located messages. Ranges have a `start` and an `end` position,
+    /// each consisting of a zero-based `byte` offset, a one-based `line` and
+    /// `column` (in characters), and a zero-based `utf16_column` as used by
+    /// the Language Server Protocol.
+    Json,
 }
 
 impl Display for DiagnosticFormat {
@@ -260,12 +313,22 @@ pub struct UpdateCommand {
     pub revert: bool,
 }
 
+/// Which report to print after compilation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
+pub enum Report {
+    /// The fonts and images that the document embeds.
+    Resources,
+}
+
 /// Which format to use for the generated output file.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, ValueEnum)]
 pub enum OutputFormat {
     Pdf,
     Png,
     Svg,
+    Html,
+    Txt,
+    Json,
 }
 
 impl Display for OutputFormat {
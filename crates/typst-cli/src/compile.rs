@@ -1,4 +1,5 @@
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use chrono::{Datelike, Timelike};
@@ -7,17 +8,21 @@ use codespan_reporting::term;
 use ecow::{eco_format, EcoString};
 use parking_lot::RwLock;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use typst::diag::{bail, At, Severity, SourceDiagnostic, StrResult};
+use typst::diag::{
+    bail, sort_diagnostics, At, JsonDiagnostic, Severity, SourceDiagnostic, StrResult,
+};
 use typst::eval::Tracer;
-use typst::foundations::{Datetime, Smart};
+use typst::foundations::{Content, Datetime};
 use typst::layout::Frame;
-use typst::model::Document;
+use typst::model::{Document, Resources};
 use typst::syntax::{FileId, Source, Span};
-use typst::visualize::Color;
+use typst::text::FontVariant;
+use typst::visualize::{Color, ImageFormat, RasterFormat, VectorFormat};
 use typst::{World, WorldExt};
+use typst_pdf::PdfOptions;
 
-use crate::args::{CompileCommand, DiagnosticFormat, Input, OutputFormat};
-use crate::timings::Timer;
+use crate::args::{CompileCommand, DiagnosticFormat, Input, OutputFormat, Report};
+use crate::timings::{self, Timer};
 use crate::watch::Status;
 use crate::world::SystemWorld;
 use crate::{set_failed, terminal};
@@ -37,6 +42,9 @@ impl CompileCommand {
                     OutputFormat::Pdf => "pdf",
                     OutputFormat::Png => "png",
                     OutputFormat::Svg => "svg",
+                    OutputFormat::Html => "html",
+                    OutputFormat::Txt => "txt",
+                    OutputFormat::Json => "json",
                 },
             )
         })
@@ -53,6 +61,9 @@ impl CompileCommand {
                 Some(ext) if ext.eq_ignore_ascii_case("pdf") => OutputFormat::Pdf,
                 Some(ext) if ext.eq_ignore_ascii_case("png") => OutputFormat::Png,
                 Some(ext) if ext.eq_ignore_ascii_case("svg") => OutputFormat::Svg,
+                Some(ext) if ext.eq_ignore_ascii_case("html") => OutputFormat::Html,
+                Some(ext) if ext.eq_ignore_ascii_case("txt") => OutputFormat::Txt,
+                Some(ext) if ext.eq_ignore_ascii_case("json") => OutputFormat::Json,
                 _ => bail!("could not infer output format for path {}.\nconsider providing the format manually with `--format/-f`", output.display()),
             }
         } else {
@@ -65,10 +76,40 @@ impl CompileCommand {
 pub fn compile(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
     let mut world =
         SystemWorld::new(&command.common).map_err(|err| eco_format!("{err}"))?;
-    timer.record(&mut world, |world| compile_once(world, &mut command, false))??;
+
+    if command.common.variants.len() <= 1 {
+        timer.record(&mut world, |world| compile_once(world, &mut command, false))??;
+        return Ok(());
+    }
+
+    // Compile each variant into its own output file.
+    let output = command.output();
+    let variants = command.common.variants.clone();
+    timer.record(&mut world, |world| {
+        for variant in &variants {
+            world.set_variant(Some(variant));
+            let mut command = command.clone();
+            command.output = Some(variant_output(&output, variant));
+            compile_once(world, &mut command, false)?;
+        }
+        Ok::<_, EcoString>(())
+    })??;
+
     Ok(())
 }
 
+/// Add the name of a variant to an output path, before its extension.
+fn variant_output(output: &Path, variant: &str) -> PathBuf {
+    let mut name = output.file_stem().unwrap_or_default().to_os_string();
+    name.push("-");
+    name.push(variant);
+    if let Some(extension) = output.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+    output.with_file_name(name)
+}
+
 /// Compile a single time.
 ///
 /// Returns whether it compiled without errors.
@@ -83,6 +124,10 @@ pub fn compile_once(
         Status::Compiling.print(command).unwrap();
     }
 
+    if command.phase_timings {
+        typst_timing::clear();
+    }
+
     // Check if main file can be read and opened.
     if let Err(errors) = world.source(world.main()).at(Span::detached()) {
         set_failed();
@@ -97,13 +142,32 @@ pub fn compile_once(
     }
 
     let mut tracer = Tracer::new();
-    let result = typst::compile(world, &mut tracer);
-    let warnings = tracer.warnings();
+    let result = if command.check {
+        typst::compile(world, &mut tracer)
+            .map(|document| Output::Checked(Box::new(document)))
+    } else if let Some(format) = DocumentFormat::of(command.output_format()?) {
+        typst::compile(world, &mut tracer)
+            .map(|document| Output::Document(Box::new(document), format))
+    } else {
+        // The JSON export only needs the evaluated content, so it skips layout.
+        typst::evaluate(world, &mut tracer).map(Output::Content)
+    };
+    let mut warnings = tracer.warnings();
 
     match result {
         // Export the PDF / PNG.
-        Ok(document) => {
-            export(world, &document, command, watching)?;
+        Ok(output) => {
+            export(world, &output, command, watching)?;
+
+            if let (
+                Some(Report::Resources),
+                Output::Document(document, _) | Output::Checked(document),
+            ) = (command.report, &output)
+            {
+                let resources = Resources::new(document);
+                print_resources(world, &resources);
+                warnings.extend(resources.warnings(command.min_dpi));
+            }
             let duration = start.elapsed();
 
             if watching {
@@ -140,36 +204,117 @@ pub fn compile_once(
         }
     }
 
+    if command.phase_timings {
+        timings::print_phases();
+    }
+
     Ok(())
 }
 
+/// What compilation produced for the export.
+enum Output {
+    /// A fully layouted document and the format to export it in.
+    Document(Box<Document>, DocumentFormat),
+    /// Only the evaluated content, for exports of the document's structure.
+    Content(Content),
+    /// A fully layouted document that is only checked and not exported.
+    Checked(Box<Document>),
+}
+
+/// A format that a layouted document is exported in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum DocumentFormat {
+    Pdf,
+    Png,
+    Svg,
+    Html,
+    Txt,
+}
+
+impl DocumentFormat {
+    /// The format to export a layouted document in, or `None` if the output
+    /// format is exported from the content instead.
+    fn of(format: OutputFormat) -> Option<Self> {
+        match format {
+            OutputFormat::Pdf => Some(Self::Pdf),
+            OutputFormat::Png => Some(Self::Png),
+            OutputFormat::Svg => Some(Self::Svg),
+            OutputFormat::Html => Some(Self::Html),
+            OutputFormat::Txt => Some(Self::Txt),
+            OutputFormat::Json => None,
+        }
+    }
+}
+
 /// Export into the target format.
+#[typst_macros::time(name = "export")]
 fn export(
     world: &mut SystemWorld,
-    document: &Document,
+    output: &Output,
     command: &CompileCommand,
     watching: bool,
 ) -> StrResult<()> {
-    match command.output_format()? {
-        OutputFormat::Png => {
+    let (document, format) = match output {
+        Output::Document(document, format) => (document, *format),
+        Output::Content(content) => return export_json(content, command),
+        Output::Checked(_) => return Ok(()),
+    };
+
+    match format {
+        DocumentFormat::Png => {
             export_image(world, document, command, watching, ImageExportFormat::Png)
         }
-        OutputFormat::Svg => {
+        DocumentFormat::Svg => {
             export_image(world, document, command, watching, ImageExportFormat::Svg)
         }
-        OutputFormat::Pdf => export_pdf(document, command),
+        DocumentFormat::Pdf => export_pdf(document, command),
+        DocumentFormat::Html => export_html(document, command),
+        DocumentFormat::Txt => export_txt(document, command),
     }
 }
 
 /// Export to a PDF.
 fn export_pdf(document: &Document, command: &CompileCommand) -> StrResult<()> {
-    let buffer = typst_pdf::pdf(document, Smart::Auto, now());
+    let options = PdfOptions {
+        timestamp: now(),
+        strip_notes: command.strip_notes,
+        ..PdfOptions::default()
+    };
+    let buffer = typst_pdf::pdf_with_options(document, &options)
+        .map_err(|err| eco_format!("failed to export PDF file: {err}"))?;
     let output = command.output();
     fs::write(output, buffer)
         .map_err(|err| eco_format!("failed to write PDF file ({err})"))?;
     Ok(())
 }
 
+/// Export to an HTML file for previewing.
+fn export_html(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    let html = typst_html::html(document, true);
+    let output = command.output();
+    fs::write(output, html)
+        .map_err(|err| eco_format!("failed to write HTML file ({err})"))?;
+    Ok(())
+}
+
+/// Export the plain text of all pages, separated by form feeds.
+fn export_txt(document: &Document, command: &CompileCommand) -> StrResult<()> {
+    let text = typst_text::text(document).join("\n\u{c}\n");
+    let output = command.output();
+    fs::write(output, text)
+        .map_err(|err| eco_format!("failed to write text file ({err})"))?;
+    Ok(())
+}
+
+/// Export the structure of the evaluated content, before layout.
+fn export_json(content: &Content, command: &CompileCommand) -> StrResult<()> {
+    let json = typst_json::json(content);
+    let output = command.output();
+    fs::write(output, json)
+        .map_err(|err| eco_format!("failed to write JSON file ({err})"))?;
+    Ok(())
+}
+
 /// Get the current date and time in UTC.
 fn now() -> Option<Datetime> {
     let now = chrono::Local::now().naive_utc();
@@ -258,6 +403,78 @@ fn export_image(
     Ok(())
 }
 
+/// Print the fonts and images a document uses as tables.
+fn print_resources(world: &mut SystemWorld, resources: &Resources) {
+    let fonts = resources.fonts.iter().map(|usage| {
+        let info = usage.font.info();
+        let FontVariant { style, weight, stretch } = info.variant;
+        [
+            info.family.as_str().into(),
+            eco_format!(
+                "{style:?}, {}, {:.0}%",
+                weight.to_number(),
+                stretch.to_ratio().get() * 100.0
+            ),
+            eco_format!("{}", usage.glyphs),
+            match world.font_path(&usage.font) {
+                Some(path) => eco_format!("{}", path.display()),
+                None => "(embedded)".into(),
+            },
+        ]
+    });
+    let fonts: Vec<_> = fonts.collect();
+    print_table("Fonts", ["Family", "Variant", "Glyphs", "File"], &fonts);
+
+    let mut images = vec![];
+    for placed in &resources.images {
+        let image = &placed.image;
+        images.push([
+            match world.find_file(image.data()) {
+                Some(id) => eco_format!("{}", id.vpath().as_rootless_path().display()),
+                None => "(unknown)".into(),
+            },
+            eco_format!("{}", placed.page),
+            match image.format() {
+                ImageFormat::Raster(RasterFormat::Png) => "png".into(),
+                ImageFormat::Raster(RasterFormat::Jpg) => "jpg".into(),
+                ImageFormat::Raster(RasterFormat::Gif) => "gif".into(),
+                ImageFormat::Vector(VectorFormat::Svg) => "svg".into(),
+            },
+            eco_format!("{}x{}", image.width(), image.height()),
+            eco_format!("{:.1}x{:.1}pt", placed.size.x.to_pt(), placed.size.y.to_pt()),
+            match placed.dpi() {
+                Some(dpi) => eco_format!("{dpi:.0}"),
+                None => "-".into(),
+            },
+        ]);
+    }
+    print_table("Images", ["File", "Page", "Format", "Pixels", "Placed", "DPI"], &images);
+}
+
+/// Print a table with a title and aligned columns.
+fn print_table<const N: usize>(title: &str, header: [&str; N], rows: &[[EcoString; N]]) {
+    let mut widths = header.map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    println!("{title}:");
+    let header = header.map(EcoString::from);
+    for row in std::iter::once(&header).chain(rows) {
+        let mut line = String::from(" ");
+        for (cell, width) in row.iter().zip(widths) {
+            line.push_str(&format!(" {cell:width$} "));
+        }
+        println!("{}", line.trim_end());
+    }
+
+    if rows.is_empty() {
+        println!("  (none)");
+    }
+}
+
 /// Caches exported files so that we can avoid re-exporting them if they haven't
 /// changed.
 ///
@@ -311,12 +528,16 @@ pub fn print_diagnostics(
     warnings: &[SourceDiagnostic],
     diagnostic_format: DiagnosticFormat,
 ) -> Result<(), codespan_reporting::files::Error> {
+    if diagnostic_format == DiagnosticFormat::Json {
+        return print_json_diagnostics(world, errors, warnings);
+    }
+
     let mut config = term::Config { tab_width: 2, ..Default::default() };
     if diagnostic_format == DiagnosticFormat::Short {
         config.display_style = term::DisplayStyle::Short;
     }
 
-    for diagnostic in warnings.iter().chain(errors) {
+    for diagnostic in &sorted(world, errors, warnings) {
         let diag = match diagnostic.severity {
             Severity::Error => Diagnostic::error(),
             Severity::Warning => Diagnostic::warning(),
@@ -347,6 +568,35 @@ pub fn print_diagnostics(
     Ok(())
 }
 
+/// Print diagnostics as JSON, one object per line.
+fn print_json_diagnostics(
+    world: &SystemWorld,
+    errors: &[SourceDiagnostic],
+    warnings: &[SourceDiagnostic],
+) -> Result<(), codespan_reporting::files::Error> {
+    let mut out = terminal::out();
+    let name = |id| codespan_reporting::files::Files::name(world, id).ok();
+    for diagnostic in &sorted(world, errors, warnings) {
+        let json = JsonDiagnostic::new(world, &name, diagnostic);
+        let line =
+            serde_json::to_string(&json).map_err(|err| CodespanError::Io(err.into()))?;
+        writeln!(out, "{line}")?;
+    }
+
+    Ok(())
+}
+
+/// Merge errors and warnings into the order in which they are printed.
+fn sorted(
+    world: &SystemWorld,
+    errors: &[SourceDiagnostic],
+    warnings: &[SourceDiagnostic],
+) -> Vec<SourceDiagnostic> {
+    let mut diagnostics: Vec<_> = errors.iter().chain(warnings).cloned().collect();
+    sort_diagnostics(world, &mut diagnostics);
+    diagnostics
+}
+
 /// Create a label for a span.
 fn label(world: &SystemWorld, span: Span) -> Option<Label<FileId>> {
     Some(Label::primary(span.id()?, world.range(span)?))
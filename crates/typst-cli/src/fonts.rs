@@ -1,18 +1,23 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::SystemTime;
 
 use fontdb::{Database, Source};
+use serde::{Deserialize, Serialize};
+use tempfile::NamedTempFile;
 use typst::diag::StrResult;
 use typst::text::{Font, FontBook, FontInfo, FontVariant};
 use typst_timing::TimingScope;
 
-use crate::args::FontsCommand;
+use crate::args::{FontArgs, FontsCommand};
 
 /// Execute a font listing command.
 pub fn fonts(command: &FontsCommand) -> StrResult<()> {
     let mut searcher = FontSearcher::new();
-    searcher.search(&command.font_paths);
+    searcher.search(&command.font);
 
     for (name, infos) in searcher.book.families() {
         println!("{name}");
@@ -57,6 +62,16 @@ impl FontSlot {
             })
             .clone()
     }
+
+    /// The font if it was already loaded.
+    pub fn loaded(&self) -> Option<&Font> {
+        self.font.get()?.as_ref()
+    }
+
+    /// The path of the font file, if it wasn't embedded into the binary.
+    pub fn path(&self) -> Option<&Path> {
+        Some(self.path.as_path()).filter(|path| !path.as_os_str().is_empty())
+    }
 }
 
 impl FontSearcher {
@@ -66,11 +81,32 @@ impl FontSearcher {
     }
 
     /// Search everything that is available.
-    pub fn search(&mut self, font_paths: &[PathBuf]) {
+    pub fn search(&mut self, args: &FontArgs) {
+        let mut cache = FontCache::load(FontCache::path(args));
+        match cache.unchanged(&args.font_paths) {
+            Some(entries) => {
+                for entry in entries {
+                    self.push(entry.path.clone(), entry.index, entry.info.clone());
+                }
+            }
+            None => self.scan(args, &mut cache),
+        }
+
+        cache.save(&args.font_paths);
+
+        // Embedded fonts have lowest priority.
+        #[cfg(feature = "embed-fonts")]
+        self.add_embedded();
+    }
+
+    /// Search the font paths and the system fonts with `fontdb`, taking the
+    /// metadata of unchanged faces from the cache.
+    fn scan(&mut self, args: &FontArgs, cache: &mut FontCache) {
+        let _scope = TimingScope::new("scan fonts", None);
         let mut db = Database::new();
 
         // Font paths have highest priority.
-        for path in font_paths {
+        for path in &args.font_paths {
             db.load_fonts_dir(path);
         }
 
@@ -85,23 +121,21 @@ impl FontSearcher {
                 Source::Binary(_) => continue,
             };
 
-            let info = db
-                .with_face_data(face.id, FontInfo::new)
-                .expect("database must contain this font");
+            let info = cache.get_or_insert(path, face.index, || {
+                db.with_face_data(face.id, FontInfo::new)
+                    .expect("database must contain this font")
+            });
 
-            if let Some(info) = info {
-                self.book.push(info);
-                self.fonts.push(FontSlot {
-                    path: path.clone(),
-                    index: face.index,
-                    font: OnceLock::new(),
-                });
-            }
+            self.push(path.clone(), face.index, info);
         }
+    }
 
-        // Embedded fonts have lowest priority.
-        #[cfg(feature = "embed-fonts")]
-        self.add_embedded();
+    /// Add a face found on disk, unless it could not be parsed.
+    fn push(&mut self, path: PathBuf, index: u32, info: Option<FontInfo>) {
+        if let Some(info) = info {
+            self.book.push(info);
+            self.fonts.push(FontSlot { path, index, font: OnceLock::new() });
+        }
     }
 
     /// Add fonts that are embedded in the binary.
@@ -120,3 +154,274 @@ impl FontSearcher {
         }
     }
 }
+
+/// Font metadata from previous runs, stored on disk.
+///
+/// Finding the installed fonts and computing their metadata requires parsing
+/// large parts of each font, which makes up most of the startup time when many
+/// fonts are installed. If none of the directories that contained fonts and
+/// none of the font files were modified since the last run, the fonts are
+/// therefore taken from the cache without searching for them at all.
+/// Otherwise, the metadata is still reused for unmodified font files.
+///
+/// Adding or removing a font modifies its directory. Fonts that are added to a
+/// directory that did not contain any fonts before are only found once another
+/// font directory changes or when the cache is disabled with
+/// `--no-font-cache`.
+#[derive(Default, Serialize, Deserialize)]
+struct FontCache {
+    /// The version of Typst that wrote the cache.
+    version: String,
+    /// The additional font paths that were searched.
+    font_paths: Vec<PathBuf>,
+    /// The searched font paths and the directories that contained fonts,
+    /// with their modification times at the time of the search.
+    dirs: Vec<(PathBuf, Option<SystemTime>)>,
+    /// The metadata of each face, or `None` for faces that could not be
+    /// parsed.
+    entries: Vec<FontCacheEntry>,
+    /// The file the cache is stored in, or `None` if caching is disabled.
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    /// Lookup from path and index into the entries.
+    #[serde(skip)]
+    lookup: HashMap<(PathBuf, u32), usize>,
+    /// Entries for the faces that were requested during this run.
+    #[serde(skip)]
+    used: Vec<FontCacheEntry>,
+    /// Whether the used entries differ from the stored ones.
+    #[serde(skip)]
+    dirty: bool,
+}
+
+/// The cached metadata of a single font face.
+#[derive(Clone, Serialize, Deserialize)]
+struct FontCacheEntry {
+    path: PathBuf,
+    index: u32,
+    modified: SystemTime,
+    info: Option<FontInfo>,
+}
+
+impl FontCache {
+    /// The path of the cache file, or `None` if caching is disabled.
+    fn path(args: &FontArgs) -> Option<PathBuf> {
+        if args.no_font_cache {
+            return None;
+        }
+
+        let dir = match &args.font_cache_dir {
+            Some(dir) => dir.clone(),
+            None => dirs::cache_dir()?.join("typst"),
+        };
+
+        Some(dir.join("fonts.json"))
+    }
+
+    /// Load the cache from the given file.
+    ///
+    /// If it is missing, corrupted, or was written by a different version of
+    /// Typst, an empty cache is returned.
+    fn load(path: Option<PathBuf>) -> Self {
+        let _scope = TimingScope::new("load font cache", None);
+        let mut cache = path
+            .as_ref()
+            .and_then(|path| fs::read(path).ok())
+            .and_then(|data| serde_json::from_slice::<Self>(&data).ok())
+            .filter(|cache| cache.version == env!("CARGO_PKG_VERSION"))
+            .unwrap_or_default();
+
+        cache.path = path;
+        cache.lookup = cache
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| ((entry.path.clone(), entry.index), i))
+            .collect();
+
+        cache
+    }
+
+    /// The cached faces in the order in which they were found, if the search
+    /// would find the same, unmodified font files again.
+    fn unchanged(&mut self, font_paths: &[PathBuf]) -> Option<&[FontCacheEntry]> {
+        let _scope = TimingScope::new("check font cache", None);
+        if self.entries.is_empty()
+            || self.font_paths != font_paths
+            || self.dirs.iter().any(|(dir, modified)| &mtime(dir) != modified)
+            || self
+                .entries
+                .iter()
+                .any(|entry| mtime(&entry.path) != Some(entry.modified))
+        {
+            return None;
+        }
+
+        self.used = self.entries.clone();
+        Some(&self.entries)
+    }
+
+    /// Get the metadata of a face from the cache if the file is unchanged or
+    /// compute it otherwise.
+    fn get_or_insert(
+        &mut self,
+        path: &Path,
+        index: u32,
+        compute: impl FnOnce() -> Option<FontInfo>,
+    ) -> Option<FontInfo> {
+        let modified = mtime(path);
+        let cached = self
+            .lookup
+            .get(&(path.to_path_buf(), index))
+            .map(|&i| &self.entries[i])
+            .filter(|entry| Some(entry.modified) == modified);
+
+        if let Some(entry) = cached {
+            self.used.push(entry.clone());
+            return entry.info.clone();
+        }
+
+        let info = compute();
+        if let Some(modified) = modified {
+            self.used.push(FontCacheEntry {
+                path: path.to_path_buf(),
+                index,
+                modified,
+                info: info.clone(),
+            });
+        }
+        self.dirty = true;
+        info
+    }
+
+    /// Write the cache back to disk if it changed.
+    ///
+    /// Only faces that were requested during this run are kept. The cache is
+    /// written to a temporary file first and then moved into place, so that
+    /// concurrent runs never read a partially written cache. Failing to
+    /// write the cache is not an error since it is only an optimization.
+    fn save(self, font_paths: &[PathBuf]) {
+        let Some(path) = self.path else { return };
+
+        let mut dirs: Vec<PathBuf> = font_paths.to_vec();
+        for entry in &self.used {
+            if let Some(parent) = entry.path.parent() {
+                if !dirs.iter().any(|dir| dir == parent) {
+                    dirs.push(parent.to_path_buf());
+                }
+            }
+        }
+        let dirs: Vec<_> = dirs
+            .into_iter()
+            .map(|dir| {
+                let modified = mtime(&dir);
+                (dir, modified)
+            })
+            .collect();
+
+        if !self.dirty
+            && self.used.len() == self.entries.len()
+            && self.font_paths == font_paths
+            && self.dirs == dirs
+        {
+            return;
+        }
+
+        let cache = Self {
+            version: env!("CARGO_PKG_VERSION").into(),
+            font_paths: font_paths.to_vec(),
+            dirs,
+            entries: self.used,
+            ..Self::default()
+        };
+
+        let Ok(data) = serde_json::to_vec(&cache) else { return };
+        let Some(parent) = path.parent() else { return };
+        fs::create_dir_all(parent).ok();
+        let Ok(mut file) = NamedTempFile::new_in(parent) else { return };
+        if file.write_all(&data).is_ok() {
+            file.persist(path).ok();
+        }
+    }
+}
+
+/// The modification time of a file or directory.
+fn mtime(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_font_cache_second_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = typst_assets::fonts().next().unwrap();
+        let font = dir.path().join("font.otf");
+        fs::write(&font, data).unwrap();
+
+        let args = FontArgs {
+            font_paths: vec![],
+            font_cache_dir: Some(dir.path().join("cache")),
+            no_font_cache: false,
+        };
+
+        // The first run parses the font and writes the cache.
+        let mut cache = FontCache::load(FontCache::path(&args));
+        let info = cache.get_or_insert(&font, 0, || FontInfo::new(data, 0));
+        assert!(info.is_some());
+        cache.save(&args.font_paths);
+        assert!(dir.path().join("cache/fonts.json").exists());
+
+        // The second run takes the metadata from the cache.
+        let mut cache = FontCache::load(FontCache::path(&args));
+        let cached = cache.get_or_insert(&font, 0, || panic!("font was parsed again"));
+        assert_eq!(cached, info);
+        assert!(!cache.dirty);
+    }
+
+    #[test]
+    fn test_font_cache_skips_search() {
+        let dir = tempfile::tempdir().unwrap();
+        let fonts = dir.path().join("fonts");
+        fs::create_dir(&fonts).unwrap();
+        let data = typst_assets::fonts().next().unwrap();
+        let font = fonts.join("font.otf");
+        fs::write(&font, data).unwrap();
+
+        let font_paths = vec![fonts.clone()];
+        let path = Some(dir.path().join("fonts.json"));
+        let mut cache = FontCache::load(path.clone());
+        assert!(cache.unchanged(&font_paths).is_none());
+        cache.get_or_insert(&font, 0, || FontInfo::new(data, 0));
+        cache.save(&font_paths);
+
+        // Nothing changed, so the fonts don't need to be searched.
+        let mut cache = FontCache::load(path.clone());
+        let entries = cache.unchanged(&font_paths).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].path, font);
+        assert!(entries[0].info.is_some());
+        cache.save(&font_paths);
+
+        // Different font paths need a search.
+        let mut cache = FontCache::load(path.clone());
+        assert!(cache.unchanged(&[]).is_none());
+
+        // So does a font that was added to a font directory.
+        fs::write(fonts.join("other.otf"), data).unwrap();
+        let mut cache = FontCache::load(path.clone());
+        assert!(cache.unchanged(&font_paths).is_none());
+    }
+
+    #[test]
+    fn test_font_cache_disabled() {
+        let args = FontArgs {
+            font_paths: vec![],
+            font_cache_dir: Some(PathBuf::from("cache")),
+            no_font_cache: true,
+        };
+        assert_eq!(FontCache::path(&args), None);
+    }
+}
@@ -15,6 +15,10 @@ use crate::world::SystemWorld;
 
 /// Execute a query command.
 pub fn query(command: &QueryCommand) -> StrResult<()> {
+    if command.common.variants.len() > 1 {
+        bail!("cannot query multiple variants at once");
+    }
+
     let mut world = SystemWorld::new(&command.common)?;
 
     // Reset everything and ensure that the main file is present.
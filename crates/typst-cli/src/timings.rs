@@ -21,14 +21,15 @@ impl Timer {
     /// Initializes the timing system and returns a timer that can be used to
     /// record timings for a specific function invocation.
     pub fn new(args: &CliArguments) -> Timer {
-        let record = match &args.command {
-            Command::Compile(command) => command.timings.clone(),
-            Command::Watch(command) => command.timings.clone(),
-            _ => None,
+        let (record, phases) = match &args.command {
+            Command::Compile(command) | Command::Watch(command) => {
+                (command.timings.clone(), command.phase_timings)
+            }
+            _ => (None, false),
         };
 
         // Enable event collection.
-        if record.is_some() {
+        if record.is_some() || phases {
             typst_timing::enable();
         }
 
@@ -87,3 +88,15 @@ fn resolve_span(world: &SystemWorld, span: Span) -> Option<(String, u32)> {
     let line = source.byte_to_line(range.start)?;
     Some((format!("{id:?}"), line as u32 + 1))
 }
+
+/// Prints the time spent in each phase of the last compilation.
+pub fn print_phases() {
+    for phase in ["parse", "eval", "layout", "export"] {
+        let duration = typst_timing::total(|name| match phase {
+            "parse" => name.ends_with("parsing file"),
+            "layout" => name.starts_with("typeset"),
+            _ => name == phase,
+        });
+        eprintln!("{phase:>8}: {:.1} ms", duration.as_secs_f64() * 1000.0);
+    }
+}
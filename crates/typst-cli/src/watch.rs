@@ -10,7 +10,7 @@ use codespan_reporting::term::{self, termcolor};
 use ecow::eco_format;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher as _};
 use same_file::is_same_file;
-use typst::diag::StrResult;
+use typst::diag::{bail, StrResult};
 
 use crate::args::{CompileCommand, Input};
 use crate::compile::compile_once;
@@ -20,6 +20,10 @@ use crate::{print_error, terminal};
 
 /// Execute a watching compilation command.
 pub fn watch(mut timer: Timer, mut command: CompileCommand) -> StrResult<()> {
+    if command.common.variants.len() > 1 {
+        bail!("cannot watch multiple variants at once");
+    }
+
     // Create a file system watcher.
     let mut watcher = Watcher::new(command.output())?;
 
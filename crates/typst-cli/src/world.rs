@@ -33,6 +33,8 @@ pub struct SystemWorld {
     root: PathBuf,
     /// The input path.
     main: FileId,
+    /// The inputs visible through `sys.inputs`.
+    inputs: Dict,
     /// Typst's standard library.
     library: Prehashed<Library>,
     /// Metadata about discovered fonts.
@@ -90,24 +92,24 @@ impl SystemWorld {
             *STDIN_ID
         };
 
-        let library = {
-            // Convert the input pairs to a dictionary.
-            let inputs: Dict = command
-                .inputs
-                .iter()
-                .map(|(k, v)| (k.as_str().into(), v.as_str().into_value()))
-                .collect();
+        // Convert the input pairs to a dictionary.
+        let inputs: Dict = command
+            .inputs
+            .iter()
+            .map(|(k, v)| (k.as_str().into(), v.as_str().into_value()))
+            .collect();
 
-            Library::builder().with_inputs(inputs).build()
-        };
+        let variant = command.variants.first().map(String::as_str);
+        let library = build_library(&inputs, variant);
 
         let mut searcher = FontSearcher::new();
-        searcher.search(&command.font_paths);
+        searcher.search(&command.font);
 
         Ok(Self {
             workdir: std::env::current_dir().ok(),
             root,
             main,
+            inputs,
             library: Prehashed::new(library),
             book: Prehashed::new(searcher.book),
             fonts: searcher.fonts,
@@ -122,6 +124,11 @@ impl SystemWorld {
         self.main
     }
 
+    /// Select the variant of the document that is compiled next.
+    pub fn set_variant(&mut self, variant: Option<&str>) {
+        self.library = Prehashed::new(build_library(&self.inputs, variant));
+    }
+
     /// The root relative to which absolute paths are resolved.
     pub fn root(&self) -> &Path {
         &self.root
@@ -141,6 +148,21 @@ impl SystemWorld {
             .filter_map(|slot| system_path(&self.root, slot.id).ok())
     }
 
+    /// Find the path of a loaded font.
+    ///
+    /// Returns `None` for fonts that are embedded into the binary.
+    pub fn font_path(&self, font: &Font) -> Option<&Path> {
+        self.fonts.iter().find(|slot| slot.loaded() == Some(font))?.path()
+    }
+
+    /// Find a file that was read with exactly the given contents.
+    pub fn find_file(&mut self, data: &Bytes) -> Option<FileId> {
+        self.slots.get_mut().values().find_map(|slot| match &slot.file.data {
+            Some(Ok(bytes)) if bytes == data => Some(slot.id),
+            _ => None,
+        })
+    }
+
     /// Reset the compilation state in preparation of a new compilation.
     pub fn reset(&mut self) {
         for slot in self.slots.get_mut().values_mut() {
@@ -213,6 +235,15 @@ impl SystemWorld {
     }
 }
 
+/// Build the standard library with the given inputs and variant.
+fn build_library(inputs: &Dict, variant: Option<&str>) -> Library {
+    let mut builder = Library::builder().with_inputs(inputs.clone());
+    if let Some(variant) = variant {
+        builder = builder.with_variant(variant);
+    }
+    builder.build()
+}
+
 /// Holds the processed data for a file ID.
 ///
 /// Both fields can be populated if the file is both imported and read().
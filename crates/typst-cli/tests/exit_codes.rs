@@ -0,0 +1,78 @@
+//! Tests for the exit codes of the command line interface.
+
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tempfile::TempDir;
+
+/// Write a document into a fresh directory and run `typst` on it.
+fn run(text: &str, args: &[&str]) -> (TempDir, Output) {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("main.typ"), text).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_typst"))
+        .args(args)
+        .current_dir(dir.path())
+        .env("XDG_CACHE_HOME", dir.path().join("cache"))
+        .output()
+        .unwrap();
+    (dir, output)
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+fn exists(dir: &TempDir, name: &str) -> bool {
+    Path::new(dir.path()).join(name).exists()
+}
+
+#[test]
+fn test_compile_success() {
+    let (dir, output) = run("Hello", &["compile", "main.typ"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(exists(&dir, "main.pdf"));
+}
+
+#[test]
+fn test_compile_error() {
+    let (dir, output) = run("#foo", &["compile", "main.typ"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("unknown variable: foo"));
+    assert!(!exists(&dir, "main.pdf"));
+}
+
+#[test]
+fn test_compile_warning() {
+    let (dir, output) = run("Hello **", &["compile", "main.typ"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("no text within stars"));
+    assert!(exists(&dir, "main.pdf"));
+}
+
+#[test]
+fn test_compile_missing_input() {
+    let (_, output) = run("", &["compile", "missing.typ"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn test_check_success() {
+    let (dir, output) = run("Hello", &["compile", "--check", "main.typ"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(!exists(&dir, "main.pdf"));
+}
+
+#[test]
+fn test_check_error() {
+    let (dir, output) = run("#foo", &["compile", "--check", "main.typ"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr(&output).contains("unknown variable: foo"));
+    assert!(!exists(&dir, "main.pdf"));
+}
+
+#[test]
+fn test_check_conflicts_with_format() {
+    let (_, output) =
+        run("Hello", &["compile", "--check", "--format", "png", "main.typ"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,430 @@
+//! Rendering of Typst documents into HTML for previewing.
+//!
+//! The exporter does not attempt to produce semantic HTML. Instead, it mirrors
+//! the laid out frames: Every page becomes a fixed-size container and every
+//! frame item is absolutely positioned within it. Text is rendered by the
+//! browser with the same font properties, so the result closely reflects the
+//! real layout, but isn't pixel-perfect.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use base64::Engine;
+use ecow::{eco_format, EcoString};
+use typst::introspection::Meta;
+use typst::layout::{Abs, Frame, FrameItem, GroupItem, Point, Size, Transform};
+use typst::model::{Destination, Document};
+use typst::text::{Font, FontStyle, TextItem};
+use typst::visualize::{
+    FixedStroke, Geometry, Image, ImageFormat, Paint, PathItem, RasterFormat, Shape,
+    VectorFormat,
+};
+
+/// Export a document into a standalone HTML file.
+///
+/// If `embed_fonts` is true, all used fonts are embedded as `@font-face` data
+/// URIs so that the browser renders the exact faces used during layout.
+/// Otherwise, text refers to the fonts by family name and relies on them being
+/// installed.
+#[typst_macros::time(name = "html")]
+pub fn html(document: &Document, embed_fonts: bool) -> String {
+    let mut renderer = HtmlRenderer::new(embed_fonts);
+    for page in &document.pages {
+        renderer.render_page(&page.frame);
+    }
+    renderer.finalize(document.title.as_deref())
+}
+
+/// Renders frames into HTML.
+struct HtmlRenderer {
+    /// The rendered pages.
+    body: String,
+    /// Whether to embed the used fonts.
+    embed_fonts: bool,
+    /// The fonts used so far, mapped to their index in `fonts`.
+    font_ids: HashMap<Font, usize>,
+    /// The fonts used so far, in order of first use.
+    fonts: Vec<Font>,
+}
+
+impl HtmlRenderer {
+    /// Create a new renderer.
+    fn new(embed_fonts: bool) -> Self {
+        Self {
+            body: String::new(),
+            embed_fonts,
+            font_ids: HashMap::new(),
+            fonts: vec![],
+        }
+    }
+
+    /// Render a page as a fixed-size container.
+    fn render_page(&mut self, frame: &Frame) {
+        write!(
+            self.body,
+            "<div class=\"typst-page\" style=\"{}\">",
+            size_css(frame.size())
+        )
+        .unwrap();
+        self.render_frame(frame);
+        self.body.push_str("</div>\n");
+    }
+
+    /// Render the items of a frame.
+    fn render_frame(&mut self, frame: &Frame) {
+        for (pos, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => self.render_group(*pos, group),
+                FrameItem::Text(text) => self.render_text(*pos, text),
+                FrameItem::Shape(shape, _) => self.render_shape(*pos, shape),
+                FrameItem::Image(image, size, _) => self.render_image(*pos, image, *size),
+                FrameItem::Meta(meta, size) => self.render_meta(*pos, meta, *size),
+            }
+        }
+    }
+
+    /// Render a group as a nested, possibly transformed container.
+    fn render_group(&mut self, pos: Point, group: &GroupItem) {
+        write!(
+            self.body,
+            "<div style=\"{}{}",
+            pos_css(pos),
+            size_css(group.frame.size())
+        )
+        .unwrap();
+        if !group.transform.is_identity() {
+            write!(self.body, "{}", transform_css(group.transform)).unwrap();
+        }
+        if group.clip_path.is_some() {
+            self.body.push_str("overflow:hidden;");
+        }
+        self.body.push_str("\">");
+        self.render_frame(&group.frame);
+        self.body.push_str("</div>");
+    }
+
+    /// Render a text run as a span positioned such that its baseline matches
+    /// the baseline from the layout.
+    fn render_text(&mut self, pos: Point, text: &TextItem) {
+        let metrics = text.font.metrics();
+        let ascent = metrics.ascender.at(text.size);
+        let descent = metrics.descender.at(text.size);
+        let top = pos.y - ascent;
+        let family = self.font_family(&text.font);
+        let variant = text.font.info().variant;
+        let style = match variant.style {
+            FontStyle::Normal => "normal",
+            FontStyle::Italic => "italic",
+            FontStyle::Oblique => "oblique",
+        };
+
+        write!(
+            self.body,
+            "<span style=\"{}width:{};line-height:{};white-space:pre;\
+             font-family:{};font-size:{};font-weight:{};font-style:{};color:{};\">",
+            pos_css(Point::new(pos.x, top)),
+            pt(text.width()),
+            pt(ascent - descent),
+            escape(&family),
+            pt(text.size),
+            variant.weight.to_number(),
+            style,
+            paint_css(&text.fill),
+        )
+        .unwrap();
+        self.body.push_str(&escape(&text.text));
+        self.body.push_str("</span>");
+    }
+
+    /// Render a shape as inline SVG.
+    fn render_shape(&mut self, pos: Point, shape: &Shape) {
+        let mut path = EcoString::new();
+        match &shape.geometry {
+            Geometry::Line(target) => {
+                path = eco_format!("M0 0L{} {}", target.x.to_pt(), target.y.to_pt());
+            }
+            Geometry::Rect(size) => {
+                let (w, h) = (size.x.to_pt(), size.y.to_pt());
+                path = eco_format!("M0 0H{w}V{h}H0Z");
+            }
+            Geometry::Path(items) => {
+                for item in &items.0 {
+                    match item {
+                        PathItem::MoveTo(p) => {
+                            write!(path, "M{} {}", p.x.to_pt(), p.y.to_pt())
+                        }
+                        PathItem::LineTo(p) => {
+                            write!(path, "L{} {}", p.x.to_pt(), p.y.to_pt())
+                        }
+                        PathItem::CubicTo(a, b, p) => write!(
+                            path,
+                            "C{} {} {} {} {} {}",
+                            a.x.to_pt(),
+                            a.y.to_pt(),
+                            b.x.to_pt(),
+                            b.y.to_pt(),
+                            p.x.to_pt(),
+                            p.y.to_pt()
+                        ),
+                        PathItem::ClosePath => write!(path, "Z"),
+                    }
+                    .unwrap();
+                }
+            }
+        }
+
+        let fill = shape.fill.as_ref().map_or("none".into(), paint_css);
+        let stroke = shape.stroke.as_ref().map_or(EcoString::new(), stroke_attrs);
+        write!(
+            self.body,
+            "<svg style=\"{}overflow:visible;\" width=\"0\" height=\"0\">\
+             <path d=\"{path}\" fill=\"{fill}\"{stroke}/></svg>",
+            pos_css(pos),
+        )
+        .unwrap();
+    }
+
+    /// Render an image as a data URI.
+    fn render_image(&mut self, pos: Point, image: &Image, size: Size) {
+        let mime = match image.format() {
+            ImageFormat::Raster(RasterFormat::Png) => "image/png",
+            ImageFormat::Raster(RasterFormat::Jpg) => "image/jpeg",
+            ImageFormat::Raster(RasterFormat::Gif) => "image/gif",
+            ImageFormat::Vector(VectorFormat::Svg) => "image/svg+xml",
+        };
+        let data = base64::engine::general_purpose::STANDARD.encode(image.data());
+        write!(
+            self.body,
+            "<img style=\"{}{}\" src=\"data:{mime};base64,{data}\"",
+            pos_css(pos),
+            size_css(size)
+        )
+        .unwrap();
+        if let Some(alt) = image.alt() {
+            write!(self.body, " alt=\"{}\"", escape(alt)).unwrap();
+        }
+        self.body.push('>');
+    }
+
+    /// Render links as transparent anchors. Other metadata is skipped.
+    fn render_meta(&mut self, pos: Point, meta: &Meta, size: Size) {
+        if let Meta::Link(Destination::Url(url)) = meta {
+            write!(
+                self.body,
+                "<a href=\"{}\" style=\"{}{}\"></a>",
+                escape(url),
+                pos_css(pos),
+                size_css(size)
+            )
+            .unwrap();
+        }
+    }
+
+    /// The CSS font family with which to refer to a font.
+    fn font_family(&mut self, font: &Font) -> EcoString {
+        if !self.embed_fonts {
+            return eco_format!("'{}'", font.info().family);
+        }
+
+        let next = self.fonts.len();
+        let id = *self.font_ids.entry(font.clone()).or_insert_with(|| {
+            self.fonts.push(font.clone());
+            next
+        });
+        eco_format!("typst-font-{id}")
+    }
+
+    /// Assemble the full HTML file.
+    fn finalize(self, title: Option<&str>) -> String {
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        if let Some(title) = title {
+            writeln!(html, "<title>{}</title>", escape(title)).unwrap();
+        }
+
+        html.push_str("<style>\n");
+        html.push_str(
+            "body { margin: 0; padding: 16px; background: #ccc; }\n\
+             .typst-page { position: relative; overflow: hidden; margin: 0 auto 16px; \
+             background: white; }\n\
+             .typst-page * { position: absolute; margin: 0; padding: 0; \
+             transform-origin: 0 0; }\n",
+        );
+        for (id, font) in self.fonts.iter().enumerate() {
+            let data = base64::engine::general_purpose::STANDARD.encode(font.data());
+            writeln!(
+                html,
+                "@font-face {{ font-family: typst-font-{id}; \
+                 src: url(data:font/otf;base64,{data}); }}"
+            )
+            .unwrap();
+        }
+        html.push_str("</style>\n</head>\n<body>\n");
+        html.push_str(&self.body);
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// Format a length in points.
+fn pt(abs: Abs) -> EcoString {
+    eco_format!("{}pt", abs.to_pt())
+}
+
+/// CSS for the position of an item.
+fn pos_css(pos: Point) -> EcoString {
+    eco_format!("left:{};top:{};", pt(pos.x), pt(pos.y))
+}
+
+/// CSS for the size of an item.
+fn size_css(size: Size) -> EcoString {
+    eco_format!("width:{};height:{};", pt(size.x), pt(size.y))
+}
+
+/// CSS for a transformation.
+fn transform_css(ts: Transform) -> EcoString {
+    eco_format!(
+        "transform:matrix({},{},{},{},{},{});",
+        ts.sx.get(),
+        ts.ky.get(),
+        ts.kx.get(),
+        ts.sy.get(),
+        // CSS matrices take their translation in pixels, which are three
+        // quarters of a point.
+        ts.tx.to_pt() * 4.0 / 3.0,
+        ts.ty.to_pt() * 4.0 / 3.0,
+    )
+}
+
+/// CSS for a paint.
+///
+/// Gradients and patterns are approximated by a solid color.
+fn paint_css(paint: &Paint) -> EcoString {
+    match paint {
+        Paint::Solid(color) => color.to_hex(),
+        Paint::Gradient(gradient) => gradient
+            .stops_ref()
+            .first()
+            .map_or("none".into(), |(color, _)| color.to_hex()),
+        Paint::Pattern(_) => "none".into(),
+    }
+}
+
+/// SVG attributes for a stroke.
+fn stroke_attrs(stroke: &FixedStroke) -> EcoString {
+    eco_format!(
+        " stroke=\"{}\" stroke-width=\"{}\"",
+        paint_css(&stroke.paint),
+        stroke.thickness.to_pt()
+    )
+}
+
+/// Escape text for use in HTML content and attribute values.
+fn escape(text: &str) -> EcoString {
+    let mut escaped = EcoString::new();
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::eval::Tracer;
+    use typst::layout::{Angle, Ratio};
+    use typst::testing::TestWorld;
+
+    use super::*;
+
+    /// Compile a document and export it to HTML.
+    fn export(world: &TestWorld, embed_fonts: bool) -> String {
+        let document = typst::compile(world, &mut Tracer::new()).unwrap();
+        html(&document, embed_fonts)
+    }
+
+    #[test]
+    fn test_html_pages_and_text() {
+        let world = TestWorld::new(
+            "#set document(title: \"A & B\")\n\
+             #set page(width: 100pt, height: 50pt, margin: 10pt)\n\
+             #set text(font: \"Linux Libertine\", fill: red)\n\
+             1 < 2 & \"b\"\n\
+             #pagebreak()\n\
+             #link(\"https://typst.app/?a=1&b=2\")[Link]",
+        );
+        let html = export(&world, false);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert_eq!(
+            html.matches("<div class=\"typst-page\" style=\"width:100pt;height:50pt;\">")
+                .count(),
+            2
+        );
+
+        // Text is escaped and keeps its font and fill.
+        assert!(html.contains(">1 &lt; 2 &amp; “b”</span>"));
+        assert!(html.contains("font-family:&#39;Linux Libertine&#39;;"));
+        assert!(html.contains("color:#ff4136;"));
+        assert!(!html.contains("@font-face"));
+
+        // Links become anchors with an escaped target.
+        assert!(html.contains("<a href=\"https://typst.app/?a=1&amp;b=2\""));
+    }
+
+    #[test]
+    fn test_html_embed_fonts() {
+        let world = TestWorld::new("Regular *Bold* Regular");
+        let html = export(&world, true);
+
+        // Every face is embedded once, in order of first use.
+        assert_eq!(html.matches("@font-face").count(), 2);
+        assert!(html.contains("@font-face { font-family: typst-font-0; "));
+        assert!(html.contains("@font-face { font-family: typst-font-1; "));
+        assert_eq!(html.matches("font-family:typst-font-0;").count(), 2);
+        assert_eq!(html.matches("font-family:typst-font-1;").count(), 1);
+    }
+
+    #[test]
+    fn test_html_shapes_and_groups() {
+        let world = TestWorld::new(
+            "#set page(width: 100pt, height: 100pt, margin: 0pt)\n\
+             #rect(width: 20pt, height: 10pt, fill: blue, stroke: 2pt + green)\n\
+             #rotate(90deg, box(clip: true, width: 10pt, height: 10pt, square(size: 20pt)))",
+        );
+        let html = export(&world, false);
+
+        assert!(html.contains(
+            "<path d=\"M0 0H20V10H0Z\" fill=\"#0074d9\" \
+             stroke=\"#2ecc40\" stroke-width=\"2\"/>"
+        ));
+        assert!(html.contains("transform:matrix("));
+        assert!(html.contains("overflow:hidden;"));
+    }
+
+    #[test]
+    fn test_html_transform() {
+        // The translation is converted from points to pixels.
+        let ts = Transform::translate(Abs::pt(3.0), Abs::pt(-6.0));
+        assert_eq!(transform_css(ts), "transform:matrix(1,0,0,1,4,-8);");
+
+        let ts = Transform::scale(Ratio::new(2.0), Ratio::new(0.5));
+        assert_eq!(transform_css(ts), "transform:matrix(2,0,0,0.5,0,0);");
+
+        let ts = Transform::rotate(Angle::deg(90.0));
+        let css = transform_css(ts);
+        let values: Vec<f64> = css["transform:matrix(".len()..css.len() - 2]
+            .split(',')
+            .map(|v| v.parse().unwrap())
+            .collect();
+        let expected = [0.0, 1.0, -1.0, 0.0, 0.0, 0.0];
+        assert!(values.iter().zip(expected).all(|(v, e)| (v - e).abs() < 1e-9));
+    }
+}
@@ -5,7 +5,7 @@ use typst::eval::{Tracer, Vm};
 use typst::foundations::{Context, Label, Scopes, Styles, Value};
 use typst::introspection::{Introspector, Locator};
 use typst::model::{BibliographyElem, Document};
-use typst::syntax::{ast, LinkedNode, Span, SyntaxKind};
+use typst::syntax::{ast, LinkedNode, SyntaxKind};
 use typst::World;
 
 /// Try to determine a set of possible values for an expression.
@@ -50,6 +50,9 @@ pub fn analyze_expr(
 
 /// Try to load a module from the current source file.
 pub fn analyze_import(world: &dyn World, source: &LinkedNode) -> Option<Value> {
+    // Use the span of the source expression so that relative paths resolve
+    // against the importing file.
+    let span = source.span();
     let (source, _) = analyze_expr(world, source).into_iter().next()?;
     if source.scope().is_some() {
         return Some(source);
@@ -67,13 +70,9 @@ pub fn analyze_import(world: &dyn World, source: &LinkedNode) -> Option<Value> {
     };
 
     let context = Context::none();
-    let mut vm = Vm::new(
-        engine,
-        context.track(),
-        Scopes::new(Some(world.library())),
-        Span::detached(),
-    );
-    typst::eval::import(&mut vm, source, Span::detached(), true)
+    let mut vm =
+        Vm::new(engine, context.track(), Scopes::new(Some(world.library())), span);
+    typst::eval::import(&mut vm, source, span, true)
         .ok()
         .map(Value::Module)
 }
@@ -10,7 +10,8 @@ use typst::foundations::{
 };
 use typst::model::Document;
 use typst::syntax::{
-    ast, is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
+    ast::{self, AstNode},
+    is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
 };
 use typst::text::RawElem;
 use typst::visualize::Color;
@@ -1369,6 +1370,28 @@ impl<'a> CompletionContext<'a> {
                     }
                 }
 
+                if let Some(v) = parent.cast::<ast::Closure>() {
+                    if node.span() == v.body().span() {
+                        for param in v.params().children() {
+                            match param {
+                                ast::Param::Pos(pattern) => {
+                                    for ident in pattern.bindings() {
+                                        defined.insert(ident.get().clone());
+                                    }
+                                }
+                                ast::Param::Named(n) => {
+                                    defined.insert(n.name().get().clone());
+                                }
+                                ast::Param::Spread(s) => {
+                                    if let Some(sink) = s.sink_ident() {
+                                        defined.insert(sink.get().clone());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 ancestor = Some(parent.clone());
                 continue;
             }
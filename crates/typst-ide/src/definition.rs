@@ -0,0 +1,346 @@
+use ecow::EcoString;
+use typst::syntax::ast::{self, AstNode};
+use typst::syntax::{FileId, LinkedNode, Source, Span, SyntaxKind};
+use typst::World;
+
+use crate::analyze::analyze_import;
+
+/// Find the definition of the identifier at a cursor position.
+///
+/// Names are resolved lexically on the syntax tree, so this also works for
+/// documents that currently fail to compile. The result is the span of the
+/// identifier that binds the name in a `let` binding, a closure parameter, a
+/// `for` loop or an import. Names imported from another file are followed
+/// into that file if the world can provide it, in which case the span points
+/// into the other file.
+///
+/// Returns `None` if there is no identifier at the cursor or if it refers to
+/// something from the standard library.
+pub fn definition(world: &dyn World, source: &Source, cursor: usize) -> Option<Span> {
+    let root = LinkedNode::new(source.root());
+    let mut leaf = root.leaf_at(cursor)?;
+    if !is_name(&leaf) {
+        // The cursor may also sit right before the identifier.
+        leaf = leaf.next_leaf().filter(|next| next.offset() == cursor)?;
+    }
+
+    if !is_name(&leaf) || is_field_or_argument_name(&leaf) {
+        return None;
+    }
+
+    if introduced_by_ancestor(&leaf) {
+        return Some(leaf.span());
+    }
+
+    let name = leaf.text().clone();
+    find_in_scopes(world, &leaf, &name, &mut vec![])
+}
+
+/// Whether the node is an identifier.
+fn is_name(node: &LinkedNode) -> bool {
+    matches!(node.kind(), SyntaxKind::Ident | SyntaxKind::MathIdent)
+}
+
+/// Whether the identifier is a field name or the name of a named argument
+/// rather than a variable.
+fn is_field_or_argument_name(leaf: &LinkedNode) -> bool {
+    let Some(parent) = leaf.parent() else { return false };
+    match parent.kind() {
+        SyntaxKind::FieldAccess => leaf.index() > 0,
+        SyntaxKind::Named => {
+            leaf.index() == 0 && parent.parent_kind() != Some(SyntaxKind::Params)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the identifier is itself bound by the construct it's part of, e.g.
+/// the `x` in `let x = 1`.
+fn introduced_by_ancestor(leaf: &LinkedNode) -> bool {
+    let mut ancestor = leaf.parent();
+    while let Some(node) = ancestor {
+        let bindings = if let Some(v) = node.cast::<ast::LetBinding>() {
+            v.kind().bindings()
+        } else if let Some(v) = node.cast::<ast::ForLoop>() {
+            v.pattern().bindings()
+        } else if let Some(v) = node.cast::<ast::Closure>() {
+            let mut bindings = param_bindings(v);
+            bindings.extend(v.name());
+            bindings
+        } else if let Some(v) = node.cast::<ast::ModuleImport>() {
+            let mut bindings: Vec<_> = match v.imports() {
+                Some(ast::Imports::Items(items)) => {
+                    items.iter().map(|item| item.bound_name()).collect()
+                }
+                _ => vec![],
+            };
+            bindings.extend(v.new_name());
+            bindings
+        } else {
+            ancestor = node.parent();
+            continue;
+        };
+
+        return bindings.iter().any(|ident| ident.span() == leaf.span());
+    }
+
+    false
+}
+
+/// Search the scopes surrounding a node for a binding of the name, from the
+/// innermost outwards.
+fn find_in_scopes(
+    world: &dyn World,
+    node: &LinkedNode,
+    name: &str,
+    visited: &mut Vec<FileId>,
+) -> Option<Span> {
+    let mut node = node.clone();
+    loop {
+        let mut sibling = node.prev_sibling();
+        while let Some(prev) = sibling {
+            if let Some(span) = find_in_binding(world, &prev, name, visited) {
+                return Some(span);
+            }
+            sibling = prev.prev_sibling();
+        }
+
+        let parent = node.parent()?.clone();
+        if let Some(v) = parent.cast::<ast::ForLoop>() {
+            if node.prev_sibling_kind() != Some(SyntaxKind::In) {
+                if let Some(ident) = find_ident(v.pattern().bindings(), name) {
+                    return Some(ident.span());
+                }
+            }
+        }
+
+        if let Some(v) = parent.cast::<ast::Closure>() {
+            if node.span() == v.body().span() {
+                // Parameters shadow the name of the closure itself.
+                if let Some(ident) = find_ident(param_bindings(v), name) {
+                    return Some(ident.span());
+                }
+                if let Some(ident) = v.name().filter(|ident| ident.as_str() == name) {
+                    return Some(ident.span());
+                }
+            }
+        }
+
+        node = parent;
+    }
+}
+
+/// Search the top level of another file for a binding of the name.
+fn find_in_file(
+    world: &dyn World,
+    id: FileId,
+    name: &str,
+    visited: &mut Vec<FileId>,
+) -> Option<Span> {
+    if visited.contains(&id) {
+        return None;
+    }
+
+    visited.push(id);
+    let source = world.source(id).ok()?;
+    let root = LinkedNode::new(source.root());
+    let mut child = root.children().last();
+    while let Some(node) = child {
+        if let Some(span) = find_in_binding(world, &node, name, visited) {
+            return Some(span);
+        }
+        child = node.prev_sibling();
+    }
+
+    None
+}
+
+/// Check whether a `let` binding or import binds the name.
+fn find_in_binding(
+    world: &dyn World,
+    node: &LinkedNode,
+    name: &str,
+    visited: &mut Vec<FileId>,
+) -> Option<Span> {
+    if let Some(v) = node.cast::<ast::LetBinding>() {
+        return find_ident(v.kind().bindings(), name).map(|ident| ident.span());
+    }
+
+    let v = node.cast::<ast::ModuleImport>()?;
+    let source = node.children().find(|child| child.is::<ast::Expr>())?;
+    let file = imported_file(&source);
+    match v.imports() {
+        None => {
+            let bound = match v.new_name() {
+                Some(ident) => EcoString::from(ident.as_str()),
+                None => module_name(&source)?,
+            };
+            if bound != name {
+                return None;
+            }
+
+            // Point to the start of the imported file if there is one.
+            let span = v.new_name().map_or(source.span(), |ident| ident.span());
+            Some(
+                file.and_then(|id| world.source(id).ok())
+                    .map_or(span, |source| source.root().span()),
+            )
+        }
+        Some(ast::Imports::Items(items)) => {
+            let item =
+                items.iter().rev().find(|item| item.bound_name().as_str() == name)?;
+            let original = item.original_name();
+            file.and_then(|id| find_in_file(world, id, &original, visited))
+                .or(Some(item.bound_name().span()))
+        }
+        Some(ast::Imports::Wildcard) => {
+            if let Some(id) = file {
+                return find_in_file(world, id, name, visited);
+            }
+
+            // Modules that aren't files, like `calc`, need to be evaluated to
+            // know what they define.
+            let module = analyze_import(world, &source)?;
+            module.scope()?.get(name)?;
+            Some(source.span())
+        }
+    }
+}
+
+/// Determine the file an import source refers to if it is a path literal.
+fn imported_file(source: &LinkedNode) -> Option<FileId> {
+    let ast::Expr::Str(path) = source.cast::<ast::Expr>()? else { return None };
+    let path = path.get();
+    if path.starts_with('@') {
+        return None;
+    }
+    Some(source.span().id()?.join(&path))
+}
+
+/// Determine the name a module is bound to by a plain import.
+fn module_name(source: &LinkedNode) -> Option<EcoString> {
+    match source.cast::<ast::Expr>()? {
+        ast::Expr::Ident(ident) => Some(ident.get().clone()),
+        ast::Expr::Str(path) => {
+            let path = path.get();
+            let stem = path.rsplit('/').next()?.split(':').next()?;
+            Some(stem.strip_suffix(".typ").unwrap_or(stem).into())
+        }
+        _ => None,
+    }
+}
+
+/// The identifiers bound by a closure's parameters.
+fn param_bindings<'a>(closure: ast::Closure<'a>) -> Vec<ast::Ident<'a>> {
+    closure
+        .params()
+        .children()
+        .flat_map(|param| match param {
+            ast::Param::Pos(pattern) => pattern.bindings(),
+            ast::Param::Named(named) => vec![named.name()],
+            ast::Param::Spread(spread) => spread.sink_ident().into_iter().collect(),
+        })
+        .collect()
+}
+
+/// Find the last identifier with the given name.
+fn find_ident<'a>(idents: Vec<ast::Ident<'a>>, name: &str) -> Option<ast::Ident<'a>> {
+    idents.into_iter().rev().find(|ident| ident.as_str() == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use typst::syntax::VirtualPath;
+    use typst::testing::TestWorld;
+    use typst::WorldExt;
+
+    use super::*;
+
+    /// Find the definition at a cursor position, counting from the end of the
+    /// text if negative.
+    fn test(world: &TestWorld, cursor: isize) -> Option<(FileId, Range<usize>)> {
+        let source = world.main();
+        let cursor = if cursor < 0 {
+            source.len_bytes().checked_add_signed(cursor + 1).unwrap()
+        } else {
+            cursor as usize
+        };
+        let span = definition(world, &source, cursor)?;
+        Some((span.id()?, world.range(span)?))
+    }
+
+    fn main() -> FileId {
+        FileId::new(None, VirtualPath::new("main.typ"))
+    }
+
+    #[test]
+    fn test_definition_local() {
+        let world = TestWorld::new("#let x = 1\n#x");
+        assert_eq!(test(&world, -1), Some((main(), 5..6)));
+        assert_eq!(test(&world, -2), Some((main(), 5..6)));
+        assert_eq!(test(&world, 5), Some((main(), 5..6)));
+
+        let world = TestWorld::new("#for (k, v) in (a: 1) [#v]");
+        assert_eq!(test(&world, -2), Some((main(), 9..10)));
+
+        let world = TestWorld::new("#let f(x, ..rest) = rest");
+        assert_eq!(test(&world, -1), Some((main(), 12..16)));
+    }
+
+    #[test]
+    fn test_definition_shadowed() {
+        let world = TestWorld::new("#let x = 1\n#{ let x = 2; x }");
+        assert_eq!(test(&world, -3), Some((main(), 18..19)));
+
+        let world = TestWorld::new("#let x = 1\n#let f(x) = x");
+        assert_eq!(test(&world, -1), Some((main(), 18..19)));
+
+        // The initializer still sees the outer binding.
+        let world = TestWorld::new("#let x = 1\n#let x = x + 1");
+        assert_eq!(test(&world, -5), Some((main(), 5..6)));
+    }
+
+    #[test]
+    fn test_definition_not_a_variable() {
+        let world = TestWorld::new("#rect(width: 1pt)");
+        assert_eq!(test(&world, 3), None);
+        assert_eq!(test(&world, 8), None);
+
+        let world = TestWorld::new("#let d = (a: 1)\n#d.a");
+        assert_eq!(test(&world, -1), None);
+    }
+
+    #[test]
+    fn test_definition_imported() {
+        let other = FileId::new(None, VirtualPath::new("other.typ"));
+        let text = "#let a = 1\n#let b = 2";
+
+        let world =
+            TestWorld::new("#import \"other.typ\": *\n#b").with_source("other.typ", text);
+        assert_eq!(test(&world, -1), Some((other, 16..17)));
+
+        let world = TestWorld::new("#import \"other.typ\": a as c\n#c")
+            .with_source("other.typ", text);
+        assert_eq!(test(&world, -1), Some((other, 5..6)));
+
+        let world = TestWorld::new("#import \"other.typ\"\n#other.b")
+            .with_source("other.typ", text);
+        assert_eq!(test(&world, -3), Some((other, 0..text.len())));
+
+        // Without the file, the import itself is the definition.
+        let world = TestWorld::new("#import \"missing.typ\": a\n#a");
+        assert_eq!(test(&world, -1), Some((main(), 23..24)));
+
+        let world = TestWorld::new("#import calc: *\n#pow");
+        assert_eq!(test(&world, -1), Some((main(), 8..12)));
+    }
+
+    #[test]
+    fn test_definition_cyclic_import() {
+        let world = TestWorld::new("#import \"other.typ\": *\n#b")
+            .with_source("other.typ", "#import \"main.typ\": *");
+        assert_eq!(test(&world, -1), None);
+    }
+}
@@ -109,6 +109,58 @@ pub fn jump_from_click(
     None
 }
 
+/// Find the calls of user-defined functions that produced the content at a
+/// click position on a page, starting with the innermost call.
+///
+/// The spans point to the call sites. Calls are only recorded when `typst`
+/// is built with the `debug` feature, otherwise this returns nothing.
+pub fn trace(document: &Document, page: NonZeroUsize, click: Point) -> Vec<Span> {
+    let mut calls = vec![];
+    if let Some(page) = document.pages.get(page.get() - 1) {
+        trace_frame(&page.frame, click, &mut calls);
+    }
+    calls.reverse();
+    calls
+}
+
+/// Collect the calls on the path to the item at the click position, starting
+/// with the outermost one. Returns whether there is such an item.
+fn trace_frame(frame: &Frame, click: Point, calls: &mut Vec<Span>) -> bool {
+    let start = calls.len();
+    for (pos, item) in frame.items() {
+        if let FrameItem::Meta(Meta::Call(span), size) = item {
+            if is_in_rect(*pos, *size, click) && !calls.contains(span) {
+                calls.push(*span);
+            }
+        }
+    }
+
+    for (pos, item) in frame.items().rev() {
+        let hit = match item {
+            // TODO: Handle transformation.
+            FrameItem::Group(group) => trace_frame(&group.frame, click - *pos, calls),
+            FrameItem::Text(text) => is_in_rect(
+                Point::new(pos.x, pos.y - text.size),
+                Size::new(text.width(), text.size),
+                click,
+            ),
+            FrameItem::Shape(shape, _) => match shape.geometry {
+                Geometry::Rect(size) => is_in_rect(*pos, size, click),
+                _ => false,
+            },
+            FrameItem::Image(_, size, _) => is_in_rect(*pos, *size, click),
+            FrameItem::Meta(..) => false,
+        };
+
+        if hit {
+            return true;
+        }
+    }
+
+    calls.truncate(start);
+    false
+}
+
 /// Find the output location in the document for a cursor position.
 pub fn jump_from_cursor(
     document: &Document,
@@ -164,3 +216,118 @@ fn is_in_rect(pos: Point, size: Size, click: Point) -> bool {
         && pos.y <= click.y
         && pos.y + size.y >= click.y
 }
+
+#[cfg(test)]
+mod tests {
+    use typst::layout::{Abs, Page};
+    use typst::visualize::{Color, Geometry};
+
+    use super::*;
+
+    #[test]
+    fn test_trace_path() {
+        // A shape in a group, with calls on both levels. The calls of other
+        // content are skipped.
+        let size = Size::splat(Abs::pt(10.0));
+        let source = Source::detached("*a* _b_ *c* _d_");
+        let spans: Vec<_> = source.root().children().map(|node| node.span()).collect();
+        let span = |i: usize| spans[i];
+        let mut inner = Frame::soft(size);
+        inner.push(Point::zero(), FrameItem::Meta(Meta::Call(span(3)), size));
+        inner.push(
+            Point::zero(),
+            FrameItem::Shape(
+                Geometry::Rect(size).filled(Color::BLACK.into()),
+                Span::detached(),
+            ),
+        );
+
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        let other = Size::splat(Abs::pt(5.0));
+        frame.push(Point::zero(), FrameItem::Meta(Meta::Call(span(1)), size * 10.0));
+        frame.push(Point::zero(), FrameItem::Meta(Meta::Call(span(2)), other));
+        frame.push_frame(Point::splat(Abs::pt(20.0)), inner);
+
+        let document = Document {
+            pages: vec![Page {
+                frame,
+                numbering: None,
+                number: 1,
+                bleed: Abs::zero(),
+                slug: Abs::zero(),
+            }],
+            ..Document::default()
+        };
+
+        let page = NonZeroUsize::new(1).unwrap();
+        let click = Point::splat(Abs::pt(25.0));
+        assert_eq!(trace(&document, page, click), [span(3), span(1)]);
+        assert!(trace(&document, page, Point::splat(Abs::pt(50.0))).is_empty());
+    }
+
+    /// Compile the text and find the calls that produced the content at each
+    /// occurrence of the needle.
+    #[cfg(feature = "debug")]
+    fn trace_all(text: &str, needle: &str) -> Vec<Vec<String>> {
+        use typst::eval::Tracer;
+        use typst::testing::TestWorld;
+        use typst::WorldExt;
+
+        let world = TestWorld::new(text);
+        let document = typst::compile(&world, &mut Tracer::new()).unwrap();
+        let source = world.main();
+        text.match_indices(needle)
+            .map(|(cursor, _)| {
+                let pos = jump_from_cursor(&document, &source, cursor + 1).unwrap();
+                let click = pos.point + Point::new(Abs::pt(1.0), Abs::pt(-2.0));
+                trace(&document, pos.page, click)
+                    .into_iter()
+                    .map(|span| text[world.range(span).unwrap()].to_string())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_trace_nested_calls() {
+        let text = "#let inner(body) = box(body)\n\
+                    #let card(body) = inner(body)\n\
+                    #let template(body) = card(body)\n\
+                    #template[Hello]";
+        assert_eq!(
+            trace_all(text, "Hello"),
+            [["inner(body)", "card(body)", "template[Hello]"]]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_trace_equal_content() {
+        // Equal content from different calls, or from no call at all, keeps
+        // its own call sites.
+        let text = "#let card(body) = box(body)\n\
+                    #let tile(body) = box(body)\n\
+                    #box[Same] #card[Same] #tile[Same] #card[Same]";
+        assert_eq!(
+            trace_all(text, "Same"),
+            [vec![], vec!["card[Same]"], vec!["tile[Same]"], vec!["card[Same]"]]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "debug")]
+    fn test_trace_keeps_values() {
+        use typst::eval::Tracer;
+        use typst::testing::TestWorld;
+
+        let world = TestWorld::new(
+            "#let card(body) = box(body)
+             #assert.eq(card[A], box[A])
+             #assert.eq(card[A].func(), box)
+             #assert.eq(repr(card[A]), repr(box[A]))
+             #assert.eq(card(strong[A]).body.func(), strong)",
+        );
+        typst::compile(&world, &mut Tracer::new()).unwrap();
+    }
+}
@@ -2,13 +2,19 @@
 
 mod analyze;
 mod complete;
+mod definition;
 mod jump;
+mod search;
 mod tooltip;
+mod words;
 
 pub use self::analyze::analyze_labels;
 pub use self::complete::{autocomplete, Completion, CompletionKind};
-pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
+pub use self::definition::definition;
+pub use self::jump::{jump_from_click, jump_from_cursor, trace, Jump};
+pub use self::search::{search, Match};
 pub use self::tooltip::{tooltip, Tooltip};
+pub use self::words::{words, WordKind};
 
 use std::fmt::Write;
 
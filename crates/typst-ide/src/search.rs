@@ -0,0 +1,301 @@
+use std::num::NonZeroUsize;
+use std::ops::Range;
+
+use typst::layout::{Abs, Frame, FrameItem, Point, Size, Transform};
+use typst::model::Document;
+use typst::syntax::{FileId, SyntaxKind};
+use typst::text::Glyph;
+use typst::World;
+
+/// A [search](search) result in a document.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Match {
+    /// The page the match is on, starting at one.
+    pub page: NonZeroUsize,
+    /// The rectangles covering the match, one per line, given as the position
+    /// of their top left corner and their size. For transformed text, these
+    /// are the bounding boxes of the transformed lines.
+    pub rects: Vec<(Point, Size)>,
+    /// The file and byte range of the matched text in the source, if it can be
+    /// traced back to a single source file.
+    pub source: Option<(FileId, Range<usize>)>,
+}
+
+/// Find all occurrences of a query in the text of a document.
+///
+/// The search is case-insensitive and treats any run of whitespace in the
+/// query as a single space, which also matches a line break in the document.
+/// Thus, a phrase that is split across two lines is found as one match with
+/// two rectangles.
+pub fn search(world: &dyn World, document: &Document, query: &str) -> Vec<Match> {
+    let mut needle: Vec<char> = vec![];
+    fold(query.trim(), |c| {
+        if c != ' ' || needle.last() != Some(&' ') {
+            needle.push(c);
+        }
+    });
+    if needle.is_empty() {
+        return vec![];
+    }
+
+    let mut matches = vec![];
+    for (i, page) in document.pages.iter().enumerate() {
+        let mut text = PageText::default();
+        text.frame(&page.frame, Transform::identity());
+        for range in text.find(&needle) {
+            let glyphs = text.glyphs_in(range);
+            matches.push(Match {
+                page: NonZeroUsize::new(i + 1).unwrap(),
+                rects: rects(&glyphs),
+                source: source(world, &glyphs),
+            });
+        }
+    }
+
+    matches
+}
+
+/// The searchable text of a page.
+#[derive(Default)]
+struct PageText<'a> {
+    /// The case-folded characters along with the index of the glyph they
+    /// belong to. Spaces inserted at line breaks don't belong to a glyph.
+    chars: Vec<(char, Option<usize>)>,
+    /// The glyphs on the page in the order of the text.
+    glyphs: Vec<Placed<'a>>,
+}
+
+/// A glyph along with its position on the page.
+struct Placed<'a> {
+    /// The glyph itself.
+    glyph: &'a Glyph,
+    /// Maps from the coordinate system of the glyph's text run, whose origin
+    /// is at the start of the run's baseline, to the page.
+    ts: Transform,
+    /// The position of the glyph's left end in its text run.
+    x: Abs,
+    /// The advance width of the glyph.
+    width: Abs,
+    /// The font size of the glyph.
+    size: Abs,
+}
+
+impl Placed<'_> {
+    /// Whether a text run with the given transform continues on the same line
+    /// as this glyph, that is, starts on its baseline.
+    fn same_line(&self, ts: Transform) -> bool {
+        self.ts.invert().is_some_and(|inv| {
+            Point::zero().transform(ts).transform(inv).y.approx_eq(Abs::zero())
+        })
+    }
+
+    /// The corners of the glyph's box on the page, reaching from its baseline
+    /// up by the font size.
+    fn corners(&self) -> [Point; 4] {
+        let (start, end) = (self.x, self.x + self.width);
+        [
+            Point::new(start, Abs::zero()),
+            Point::new(end, Abs::zero()),
+            Point::new(start, -self.size),
+            Point::new(end, -self.size),
+        ]
+        .map(|point| point.transform(self.ts))
+    }
+}
+
+impl<'a> PageText<'a> {
+    /// Collect the text in a frame that is placed with the given transform.
+    fn frame(&mut self, frame: &'a Frame, ts: Transform) {
+        for (pos, item) in frame.items() {
+            let ts = ts.pre_concat(Transform::translate(pos.x, pos.y));
+            match item {
+                FrameItem::Group(group) => {
+                    self.frame(&group.frame, ts.pre_concat(group.transform))
+                }
+                FrameItem::Text(text) => {
+                    // Text on another line is separated by a space.
+                    if self.glyphs.last().is_some_and(|last| !last.same_line(ts)) {
+                        self.space(None);
+                    }
+
+                    let mut x = Abs::zero();
+                    let mut prev = None;
+                    for glyph in &text.glyphs {
+                        let width = glyph.x_advance.at(text.size);
+                        let index = self.glyphs.len();
+                        let size = text.size;
+                        self.glyphs.push(Placed { glyph, ts, x, width, size });
+                        x += width;
+
+                        // Multiple glyphs of one cluster share the same text.
+                        if prev == Some(&glyph.range) {
+                            continue;
+                        }
+                        prev = Some(&glyph.range);
+
+                        fold(&text.text[glyph.range()], |c| {
+                            if c == ' ' {
+                                self.space(Some(index));
+                            } else {
+                                self.chars.push((c, Some(index)));
+                            }
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Add a space unless the text already ends with one.
+    fn space(&mut self, glyph: Option<usize>) {
+        if self.chars.last().is_some_and(|&(c, _)| c != ' ') {
+            self.chars.push((' ', glyph));
+        }
+    }
+
+    /// Find the non-overlapping occurrences of a folded needle.
+    fn find(&self, needle: &[char]) -> Vec<Range<usize>> {
+        let mut ranges = vec![];
+        let mut i = 0;
+        while i + needle.len() <= self.chars.len() {
+            let window = &self.chars[i..i + needle.len()];
+            if window.iter().map(|&(c, _)| c).eq(needle.iter().copied()) {
+                ranges.push(i..i + needle.len());
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+        ranges
+    }
+
+    /// The glyphs that a range of characters belongs to.
+    fn glyphs_in(&self, range: Range<usize>) -> Vec<&Placed<'a>> {
+        let mut indices: Vec<usize> =
+            self.chars[range].iter().filter_map(|&(_, glyph)| glyph).collect();
+        indices.dedup();
+        indices.into_iter().map(|i| &self.glyphs[i]).collect()
+    }
+}
+
+/// Case-fold text and turn whitespace into spaces.
+fn fold(text: &str, mut f: impl FnMut(char)) {
+    for c in text.chars() {
+        if c.is_whitespace() {
+            f(' ');
+        } else {
+            c.to_lowercase().for_each(&mut f);
+        }
+    }
+}
+
+/// Compute the rectangles covering glyphs, one per line.
+fn rects(glyphs: &[&Placed]) -> Vec<(Point, Size)> {
+    let mut lines: Vec<(&Placed, Point, Point)> = vec![];
+    for &placed in glyphs {
+        let corners = placed.corners();
+        let min = corners.into_iter().reduce(|a, b| a.min(b)).unwrap();
+        let max = corners.into_iter().reduce(|a, b| a.max(b)).unwrap();
+        match lines.last_mut() {
+            Some((first, start, end)) if first.same_line(placed.ts) => {
+                *start = start.min(min);
+                *end = end.max(max);
+            }
+            _ => lines.push((placed, min, max)),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(_, start, end)| (start, (end - start).to_size()))
+        .collect()
+}
+
+/// Trace glyphs back to a range in their source file.
+fn source(world: &dyn World, glyphs: &[&Placed]) -> Option<(FileId, Range<usize>)> {
+    let (id, start) = offset(world, glyphs.first()?.glyph, false)?;
+    let (end_id, end) = offset(world, glyphs.last()?.glyph, true)?;
+    (id == end_id && start <= end).then_some((id, start..end))
+}
+
+/// The source offset of the start or end of a glyph.
+fn offset(world: &dyn World, glyph: &Glyph, end: bool) -> Option<(FileId, usize)> {
+    let (span, span_offset) = glyph.span;
+    let id = span.id()?;
+    let source = world.source(id).ok()?;
+    let node = source.find(span)?;
+    let range = node.range();
+    let offset = if node.kind() == SyntaxKind::Text {
+        let mut offset = range.start + usize::from(span_offset);
+        if end {
+            offset += glyph.range().len();
+        }
+        offset.min(range.end)
+    } else if end {
+        range.end
+    } else {
+        range.start
+    };
+    Some((id, offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::eval::Tracer;
+    use typst::testing::TestWorld;
+
+    use super::*;
+
+    fn test(text: &str, query: &str) -> (TestWorld, Vec<Match>) {
+        let world = TestWorld::new(text);
+        let document = typst::compile(&world, &mut Tracer::new()).unwrap();
+        let matches = search(&world, &document, query);
+        (world, matches)
+    }
+
+    #[test]
+    fn test_search_case_insensitive() {
+        let text = "Typst is typeset. TYPST! Ärger ärger";
+        let (world, matches) = test(text, "typst");
+        assert_eq!(matches.len(), 2);
+        assert!(matches.iter().all(|m| m.page.get() == 1 && m.rects.len() == 1));
+        let id = world.main().id();
+        assert_eq!(matches[0].source, Some((id, 0..5)));
+        assert_eq!(matches[1].source, Some((id, 18..23)));
+        assert_eq!(test(text, "ÄRGER").1.len(), 2);
+        assert!(test(text, "  ").1.is_empty());
+    }
+
+    #[test]
+    fn test_search_across_lines() {
+        let text = "#block(width: 40pt)[Quick brown fox]";
+        let (world, matches) = test(text, "BROWN\n  fox");
+        assert_eq!(matches.len(), 1);
+        let rects = &matches[0].rects;
+        assert_eq!(rects.len(), 2);
+        assert!(rects[0].0.y < rects[1].0.y);
+        assert_eq!(matches[0].source, Some((world.main().id(), 26..35)));
+    }
+
+    #[test]
+    fn test_search_pages() {
+        let (_, matches) = test("Hello #pagebreak() Hello", "hello");
+        let pages: Vec<_> = matches.iter().map(|m| m.page.get()).collect();
+        assert_eq!(pages, [1, 2]);
+    }
+
+    #[test]
+    fn test_search_transformed() {
+        let size = |text| test(text, "hello").1[0].rects[0].1;
+        let plain = size("Hello");
+        let rotated = size("#rotate(90deg, reflow: true)[Hello]");
+        let scaled = size("#scale(200%, reflow: true)[Hello]");
+        assert!(rotated.x.approx_eq(plain.y) && rotated.y.approx_eq(plain.x));
+        assert!(scaled.x.approx_eq(2.0 * plain.x) && scaled.y.approx_eq(2.0 * plain.y));
+
+        // Transformed text is still part of the text around it.
+        let (_, matches) = test("Hello #scale(x: 50%)[brave] world", "hello brave world");
+        assert_eq!(matches.len(), 1);
+    }
+}
@@ -0,0 +1,230 @@
+use std::ops::Range;
+
+use typst::syntax::{Source, SyntaxKind, SyntaxNode};
+
+/// Find the words in a source file along with the kind of text they are in.
+///
+/// This is meant for spellcheckers, which can pick the kinds of text they are
+/// interested in, typically just prose. Apostrophes and hyphens between
+/// letters don't split words, so `don't` and `well-known` are single words.
+/// Math, comments, and markup syntax like labels and references are skipped.
+///
+/// The ranges are byte ranges into the source text.
+pub fn words(source: &Source) -> Vec<(Range<usize>, WordKind)> {
+    let mut finder = WordFinder { text: source.text(), words: vec![], run: None };
+    finder.node(source.root(), 0, false);
+    finder.flush();
+    finder.words
+}
+
+/// The kind of text a word is in.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WordKind {
+    /// Prose in markup.
+    Prose,
+    /// The text of a raw block or inline raw text.
+    Raw,
+    /// An identifier in code, for example the name of a function. The whole
+    /// identifier is one word.
+    Code,
+    /// The contents of a string literal.
+    Str,
+}
+
+/// Walks a syntax tree and collects its words.
+struct WordFinder<'a> {
+    text: &'a str,
+    words: Vec<(Range<usize>, WordKind)>,
+    /// The range of adjacent prose leaves that are split into words together.
+    run: Option<Range<usize>>,
+}
+
+impl WordFinder<'_> {
+    fn node(&mut self, node: &SyntaxNode, offset: usize, in_raw: bool) {
+        let range = offset..offset + node.len();
+        match node.kind() {
+            SyntaxKind::Equation
+            | SyntaxKind::Label
+            | SyntaxKind::Ref
+            | SyntaxKind::Link
+            | SyntaxKind::LineComment
+            | SyntaxKind::BlockComment => {}
+            SyntaxKind::Text if in_raw => self.split(range, WordKind::Raw),
+            SyntaxKind::Text => self.prose(range),
+            SyntaxKind::SmartQuote if node.text() == "'" => self.prose(range),
+            SyntaxKind::Ident => self.push(range, WordKind::Code),
+            SyntaxKind::Str => self.string(range),
+            kind => {
+                let mut offset = offset;
+                for child in node.children() {
+                    self.node(child, offset, in_raw || kind == SyntaxKind::Raw);
+                    offset += child.len();
+                }
+            }
+        }
+    }
+
+    /// Add a prose leaf, joining it with the previous one if they are
+    /// adjacent.
+    fn prose(&mut self, range: Range<usize>) {
+        match &mut self.run {
+            Some(run) if run.end == range.start => run.end = range.end,
+            _ => {
+                self.flush();
+                self.run = Some(range);
+            }
+        }
+    }
+
+    /// Split a string literal into words, without its quotes and escapes.
+    fn string(&mut self, range: Range<usize>) {
+        self.flush();
+        let inner = range.start + 1..range.end - 1;
+        let mut masked = self.text[inner.clone()].to_string();
+
+        // Blank out escape sequences like `\n` or `\u{200b}` so that they
+        // don't become part of a word. Their length stays the same so that
+        // the ranges still match the source.
+        let mut i = 0;
+        while let Some(found) = masked[i..].find('\\') {
+            let start = i + found;
+            let rest = &masked[start + 1..];
+            let len = match rest.chars().next() {
+                Some('u') if rest[1..].starts_with('{') => {
+                    rest.find('}').map_or(rest.len(), |end| end + 1)
+                }
+                Some(c) => c.len_utf8(),
+                None => 0,
+            };
+            let end = start + 1 + len;
+            masked.replace_range(start..end, &" ".repeat(end - start));
+            i = end;
+        }
+
+        self.tokenize(&masked, inner.start, WordKind::Str);
+    }
+
+    /// Split the source text in a range into words.
+    fn split(&mut self, range: Range<usize>, kind: WordKind) {
+        self.flush();
+        let text = self.text;
+        self.tokenize(&text[range.clone()], range.start, kind);
+    }
+
+    /// Add a range as a single word.
+    fn push(&mut self, range: Range<usize>, kind: WordKind) {
+        self.flush();
+        self.words.push((range, kind));
+    }
+
+    /// Split the pending prose run into words.
+    fn flush(&mut self) {
+        if let Some(run) = self.run.take() {
+            let text = self.text;
+            self.tokenize(&text[run.clone()], run.start, WordKind::Prose);
+        }
+    }
+
+    /// Split text that starts at `offset` in the source into words.
+    fn tokenize(&mut self, text: &str, offset: usize, kind: WordKind) {
+        let mut start = None;
+        let mut end = 0;
+        let mut chars = text.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if c.is_alphanumeric() {
+                start.get_or_insert(i);
+                end = i + c.len_utf8();
+                continue;
+            }
+
+            // Apostrophes and hyphens are part of a word if they are
+            // surrounded by letters.
+            let joins = matches!(c, '\'' | '’' | '-' | '‐')
+                && start.is_some()
+                && end == i
+                && chars.peek().is_some_and(|&(_, next)| next.is_alphanumeric());
+
+            if !joins {
+                if let Some(start) = start.take() {
+                    self.words.push((offset + start..offset + end, kind));
+                }
+            }
+        }
+
+        if let Some(start) = start {
+            self.words.push((offset + start..offset + end, kind));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test(text: &str) -> Vec<(&str, WordKind)> {
+        let source = Source::detached(text);
+        words(&source)
+            .into_iter()
+            .map(|(range, kind)| (&text[range], kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_words_prose() {
+        use WordKind::Prose;
+        assert_eq!(
+            test("Don't split well-known words -- or *bold*ness."),
+            [
+                ("Don't", Prose),
+                ("split", Prose),
+                ("well-known", Prose),
+                ("words", Prose),
+                ("or", Prose),
+                ("bold", Prose),
+                ("ness", Prose),
+            ]
+        );
+        assert_eq!(
+            test("Grüße 'aus' Köln"),
+            [("Grüße", Prose), ("aus", Prose), ("Köln", Prose)]
+        );
+        assert_eq!(test("A $x + y$ <label> @ref // note"), [("A", Prose)]);
+    }
+
+    #[test]
+    fn test_words_mixed() {
+        use WordKind::*;
+        assert_eq!(
+            test("= Intro\nSee `raw text` and\n```rust\nfn main() {}\n```\n#text(fill: red, \"Hi\\nthere\")[More]"),
+            [
+                ("Intro", Prose),
+                ("See", Prose),
+                ("raw", Raw),
+                ("text", Raw),
+                ("and", Prose),
+                ("fn", Raw),
+                ("main", Raw),
+                ("text", Code),
+                ("fill", Code),
+                ("red", Code),
+                ("Hi", Str),
+                ("there", Str),
+                ("More", Prose),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_ranges() {
+        let text = "#let my-var = 1\nÄpfel's peel";
+        let source = Source::detached(text);
+        assert_eq!(
+            words(&source),
+            [
+                (5..11, WordKind::Code),
+                (16..24, WordKind::Prose),
+                (25..29, WordKind::Prose),
+            ]
+        );
+    }
+}
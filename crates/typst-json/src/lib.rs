@@ -0,0 +1,441 @@
+//! Export of the structure of Typst content as JSON.
+//!
+//! Unlike the other exporters, this one works on the evaluated content of a
+//! document rather than on its laid out frames. The result describes what the
+//! document consists of, for instance to feed it into a static site generator.
+//! Since show rules only apply during layout, the content appears as written.
+//!
+//! # Schema
+//! The output is an object with these fields:
+//! - `title`: The title set with `{set document(title: ..)}` as plain text, or
+//!   `null`.
+//! - `author`: The authors set with `{set document(author: ..)}`, an array of
+//!   strings.
+//! - `children`: The document's nodes, an array.
+//!
+//! Each node is an object whose `kind` field determines its other fields. The
+//! `label` field is only present if the element is labelled.
+//! - `heading`: `level` (a number starting at one), `text`, `label`.
+//! - `paragraph`: `text`. Inline markup like emphasis is reduced to its text.
+//! - `list` and `enum`: `items`, an array of arrays of nodes, one per item.
+//! - `terms`: `items`, an array of objects with a `term` string and a
+//!   `description` array of nodes.
+//! - `figure`: `caption` (a string or `null`), `label`, and `children`, the
+//!   nodes in the figure's body.
+//! - `raw`: `lang` (a string or `null`), `text`, `label`. Only for raw blocks,
+//!   inline raw text is part of its paragraph.
+//! - `equation`: `text`, `label`. Only for block equations.
+//! - `metadata`: `value`, the metadata's value as in `typst query`, `label`.
+//!
+//! The content of other elements is flattened into the surrounding nodes.
+
+use ecow::EcoString;
+use serde::Serialize;
+use typst::foundations::{
+    Content, IntoValue, SequenceElem, StyleChain, StyledElem, Value,
+};
+use typst::introspection::MetadataElem;
+use typst::layout::LayoutMultiple;
+use typst::math::EquationElem;
+use typst::model::{
+    DocumentElem, EnumItem, FigureElem, HeadingElem, ListItem, ParbreakElem, TermItem,
+};
+use typst::text::{LinebreakElem, RawElem, SpaceElem, TextElem};
+
+/// Export the structure of content into a pretty-printed JSON string.
+#[typst_macros::time(name = "json")]
+pub fn json(content: &Content) -> String {
+    serde_json::to_string_pretty(&structure(content)).unwrap()
+}
+
+/// Extract the structure of content.
+pub fn structure(content: &Content) -> Structure {
+    let mut builder = Builder::default();
+    builder.content(content, StyleChain::default());
+    let children = builder.finish();
+    Structure {
+        title: builder.title,
+        author: builder.author,
+        children,
+    }
+}
+
+/// The structure of a document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Structure {
+    /// The document's title.
+    pub title: Option<EcoString>,
+    /// The document's authors.
+    pub author: Vec<EcoString>,
+    /// The document's nodes.
+    pub children: Vec<Node>,
+}
+
+/// A structural part of a document.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Node {
+    /// A section heading.
+    Heading {
+        level: usize,
+        text: EcoString,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<EcoString>,
+    },
+    /// A paragraph of text.
+    Paragraph { text: EcoString },
+    /// A bullet list.
+    List { items: Vec<Vec<Node>> },
+    /// A numbered list.
+    Enum { items: Vec<Vec<Node>> },
+    /// A term list.
+    Terms { items: Vec<Term> },
+    /// A figure with its body.
+    Figure {
+        caption: Option<EcoString>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<EcoString>,
+        children: Vec<Node>,
+    },
+    /// A raw block.
+    Raw {
+        lang: Option<EcoString>,
+        text: EcoString,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<EcoString>,
+    },
+    /// A block equation.
+    Equation {
+        text: EcoString,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<EcoString>,
+    },
+    /// Metadata embedded into the document.
+    Metadata {
+        value: Value,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<EcoString>,
+    },
+}
+
+/// An item in a term list.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Term {
+    /// The term that is described.
+    pub term: EcoString,
+    /// The description of the term.
+    pub description: Vec<Node>,
+}
+
+/// Collects the nodes of content.
+#[derive(Default)]
+struct Builder {
+    title: Option<EcoString>,
+    author: Vec<EcoString>,
+    nodes: Vec<Node>,
+    /// The text of the current paragraph.
+    par: EcoString,
+}
+
+impl Builder {
+    /// Add content to the structure.
+    fn content(&mut self, content: &Content, styles: StyleChain) {
+        let label = content.label().map(|label| label.as_str().into());
+        if let Some(sequence) = content.to_packed::<SequenceElem>() {
+            for child in &sequence.children {
+                self.content(child, styles);
+            }
+        } else if let Some(styled) = content.to_packed::<StyledElem>() {
+            let styles = styles.chain(&styled.styles);
+            if let Some(title) = DocumentElem::title_in(styles) {
+                self.title = Some(title.plain_text());
+            }
+            if let Value::Array(author) = DocumentElem::author_in(styles).into_value() {
+                if !author.is_empty() {
+                    self.author =
+                        author.into_iter().filter_map(|v| v.cast().ok()).collect();
+                }
+            }
+            self.content(&styled.child, styles);
+        } else if content.is::<TextElem>() || content.is::<SpaceElem>() {
+            self.par.push_str(&content.plain_text());
+        } else if content.is::<LinebreakElem>() {
+            self.par.push(' ');
+        } else if content.is::<ParbreakElem>() {
+            self.flush();
+        } else if let Some(heading) = content.to_packed::<HeadingElem>() {
+            self.push(Node::Heading {
+                level: heading.resolve_level(styles).get(),
+                text: heading.body().plain_text(),
+                label,
+            });
+        } else if let Some(item) = content.to_packed::<ListItem>() {
+            let nodes = Self::nested(item.body(), styles);
+            self.item(Node::List { items: vec![nodes] });
+        } else if let Some(item) = content.to_packed::<EnumItem>() {
+            let nodes = Self::nested(item.body(), styles);
+            self.item(Node::Enum { items: vec![nodes] });
+        } else if let Some(item) = content.to_packed::<TermItem>() {
+            let term = Term {
+                term: item.term().plain_text(),
+                description: Self::nested(item.description(), styles),
+            };
+            self.item(Node::Terms { items: vec![term] });
+        } else if let Some(figure) = content.to_packed::<FigureElem>() {
+            self.push(Node::Figure {
+                caption: figure
+                    .caption(styles)
+                    .as_ref()
+                    .map(|caption| caption.body().plain_text()),
+                label,
+                children: Self::nested(figure.body(), styles),
+            });
+        } else if let Some(raw) = content.to_packed::<RawElem>() {
+            if raw.block(styles) {
+                self.push(Node::Raw {
+                    lang: raw.lang(styles).clone(),
+                    text: content.plain_text(),
+                    label,
+                });
+            } else {
+                self.par.push_str(&content.plain_text());
+            }
+        } else if let Some(equation) = content.to_packed::<EquationElem>() {
+            if equation.block(styles) {
+                self.push(Node::Equation { text: content.plain_text(), label });
+            } else {
+                self.par.push_str(&content.plain_text());
+            }
+        } else if let Some(metadata) = content.to_packed::<MetadataElem>() {
+            self.push(Node::Metadata { value: metadata.value.clone(), label });
+        } else {
+            self.other(content, styles);
+        }
+    }
+
+    /// Add the content in the fields of an element that isn't part of the
+    /// structure. The parts of block-level elements, like the cells of a
+    /// table, end up in separate paragraphs.
+    fn other(&mut self, content: &Content, styles: StyleChain) {
+        let block = content.can::<dyn LayoutMultiple>();
+        for (_, value) in content.fields() {
+            let children = match value {
+                Value::Content(content) => vec![content],
+                Value::Array(array) => {
+                    array.into_iter().filter_map(|v| v.cast().ok()).collect()
+                }
+                _ => continue,
+            };
+            for child in children {
+                self.content(&child, styles);
+                if block {
+                    self.flush();
+                }
+            }
+        }
+    }
+
+    /// Collect the nodes of nested content, like the body of a list item.
+    fn nested(content: &Content, styles: StyleChain) -> Vec<Node> {
+        let mut inner = Builder::default();
+        inner.content(content, styles);
+        inner.finish()
+    }
+
+    /// Add a list with a single item, joining it with the previous list of
+    /// the same kind if nothing came in between.
+    fn item(&mut self, node: Node) {
+        let node = match (self.nodes.last_mut(), node) {
+            (Some(Node::List { items }), Node::List { items: new })
+            | (Some(Node::Enum { items }), Node::Enum { items: new })
+                if self.par.trim().is_empty() =>
+            {
+                items.extend(new);
+                return;
+            }
+            (Some(Node::Terms { items }), Node::Terms { items: new })
+                if self.par.trim().is_empty() =>
+            {
+                items.extend(new);
+                return;
+            }
+            (_, node) => node,
+        };
+        self.push(node);
+    }
+
+    /// Add a node after the current paragraph.
+    fn push(&mut self, node: Node) {
+        self.flush();
+        self.nodes.push(node);
+    }
+
+    /// End the current paragraph.
+    fn flush(&mut self) {
+        let text: Vec<_> = self.par.split_whitespace().collect();
+        if !text.is_empty() {
+            self.nodes.push(Node::Paragraph { text: text.join(" ").into() });
+        }
+        self.par.clear();
+    }
+
+    /// Finish the structure.
+    fn finish(&mut self) -> Vec<Node> {
+        self.flush();
+        std::mem::take(&mut self.nodes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comemo::Track;
+    use typst::engine::Route;
+    use typst::eval::Tracer;
+    use typst::testing::TestWorld;
+    use typst::World;
+
+    use super::*;
+
+    /// Evaluate a source file and export its structure.
+    fn export(text: &str) -> String {
+        let world = TestWorld::new(text);
+        let module = typst::eval::eval(
+            (&world as &dyn World).track(),
+            Route::default().track(),
+            Tracer::new().track_mut(),
+            &world.main(),
+        )
+        .unwrap();
+        json(&module.content())
+    }
+
+    #[test]
+    fn test_json_document() {
+        let output = export(
+            "#set document(title: [Report], author: \"Jane\")\n\
+             = Introduction <intro>\n\
+             Some *bold* and `inline` text\\\n\
+             on two lines.\n\n\
+             - First\n\
+             - Second\n\
+             \x20 + Nested\n\n\
+             / Term: Described.\n\
+             #figure(caption: [A plot.])[Plot] <plot>\n\
+             ```rust\nfn main() {}\n```\n\
+             #metadata((answer: 42)) <data>\n\
+             $ x = 1 $",
+        );
+
+        assert_eq!(
+            output,
+            r#"{
+  "title": "Report",
+  "author": [
+    "Jane"
+  ],
+  "children": [
+    {
+      "kind": "heading",
+      "level": 1,
+      "text": "Introduction",
+      "label": "intro"
+    },
+    {
+      "kind": "paragraph",
+      "text": "Some bold and inline text on two lines."
+    },
+    {
+      "kind": "list",
+      "items": [
+        [
+          {
+            "kind": "paragraph",
+            "text": "First"
+          }
+        ],
+        [
+          {
+            "kind": "paragraph",
+            "text": "Second"
+          },
+          {
+            "kind": "enum",
+            "items": [
+              [
+                {
+                  "kind": "paragraph",
+                  "text": "Nested"
+                }
+              ]
+            ]
+          }
+        ]
+      ]
+    },
+    {
+      "kind": "terms",
+      "items": [
+        {
+          "term": "Term",
+          "description": [
+            {
+              "kind": "paragraph",
+              "text": "Described."
+            }
+          ]
+        }
+      ]
+    },
+    {
+      "kind": "figure",
+      "caption": "A plot.",
+      "label": "plot",
+      "children": [
+        {
+          "kind": "paragraph",
+          "text": "Plot"
+        }
+      ]
+    },
+    {
+      "kind": "raw",
+      "lang": "rust",
+      "text": "fn main() {}"
+    },
+    {
+      "kind": "metadata",
+      "value": {
+        "answer": 42
+      },
+      "label": "data"
+    },
+    {
+      "kind": "equation",
+      "text": "x = 1"
+    }
+  ]
+}"#
+        );
+
+        // The output is valid JSON with the documented keys.
+        let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let children = value["children"].as_array().unwrap();
+        assert!(children.iter().all(|child| child["kind"].is_string()));
+        assert_eq!(children[0]["level"], 1);
+        assert_eq!(value["title"], "Report");
+    }
+
+    #[test]
+    fn test_json_table() {
+        let output = export("#table(columns: 2)[A][B]\n\nAfter the table.");
+        let structure: serde_json::Value = serde_json::from_str(&output).unwrap();
+        let texts: Vec<_> = structure["children"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|child| child["text"].as_str().unwrap())
+            .collect();
+        assert_eq!(texts, ["A", "B", "After the table."]);
+        assert!(structure["title"].is_null());
+    }
+}
@@ -319,7 +319,14 @@ fn create_wrapper_closure(func: &Func) -> TokenStream {
     };
 
     // Throws errors about unexpected arguments.
-    let finish = (!func.special.args).then(|| quote! { args.take().finish()?; });
+    let finish = (!func.special.args).then(|| {
+        let names = func
+            .params
+            .iter()
+            .filter(|param| param.named)
+            .map(|param| &param.name);
+        quote! { args.take().finish_with([#(#names),*])?; }
+    });
 
     // This is the actual function call.
     let call = {
@@ -1,15 +1,17 @@
+use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{CidFontType, FontFlags, SystemInfo, UnicodeCmap};
 use pdf_writer::{Filter, Finish, Name, Rect, Str};
-use ttf_parser::{name_id, GlyphId, Tag};
+use ttf_parser::{name_id, GlyphId, Permissions, Tag};
+use typst::diag::StrResult;
 use typst::text::Font;
 use typst::util::SliceExt;
 use unicode_properties::{GeneralCategory, UnicodeGeneralCategory};
 
-use crate::{deflate, EmExt, PdfContext};
+use crate::{deflate, EmExt, ExportError, PdfContext};
 
 const CFF: Tag = Tag::from_bytes(b"CFF ");
 const CFF2: Tag = Tag::from_bytes(b"CFF2");
@@ -22,7 +24,7 @@ const SYSTEM_INFO: SystemInfo = SystemInfo {
 
 /// Embed all used fonts into the PDF.
 #[typst_macros::time(name = "write fonts")]
-pub(crate) fn write_fonts(ctx: &mut PdfContext) {
+pub(crate) fn write_fonts(ctx: &mut PdfContext) -> Result<(), ExportError> {
     for font in ctx.font_map.items() {
         let type0_ref = ctx.alloc.bump();
         let cid_ref = ctx.alloc.bump();
@@ -151,7 +153,10 @@ pub(crate) fn write_fonts(ctx: &mut PdfContext) {
 
         // Subset and write the font's bytes.
         let glyphs: Vec<_> = glyph_set.keys().copied().collect();
-        let data = subset_font(font, &glyphs);
+        let data = subset_font(font, &glyphs).map_err(|reason| ExportError::Font {
+            family: font.info().family.as_str().into(),
+            reason,
+        })?;
 
         let mut stream = ctx.pdf.stream(data_ref, &data);
         stream.filter(Filter::FlateDecode);
@@ -161,6 +166,21 @@ pub(crate) fn write_fonts(ctx: &mut PdfContext) {
 
         stream.finish();
     }
+
+    Ok(())
+}
+
+/// Ensure that the license of all used fonts permits embedding them.
+pub(crate) fn check_embeddable(ctx: &PdfContext) -> Result<(), ExportError> {
+    for font in ctx.font_map.items() {
+        if font.ttf().permissions() == Some(Permissions::Restricted) {
+            return Err(ExportError::Font {
+                family: font.info().family.as_str().into(),
+                reason: "its license forbids embedding into an archival PDF".into(),
+            });
+        }
+    }
+    Ok(())
 }
 
 /// Subset a font to the given glyphs.
@@ -169,19 +189,71 @@ pub(crate) fn write_fonts(ctx: &mut PdfContext) {
 /// - For a font with CFF outlines, this returns just the CFF font program.
 #[comemo::memoize]
 #[typst_macros::time(name = "subset font")]
-fn subset_font(font: &Font, glyphs: &[u16]) -> Arc<Vec<u8>> {
-    let data = font.data();
+fn subset_font(font: &Font, glyphs: &[u16]) -> StrResult<Arc<Vec<u8>>> {
+    // The subset is a standalone font. If subsetting fails, the font's face
+    // is embedded in full, so it must be taken out of its collection.
     let profile = subsetter::Profile::pdf(glyphs);
-    let subsetted = subsetter::subset(data, font.index(), profile);
-    let mut data = subsetted.as_deref().unwrap_or(data);
+    let data = match subsetter::subset(font.data(), font.index(), profile) {
+        Ok(subsetted) => Cow::Owned(subsetted),
+        Err(_) => extract_face(font.data(), font.index())?,
+    };
+    Ok(Arc::new(deflate(extract_cff(&data)?)))
+}
+
+/// Extract a face from a font collection as a standalone font. Data that isn't
+/// a collection already is a standalone font and is returned as is.
+pub(crate) fn extract_face(data: &[u8], index: u32) -> StrResult<Cow<'_, [u8]>> {
+    if !data.starts_with(b"ttcf") {
+        return Ok(Cow::Borrowed(data));
+    }
 
-    // Extract the standalone CFF font program if applicable.
-    let raw = ttf_parser::RawFace::parse(data, 0).unwrap();
-    if let Some(cff) = raw.table(CFF) {
-        data = cff;
+    let malformed = "the font data is malformed";
+    let raw = ttf_parser::RawFace::parse(data, index).map_err(|_| malformed)?;
+    let read = |offset: usize| -> StrResult<u32> {
+        let bytes = data.get(offset..offset + 4).ok_or(malformed)?;
+        Ok(u32::from_be_bytes(bytes.try_into().unwrap()))
+    };
+
+    // The face's header, with the version of its outlines and the
+    // parameters for a binary search through its table records.
+    let count = raw.table_records.len();
+    let selector = count.checked_ilog2().unwrap_or(0);
+    let range = 16 * (1u16 << selector);
+    let mut font = vec![];
+    font.extend(read(read(12 + 4 * index as usize)? as usize)?.to_be_bytes());
+    font.extend(count.to_be_bytes());
+    font.extend(range.to_be_bytes());
+    font.extend((selector as u16).to_be_bytes());
+    font.extend((16 * count - range).to_be_bytes());
+
+    // The table records, with the tables following them in the same order.
+    let mut offset = font.len() + 16 * count as usize;
+    let mut tables = vec![];
+    for record in raw.table_records {
+        let start = record.offset as usize;
+        let table = data.get(start..start + record.length as usize).ok_or(malformed)?;
+        font.extend(record.tag.to_bytes());
+        font.extend(record.check_sum.to_be_bytes());
+        font.extend((offset as u32).to_be_bytes());
+        font.extend(record.length.to_be_bytes());
+        tables.extend(table);
+
+        // Tables start at four-byte boundaries.
+        let padding = (4 - table.len() % 4) % 4;
+        tables.resize(tables.len() + padding, 0);
+        offset += table.len() + padding;
     }
 
-    Arc::new(deflate(data))
+    font.extend(tables);
+    Ok(Cow::Owned(font))
+}
+
+/// Extract the standalone CFF font program from an OpenType font if it has
+/// one. Fails if the data can't be parsed.
+pub(crate) fn extract_cff(data: &[u8]) -> StrResult<&[u8]> {
+    let raw =
+        ttf_parser::RawFace::parse(data, 0).map_err(|_| "the font data is malformed")?;
+    Ok(raw.table(CFF).unwrap_or(data))
 }
 
 /// Produce a unique 6 letter tag for a glyph set.
@@ -213,7 +285,7 @@ fn create_cmap(font: &Font, glyph_set: &mut BTreeMap<u16, EcoString>) -> Unicode
 
         subtable.codepoints(|n| {
             let Some(c) = std::char::from_u32(n) else { return };
-            if c.general_category() == GeneralCategory::PrivateUse {
+            if c.general_category() == GeneralCategory::PrivateUse || c == '\u{ad}' {
                 return;
             }
 
@@ -3,40 +3,53 @@ use std::io::Cursor;
 
 use image::{DynamicImage, GenericImageView, Rgba};
 use pdf_writer::{Chunk, Filter, Finish, Ref};
+use typst::diag::{bail, StrResult};
 use typst::util::Deferred;
 use typst::visualize::{
     ColorSpace, Image, ImageKind, RasterFormat, RasterImage, SvgImage,
 };
 
-use crate::{deflate, PdfContext};
+use crate::{deflate, ExportError, PdfContext};
 
 /// Creates a new PDF image from the given image.
 ///
 /// Also starts the deferred encoding of the image.
 #[comemo::memoize]
-pub fn deferred_image(image: Image) -> Deferred<EncodedImage> {
+pub fn deferred_image(image: Image) -> Deferred<StrResult<EncodedImage>> {
     Deferred::new(move || match image.kind() {
         ImageKind::Raster(raster) => {
             let raster = raster.clone();
             let (width, height) = (raster.width(), raster.height());
-            let (data, filter, has_color) = encode_raster_image(&raster);
+            let (data, filter, has_color) = encode_raster_image(&raster)?;
             let icc = raster.icc().map(deflate);
 
             let alpha =
                 raster.dynamic().color().has_alpha().then(|| encode_alpha(&raster));
 
-            EncodedImage::Raster { data, filter, has_color, width, height, icc, alpha }
+            Ok(EncodedImage::Raster {
+                data,
+                filter,
+                has_color,
+                width,
+                height,
+                icc,
+                alpha,
+            })
         }
-        ImageKind::Svg(svg) => EncodedImage::Svg(encode_svg(svg)),
+        ImageKind::Svg(svg) => Ok(EncodedImage::Svg(encode_svg(svg))),
     })
 }
 
 /// Embed all used images into the PDF.
 #[typst_macros::time(name = "write images")]
-pub(crate) fn write_images(ctx: &mut PdfContext) {
+pub(crate) fn write_images(ctx: &mut PdfContext) -> Result<(), ExportError> {
     for (i, _) in ctx.image_map.items().enumerate() {
-        let handle = ctx.image_deferred_map.get(&i).unwrap();
-        match handle.wait() {
+        let (handle, span) = ctx.image_deferred_map.get(&i).unwrap();
+        let encoded = handle.wait().as_ref().map_err(|reason| ExportError::Image {
+            span: *span,
+            reason: reason.clone(),
+        })?;
+        match encoded {
             EncodedImage::Raster {
                 data,
                 filter,
@@ -105,33 +118,44 @@ pub(crate) fn write_images(ctx: &mut PdfContext) {
             }
         }
     }
+
+    Ok(())
 }
 
 /// Encode an image with a suitable filter and return the data, filter and
 /// whether the image has color.
 ///
 /// Skips the alpha channel as that's encoded separately.
-fn encode_raster_image(image: &RasterImage) -> (Vec<u8>, Filter, bool) {
-    let dynamic = image.dynamic();
+fn encode_raster_image(image: &RasterImage) -> StrResult<(Vec<u8>, Filter, bool)> {
+    encode_pixels(image.dynamic(), image.format() == RasterFormat::Jpg)
+}
+
+/// Encode decoded pixels, with JPEG encoding if `jpeg` is true.
+pub(crate) fn encode_pixels(
+    dynamic: &DynamicImage,
+    jpeg: bool,
+) -> StrResult<(Vec<u8>, Filter, bool)> {
     let channel_count = dynamic.color().channel_count();
     let has_color = channel_count > 2;
 
-    if image.format() == RasterFormat::Jpg {
+    if jpeg {
         let mut data = Cursor::new(vec![]);
-        dynamic.write_to(&mut data, image::ImageFormat::Jpeg).unwrap();
-        (data.into_inner(), Filter::DctDecode, has_color)
-    } else {
-        // TODO: Encode flate streams with PNG-predictor?
-        let data = match (dynamic, channel_count) {
-            (DynamicImage::ImageLuma8(luma), _) => deflate(luma.as_raw()),
-            (DynamicImage::ImageRgb8(rgb), _) => deflate(rgb.as_raw()),
-            // Grayscale image
-            (_, 1 | 2) => deflate(dynamic.to_luma8().as_raw()),
-            // Anything else
-            _ => deflate(dynamic.to_rgb8().as_raw()),
-        };
-        (data, Filter::FlateDecode, has_color)
+        if let Err(err) = dynamic.write_to(&mut data, image::ImageFormat::Jpeg) {
+            bail!("failed to encode JPEG ({err})");
+        }
+        return Ok((data.into_inner(), Filter::DctDecode, has_color));
     }
+
+    // TODO: Encode flate streams with PNG-predictor?
+    let data = match (dynamic, channel_count) {
+        (DynamicImage::ImageLuma8(luma), _) => deflate(luma.as_raw()),
+        (DynamicImage::ImageRgb8(rgb), _) => deflate(rgb.as_raw()),
+        // Grayscale image
+        (_, 1 | 2) => deflate(dynamic.to_luma8().as_raw()),
+        // Anything else
+        _ => deflate(dynamic.to_rgb8().as_raw()),
+    };
+    Ok((data, Filter::FlateDecode, has_color))
 }
 
 /// Encode an image's alpha channel if present.
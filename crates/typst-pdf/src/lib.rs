@@ -11,18 +11,21 @@ mod pattern;
 
 use std::cmp::Eq;
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
 use std::hash::Hash;
 use std::sync::Arc;
 
 use base64::Engine;
 use ecow::{eco_format, EcoString};
-use pdf_writer::types::Direction;
-use pdf_writer::writers::Destination;
+use pdf_writer::types::{Direction, OutputIntentSubtype};
+use pdf_writer::writers::{Destination, OutputIntent};
 use pdf_writer::{Finish, Name, Pdf, Ref, Str, TextStr};
+use typst::diag::StrResult;
 use typst::foundations::{Datetime, Label, NativeElement, Smart};
 use typst::introspection::Location;
 use typst::layout::{Abs, Dir, Em, Transform};
 use typst::model::{Document, HeadingElem};
+use typst::syntax::Span;
 use typst::text::{Font, Lang};
 use typst::util::Deferred;
 use typst::visualize::Image;
@@ -37,7 +40,8 @@ use crate::pattern::PdfPattern;
 
 /// Export a document into a PDF file.
 ///
-/// Returns the raw bytes making up the PDF file.
+/// Returns the raw bytes making up the PDF file or an error if an image or a
+/// font can't be embedded.
 ///
 /// The `ident` parameter, if given, shall be a string that uniquely and stably
 /// identifies the document. It should not change between compilations of the
@@ -59,18 +63,125 @@ pub fn pdf(
     document: &Document,
     ident: Smart<&str>,
     timestamp: Option<Datetime>,
-) -> Vec<u8> {
+) -> Result<Vec<u8>, ExportError> {
+    let options = PdfOptions { ident, timestamp, ..PdfOptions::default() };
     let mut ctx = PdfContext::new(document);
     page::construct_pages(&mut ctx, &document.pages);
-    font::write_fonts(&mut ctx);
-    image::write_images(&mut ctx);
+    write(ctx, &options)
+}
+
+/// Export a document into a PDF file with the given options.
+///
+/// Like [`pdf`], this fails if an image or a font can't be embedded. In
+/// [archival](PdfOptions::archival) mode, it is also an error if the document
+/// uses a font whose license forbids embedding.
+#[typst_macros::time(name = "pdf")]
+pub fn pdf_with_options(
+    document: &Document,
+    options: &PdfOptions,
+) -> Result<Vec<u8>, ExportError> {
+    let mut ctx = PdfContext::new(document);
+    ctx.outline_text = options.outline_text;
+    ctx.outline_families = options
+        .outline_families
+        .iter()
+        .map(|family| family.to_lowercase())
+        .collect();
+    ctx.strip_notes = options.strip_notes;
+    page::construct_pages(&mut ctx, &document.pages);
+    if options.archival {
+        font::check_embeddable(&ctx)?;
+        // The sRGB profile doubles as the output intent's profile, so it must
+        // be written even if no content uses it.
+        ctx.colors.srgb(&mut ctx.alloc);
+    }
+    write(ctx, options)
+}
+
+/// An error that prevents a document from being exported into a PDF.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ExportError {
+    /// An image can't be encoded.
+    Image {
+        /// Where the image is used in the document.
+        span: Span,
+        /// Why the image can't be encoded.
+        reason: EcoString,
+    },
+    /// A font can't be embedded.
+    Font {
+        /// The family of the font.
+        family: EcoString,
+        /// Why the font can't be embedded.
+        reason: EcoString,
+    },
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Image { reason, .. } => write!(f, "cannot embed image ({reason})"),
+            Self::Font { family, reason } => {
+                write!(f, "cannot embed font {family} ({reason})")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// Settings for exporting a PDF file.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct PdfOptions<'a> {
+    /// A string that uniquely and stably identifies the document. See [`pdf`]
+    /// for details.
+    pub ident: Smart<&'a str>,
+    /// The creation date of the document as a UTC datetime. Only used if
+    /// `set document(date: ..)` is `auto`.
+    pub timestamp: Option<Datetime>,
+    /// Whether to produce a self-contained file suitable for archival.
+    ///
+    /// This identifies the file as PDF/A-2b in its XMP metadata and adds an
+    /// output intent with an embedded sRGB profile. The document identifier
+    /// is derived from the file's contents unless an `ident` is given, so that
+    /// the same document always yields the same file. Typst always embeds its
+    /// fonts and never encrypts, but in this mode, using a font that may not
+    /// be embedded is an error unless its text is converted to outlines.
+    ///
+    /// The output is not validated against the PDF/A standard, so it can still
+    /// violate it in other ways.
+    pub archival: bool,
+    /// Whether to draw all text as filled glyph outlines instead of embedding
+    /// its fonts.
+    ///
+    /// This is meant for fonts whose licenses forbid embedding. The text can
+    /// still be copied and searched, but it loses its hinting, renders less
+    /// crisply at small sizes, and makes the file larger. Prefer listing the
+    /// affected fonts in `outline_families`.
+    ///
+    /// Text with bitmap or color glyphs, like emoji, can't be drawn as
+    /// outlines. Its font is embedded as usual, which fails in archival mode
+    /// if the font's license forbids embedding.
+    pub outline_text: bool,
+    /// Font families whose text is drawn as outlines even if `outline_text` is
+    /// disabled. The names are matched case-insensitively.
+    pub outline_families: &'a [&'a str],
+    /// Whether to leave out the notes created with `meta.pdf.note`, for example
+    /// for the final version of a reviewed document.
+    pub strip_notes: bool,
+}
+
+/// Write everything after the pages have been constructed.
+fn write(mut ctx: PdfContext, options: &PdfOptions) -> Result<Vec<u8>, ExportError> {
+    font::write_fonts(&mut ctx)?;
+    image::write_images(&mut ctx)?;
     gradient::write_gradients(&mut ctx);
     extg::write_external_graphics_states(&mut ctx);
     pattern::write_patterns(&mut ctx);
     write_named_destinations(&mut ctx);
     page::write_page_tree(&mut ctx);
-    write_catalog(&mut ctx, ident, timestamp);
-    ctx.pdf.finish()
+    write_catalog(&mut ctx, options);
+    Ok(ctx.pdf.finish())
 }
 
 /// Context for exporting a whole PDF document.
@@ -92,6 +203,12 @@ struct PdfContext<'a> {
     /// We keep track of this to determine the main document language.
     /// BTreeMap is used to write sorted list of languages to metadata.
     languages: BTreeMap<Lang, usize>,
+    /// Whether all text is drawn as outlines.
+    outline_text: bool,
+    /// The lowercased families whose text is drawn as outlines.
+    outline_families: Vec<String>,
+    /// Whether notes are left out.
+    strip_notes: bool,
 
     /// Allocator for indirect reference IDs.
     alloc: Ref,
@@ -116,8 +233,9 @@ struct PdfContext<'a> {
     font_map: Remapper<Font>,
     /// Deduplicates images used across the document.
     image_map: Remapper<Image>,
-    /// Handles to deferred image conversions.
-    image_deferred_map: HashMap<usize, Deferred<EncodedImage>>,
+    /// Handles to deferred image conversions and where the images are first
+    /// used.
+    image_deferred_map: HashMap<usize, (Deferred<StrResult<EncodedImage>>, Span)>,
     /// Deduplicates gradients used across the document.
     gradient_map: Remapper<PdfGradient>,
     /// Deduplicates patterns used across the document.
@@ -126,7 +244,7 @@ struct PdfContext<'a> {
     extg_map: Remapper<ExtGState>,
 
     /// A sorted list of all named destinations.
-    dests: Vec<(Label, Ref)>,
+    dests: Vec<(EcoString, Ref)>,
     /// Maps from locations to named destinations that point to them.
     loc_to_dest: HashMap<Location, Label>,
 }
@@ -141,6 +259,9 @@ impl<'a> PdfContext<'a> {
             pages: vec![],
             glyph_sets: HashMap::new(),
             languages: BTreeMap::new(),
+            outline_text: false,
+            outline_families: vec![],
+            strip_notes: false,
             alloc,
             page_tree_ref,
             page_refs: vec![],
@@ -160,10 +281,19 @@ impl<'a> PdfContext<'a> {
             loc_to_dest: HashMap::new(),
         }
     }
+
+    /// Whether text in the given font is drawn as outlines.
+    fn outlines(&self, font: &Font) -> bool {
+        self.outline_text
+            || self
+                .outline_families
+                .iter()
+                .any(|family| font.info().family.to_lowercase() == *family)
+    }
 }
 
 /// Write the document catalog.
-fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Datetime>) {
+fn write_catalog(ctx: &mut PdfContext, options: &PdfOptions) {
     let lang = ctx.languages.iter().max_by_key(|(_, &count)| count).map(|(&l, _)| l);
 
     let dir = if lang.map(Lang::dir) == Some(Dir::RTL) {
@@ -220,7 +350,7 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
         xmp.pdf_keywords(&joined);
     }
 
-    if let Some(date) = ctx.document.date.unwrap_or(timestamp) {
+    if let Some(date) = ctx.document.date.unwrap_or(options.timestamp) {
         let tz = ctx.document.date.is_auto();
         if let Some(pdf_date) = pdf_date(date, tz) {
             info.creation_date(pdf_date);
@@ -243,9 +373,13 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
 
     // Determine the document's ID. It should be as stable as possible.
     const PDF_VERSION: &str = "PDF-1.7";
-    let doc_id = if let Smart::Custom(ident) = ident {
+    let doc_id = if let Smart::Custom(ident) = options.ident {
         // We were provided with a stable ID. Yay!
         hash_base64(&(PDF_VERSION, ident))
+    } else if options.archival {
+        // Archival files must be reproducible, so the ID only depends on the
+        // contents.
+        instance_id.clone()
     } else if ctx.document.title.is_some() && !ctx.document.author.is_empty() {
         // If not provided from the outside, but title and author were given, we
         // compute a hash of them, which should be reasonably stable and unique.
@@ -263,6 +397,10 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
 
     xmp.rendition_class(RenditionClass::Proof);
     xmp.pdf_version("1.7");
+    if options.archival {
+        xmp.pdfa_part("2");
+        xmp.pdfa_conformance("B");
+    }
 
     let xmp_buf = xmp.finish(None);
     let meta_ref = ctx.alloc.bump();
@@ -271,18 +409,37 @@ fn write_catalog(ctx: &mut PdfContext, ident: Smart<&str>, timestamp: Option<Dat
         .pair(Name(b"Type"), Name(b"Metadata"))
         .pair(Name(b"Subtype"), Name(b"XML"));
 
+    // Write the output intent, which tells viewers how to reproduce the
+    // colors. The sRGB profile was already written with the color spaces.
+    let intent_ref = options.archival.then(|| {
+        let intent_ref = ctx.alloc.bump();
+        let profile_ref = ctx.colors.srgb(&mut ctx.alloc);
+        ctx.pdf
+            .indirect(intent_ref)
+            .start::<OutputIntent>()
+            .subtype(OutputIntentSubtype::PDFA)
+            .output_condition(TextStr("sRGB IEC61966-2.1"))
+            .output_condition_identifier(TextStr("sRGB IEC61966-2.1"))
+            .registry_name(TextStr("http://www.color.org"))
+            .dest_output_profile(profile_ref);
+        intent_ref
+    });
+
     // Write the document catalog.
     let mut catalog = ctx.pdf.catalog(ctx.alloc.bump());
     catalog.pages(ctx.page_tree_ref);
     catalog.viewer_preferences().direction(dir);
     catalog.metadata(meta_ref);
+    if let Some(intent_ref) = intent_ref {
+        catalog.insert(Name(b"OutputIntents")).array().item(intent_ref);
+    }
 
     // Write the named destination tree.
     let mut name_dict = catalog.names();
     let mut dests_name_tree = name_dict.destinations();
     let mut names = dests_name_tree.names();
-    for &(name, dest_ref, ..) in &ctx.dests {
-        names.insert(Str(name.as_str().as_bytes()), dest_ref);
+    for (name, dest_ref) in &ctx.dests {
+        names.insert(Str(name.as_bytes()), *dest_ref);
     }
     names.finish();
     dests_name_tree.finish();
@@ -315,36 +472,51 @@ fn write_named_destinations(ctx: &mut PdfContext) {
 
     // Find all headings that have a label and are the first among other
     // headings with the same label.
-    let mut matches: Vec<_> = ctx
+    let matches: Vec<_> = ctx
         .document
         .introspector
         .query(&HeadingElem::elem().select())
         .iter()
         .filter_map(|elem| elem.location().zip(elem.label()))
-        .filter(|&(_, label)| seen.insert(label))
+        .filter(|&(_, label)| seen.insert(EcoString::from(label.as_str())))
         .collect();
 
-    // Named destinations must be sorted by key.
-    matches.sort_by_key(|&(_, label)| label);
-
+    let mut dests = vec![];
     for (loc, label) in matches {
         let pos = ctx.document.introspector.position(loc);
         let index = pos.page.get() - 1;
         let y = (pos.point.y - Abs::pt(10.0)).max(Abs::zero());
 
         if let Some(page) = ctx.pages.get(index) {
-            let dest_ref = ctx.alloc.bump();
             let x = pos.point.x.to_f32();
             let y = (page.size.y - y).to_f32();
-            ctx.dests.push((label, dest_ref));
+            dests.push((label.as_str().into(), page.id, x, y));
             ctx.loc_to_dest.insert(loc, label);
-            ctx.pdf
-                .indirect(dest_ref)
-                .start::<Destination>()
-                .page(page.id)
-                .xyz(x, y, None);
         }
     }
+
+    // Add the destinations created with `meta.pdf.dest`, whose positions are
+    // already in the PDF coordinate system.
+    for page in &ctx.pages {
+        for (name, point) in &page.dests {
+            if seen.insert(name.clone()) {
+                dests.push((name.clone(), page.id, point.x.to_f32(), point.y.to_f32()));
+            }
+        }
+    }
+
+    // Named destinations must be sorted by key.
+    dests.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (name, page_id, x, y) in dests {
+        let dest_ref = ctx.alloc.bump();
+        ctx.dests.push((name, dest_ref));
+        ctx.pdf
+            .indirect(dest_ref)
+            .start::<Destination>()
+            .page(page_id)
+            .xyz(x, y, None);
+    }
 }
 
 /// Compress data with the DEFLATE algorithm.
@@ -491,3 +663,597 @@ fn transform_to_array(ts: Transform) -> [f32; 6] {
         ts.ty.to_f32(),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use ::image::{DynamicImage, ImageBuffer};
+    use pdf_writer::Filter;
+    use typst::eval::Tracer;
+    use typst::foundations::Bytes;
+    use typst::introspection::Meta;
+    use typst::layout::{Frame, FrameItem, Page, Point, Size};
+    use typst::model::{PdfMarker, PdfNote};
+    use typst::syntax::Span;
+    use typst::testing::TestWorld;
+    use typst::text::{Glyph, TextItem};
+    use typst::visualize::{Color, FixedStroke, Geometry, Paint, Pattern, RelativeTo};
+
+    use super::*;
+
+    /// Wrap a frame into a document with a single page.
+    fn document(frame: Frame) -> Document {
+        let page = Page {
+            frame,
+            numbering: None,
+            number: 1,
+            bleed: Abs::zero(),
+            slug: Abs::zero(),
+        };
+        Document { pages: vec![page], ..Document::default() }
+    }
+
+    /// Construct the pages of a document without writing the whole PDF.
+    fn construct(document: &Document) -> PdfContext<'_> {
+        let mut ctx = PdfContext::new(document);
+        page::construct_pages(&mut ctx, &document.pages);
+        ctx
+    }
+
+    /// The decompressed content stream of the first constructed page.
+    fn content(ctx: &PdfContext) -> Vec<u8> {
+        miniz_oxide::inflate::decompress_to_vec_zlib(ctx.pages[0].content.wait()).unwrap()
+    }
+
+    /// How often an operation occurs in a content stream.
+    fn count(content: &[u8], op: &[u8]) -> usize {
+        content.windows(op.len()).filter(|w| w == &op).count()
+    }
+
+    /// Export a document and return the PDF as text.
+    fn export(document: &Document, options: &PdfOptions) -> String {
+        let bytes = pdf_with_options(document, options).unwrap();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// The first of the bundled fonts.
+    fn font() -> Font {
+        let data = typst_assets::fonts().next().unwrap();
+        Font::new(Bytes::from_static(data), 0).unwrap()
+    }
+
+    /// A glyph without an advance.
+    fn glyph(id: u16, range: Range<u16>) -> Glyph {
+        Glyph {
+            id,
+            x_advance: Em::zero(),
+            x_offset: Em::zero(),
+            range,
+            span: (Span::detached(), 0),
+        }
+    }
+
+    /// A glyph for each character of a text, set at the font's advances.
+    /// Unused soft hyphens are left out, like shaping does.
+    fn glyphs(font: &Font, text: &str) -> Vec<Glyph> {
+        text.char_indices()
+            .filter(|&(_, c)| c != '\u{ad}')
+            .map(|(i, c)| {
+                let id = font.ttf().glyph_index(c).unwrap().0;
+                Glyph {
+                    x_advance: font.advance(id).unwrap(),
+                    ..glyph(id, i as u16..(i + c.len_utf8()) as u16)
+                }
+            })
+            .collect()
+    }
+
+    /// An item of black text in 11pt.
+    fn text_item(font: &Font, text: &str, glyphs: Vec<Glyph>) -> TextItem {
+        TextItem {
+            font: font.clone(),
+            size: Abs::pt(11.0),
+            fill: Color::BLACK.into(),
+            stroke: None,
+            lang: Lang::ENGLISH,
+            text: text.into(),
+            glyphs,
+        }
+    }
+
+    #[test]
+    fn test_pdf_markers() {
+        let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(200.0)));
+        for (name, y) in [("second", 50.0), ("first", 20.0), ("second", 80.0)] {
+            let marker = Meta::Pdf(PdfMarker::Dest(name.into()));
+            frame.push(
+                Point::new(Abs::pt(10.0), Abs::pt(y)),
+                FrameItem::Meta(marker, Size::zero()),
+            );
+        }
+        let comment = Meta::Pdf(PdfMarker::Comment("a\nb".into()));
+        frame.push(Point::zero(), FrameItem::Meta(comment, Size::zero()));
+
+        let document = document(frame);
+        let mut ctx = construct(&document);
+        write_named_destinations(&mut ctx);
+
+        // Destinations are sorted, deduplicated and flipped vertically.
+        let page = &ctx.pages[0];
+        let names: Vec<_> = ctx.dests.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["first", "second"]);
+        assert_eq!(
+            page.dests[0],
+            ("second".into(), Point::new(Abs::pt(10.0), Abs::pt(150.0)))
+        );
+
+        let content = content(&ctx);
+        let comment = b"/Comment <<\n  /Text <FEFF0061000A0062>\n>> DP";
+        assert_eq!(count(&content, comment), 1);
+
+        let text = export(&document, &PdfOptions::default());
+        assert!(text.contains("/Dests"));
+        assert!(text.contains("(first)") && text.contains("(second)"));
+    }
+
+    #[test]
+    fn test_pdf_notes() {
+        let note = PdfNote {
+            text: "Check this figure".into(),
+            author: Some("AB".into()),
+            date: Datetime::from_ymd(2024, 3, 1),
+        };
+        let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(200.0)));
+        frame.push(
+            Point::new(Abs::pt(10.0), Abs::pt(50.0)),
+            FrameItem::Meta(Meta::Pdf(PdfMarker::Note(note)), Size::zero()),
+        );
+        let document = document(frame);
+        let export = |strip_notes, archival| {
+            let options = PdfOptions { strip_notes, archival, ..PdfOptions::default() };
+            export(&document, &options)
+        };
+
+        // The note becomes a text annotation hanging down from its position.
+        let text = export(false, false);
+        assert!(text.contains("/Subtype /Text"));
+        assert!(text.contains("/Rect [10 130 30 150]"));
+        assert!(text.contains("/Contents (Check this figure)"));
+        assert!(text.contains("/T (AB)"));
+        assert!(text.contains("/M (D:20240301)"));
+        assert!(text.contains("/Name /Note"));
+
+        // PDF/A requires annotations to be printed, and text annotations to
+        // neither zoom nor rotate.
+        let archived = export(false, true);
+        assert!(archived.contains("/Subtype /Text"));
+        assert!(archived.contains("/F 28"));
+
+        // Stripped notes leave no trace.
+        let stripped = export(true, false);
+        assert!(!stripped.contains("/Subtype /Text"));
+        assert!(!stripped.contains("Check this figure"));
+    }
+
+    #[test]
+    fn test_pdf_text_run() {
+        let font = font();
+        let text = "Typst is a new markup-based typesetting system.";
+        let mut glyphs = glyphs(&font, text);
+        for (glyph, c) in glyphs.iter_mut().zip(text.chars()) {
+            if c == 'T' {
+                // A kerning pair.
+                glyph.x_advance -= Em::new(0.05);
+            }
+        }
+
+        let mut frame = Frame::soft(Size::new(Abs::pt(300.0), Abs::pt(100.0)));
+        let item = text_item(&font, text, glyphs);
+        frame.push(Point::new(Abs::pt(10.0), Abs::pt(20.0)), FrameItem::Text(item));
+        let content = content(&construct(&document(frame)));
+
+        // The whole run is positioned once and shown with a single operation
+        // that only carries an adjustment where the advance differs from the
+        // font's.
+        assert_eq!(count(&content, b" Tm"), 1);
+        assert_eq!(count(&content, b" TJ"), 1);
+        assert_eq!(count(&content, b" Td"), 0);
+        assert_eq!(count(&content, b") 50 ("), 1);
+
+        // That's far less than a positioning operation per glyph would take.
+        assert!(content.len() < 8 * text.len());
+    }
+
+    #[test]
+    fn test_pdf_soft_hyphen_text() {
+        let font = font();
+        let space = font.ttf().glyph_index(' ').unwrap().0;
+        let item =
+            |text: &str| text_item(&font, text, vec![glyph(space, 0..text.len() as u16)]);
+
+        // An unused soft hyphen is shaped to an invisible glyph. That glyph
+        // must not be mapped to the soft hyphen, but still to a later space.
+        let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        frame.push(Point::zero(), FrameItem::Text(item("\u{ad}")));
+        let glyph_text =
+            |frame| construct(&document(frame)).glyph_sets[&font][&space].clone();
+        assert_eq!(glyph_text(frame.clone()), "");
+
+        frame.push(Point::zero(), FrameItem::Text(item(" ")));
+        assert_eq!(glyph_text(frame), " ");
+    }
+
+    #[test]
+    fn test_pdf_cluster_text() {
+        let font = font();
+        let [e, acute, x] =
+            ['e', '\u{301}', 'x'].map(|c| font.ttf().glyph_index(c).unwrap().0);
+
+        // The base character and the combining mark form one cluster, and the
+        // base character also appears on its own.
+        let item = text_item(
+            &font,
+            "e\u{301}xe",
+            vec![glyph(e, 0..3), glyph(acute, 0..3), glyph(x, 3..4), glyph(e, 4..5)],
+        );
+
+        let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        frame.push(Point::zero(), FrameItem::Text(item));
+        let document = document(frame);
+        let ctx = construct(&document);
+
+        // The cluster's glyphs don't carry its text, so that the lone base
+        // character keeps its own.
+        let glyph_set = &ctx.glyph_sets[&font];
+        assert_eq!(glyph_set[&e], "e");
+        assert_eq!(glyph_set[&acute], "");
+        assert_eq!(glyph_set[&x], "x");
+
+        // Instead, the cluster is marked with its actual text.
+        let content = content(&ctx);
+        let span = b"/Span <<\n  /ActualText <FEFF00650301>\n>> BDC";
+        assert_eq!(count(&content, span), 1);
+        assert_eq!(count(&content, b"\nEMC"), 1);
+        assert_eq!(count(&content, b" TJ"), 2);
+    }
+
+    #[test]
+    fn test_pdf_text_stroke() {
+        let font = font();
+        let item = |stroke: Option<FixedStroke>| TextItem {
+            fill: Color::WHITE.into(),
+            stroke,
+            ..text_item(&font, "T", glyphs(&font, "T"))
+        };
+
+        let stream = |items: Vec<TextItem>| {
+            let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+            for item in items {
+                frame.push(Point::with_y(Abs::pt(20.0)), FrameItem::Text(item));
+            }
+            content(&construct(&document(frame)))
+        };
+
+        // Fill-only text keeps the default rendering mode.
+        let filled = stream(vec![item(None)]);
+        assert_eq!(count(&filled, b" Tr"), 0);
+        assert_eq!(count(&filled, b" w"), 0);
+
+        // Stroked text is filled and stroked and sets the stroke width. Text
+        // after it switches back to filling only.
+        let stroke = FixedStroke::from_pair(Color::BLACK, Abs::pt(0.5));
+        let stroked = stream(vec![item(Some(stroke)), item(None)]);
+        assert_eq!(count(&stroked, b"2 Tr"), 1);
+        assert_eq!(count(&stroked, b"0 Tr"), 1);
+        assert_eq!(count(&stroked, b"0.5 w"), 1);
+    }
+
+    #[test]
+    fn test_pdf_bleed_boxes() {
+        let page = |bleed: f64, slug: f64| Page {
+            frame: Frame::soft(Size::splat(Abs::pt(100.0 + 2.0 * (bleed + slug)))),
+            numbering: None,
+            number: 1,
+            bleed: Abs::pt(bleed),
+            slug: Abs::pt(slug),
+        };
+
+        let document = Document {
+            pages: vec![page(8.0, 10.0), page(0.0, 0.0)],
+            ..Document::default()
+        };
+        let text = export(&document, &PdfOptions::default());
+
+        // Only the first page has a bleed and crop marks.
+        assert!(text.contains("/MediaBox [0 0 136 136]"));
+        assert!(text.contains("/BleedBox [10 10 126 126]"));
+        assert!(text.contains("/TrimBox [18 18 118 118]"));
+        assert!(text.contains("/MediaBox [0 0 100 100]"));
+        assert_eq!(text.matches("/TrimBox").count(), 1);
+    }
+
+    #[test]
+    fn test_pdf_archival() {
+        let document = |data: Bytes, text: &str| {
+            let font = Font::new(data, 0).unwrap();
+            let item = text_item(&font, text, glyphs(&font, text));
+            let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+            frame.push(Point::with_y(Abs::pt(20.0)), FrameItem::Text(item));
+            Document {
+                title: Some("Report".into()),
+                author: vec!["Jane".into(), "John".into()],
+                ..document(frame)
+            }
+        };
+
+        // Find an indirect object and a reference in it.
+        fn object(text: &str, id: &str) -> String {
+            let start = text.find(&format!("\n{id} 0 obj")).unwrap();
+            let end = start + text[start..].find("endobj").unwrap();
+            text[start..end].into()
+        }
+        fn reference(object: &str, key: &str) -> String {
+            let start = object.find(key).unwrap() + key.len();
+            object[start..].split_whitespace().next().unwrap().into()
+        }
+
+        let data = typst_assets::fonts().next().unwrap();
+        let font = Bytes::from_static(data);
+        let options = PdfOptions {
+            timestamp: Datetime::from_ymd(2024, 3, 1),
+            archival: true,
+            ..PdfOptions::default()
+        };
+        let archive = |text| export(&document(font.clone(), text), &options);
+        let text = archive("Hello");
+
+        // The catalog points to an output intent with an embedded sRGB profile.
+        let catalog = object(&text, &reference(&text, "/Root "));
+        let intent = object(&text, &reference(&catalog, "/OutputIntents ["));
+        assert!(intent.contains("/Type /OutputIntent"));
+        assert!(intent.contains("/S /GTS_PDFA1"));
+        let profile = object(&text, &reference(&intent, "/DestOutputProfile "));
+        assert!(profile.contains("/N 3"));
+        assert!(profile.contains("stream"));
+
+        // The XMP metadata identifies the standard and holds the metadata.
+        let metadata = object(&text, &reference(&catalog, "/Metadata "));
+        assert!(metadata.contains("/Subtype /XML"));
+        assert!(metadata.contains("<pdfaid:part>2</pdfaid:part>"));
+        assert!(metadata.contains("<pdfaid:conformance>B</pdfaid:conformance>"));
+        assert!(metadata.contains("Report"));
+        assert!(metadata.contains("Jane, John"));
+        assert!(metadata.contains("2024-03-01"));
+
+        // The font is embedded.
+        assert!(text.contains("/FontFile2"));
+
+        // The identifier depends on the contents, but not on anything else.
+        let id = |text: &str| reference(text, "/ID [");
+        assert_eq!(text, archive("Hello"));
+        assert_ne!(id(&text), id(&archive("World")));
+        let plain = |text| export(&document(font.clone(), text), &PdfOptions::default());
+        assert_eq!(id(&plain("Hello")), id(&plain("World")));
+        assert!(!plain("Hello").contains("/OutputIntents"));
+
+        // A font that may not be embedded is rejected.
+        let mut restricted = data.to_vec();
+        let os2 = ttf_parser::RawFace::parse(data, 0)
+            .unwrap()
+            .table(ttf_parser::Tag::from_bytes(b"OS/2"))
+            .unwrap();
+        let fs_type = os2.as_ptr() as usize - data.as_ptr() as usize + 8;
+        restricted[fs_type..fs_type + 2].copy_from_slice(&2u16.to_be_bytes());
+        let error =
+            pdf_with_options(&document(restricted.into(), "Hi"), &options).unwrap_err();
+        assert!(matches!(error, ExportError::Font { .. }));
+        assert!(error.to_string().contains("license forbids embedding"));
+    }
+
+    #[test]
+    fn test_pdf_outlined_text() {
+        let font = font();
+        let text = "Hi\u{ad}!";
+        let item = text_item(&font, text, glyphs(&font, text));
+        let mut frame = Frame::soft(Size::new(Abs::pt(100.0), Abs::pt(100.0)));
+        frame.push(Point::with_y(Abs::pt(20.0)), FrameItem::Text(item));
+        let document = document(frame);
+
+        // The font is only embedded if its text isn't outlined.
+        assert!(export(&document, &PdfOptions::default()).contains("/FontFile"));
+        let family = font.info().family.to_uppercase();
+        let options = PdfOptions {
+            outline_families: &[&family],
+            ..PdfOptions::default()
+        };
+        let outlined = export(&document, &options);
+        assert!(!outlined.contains("/FontFile"));
+        assert!(!outlined.contains("/Type /Font"));
+
+        // The glyphs are filled paths in a span that carries the text.
+        let mut ctx = PdfContext::new(&document);
+        ctx.outline_text = true;
+        page::construct_pages(&mut ctx, &document.pages);
+        let content = content(&ctx);
+        assert_eq!(count(&content, b" BT"), 0);
+        assert_eq!(count(&content, b"\nf\n"), 3);
+        assert!(count(&content, b" c\n") > 0 || count(&content, b" l\n") > 0);
+        assert_eq!(count(&content, b"/Span <<"), 1);
+        assert_eq!(count(&content, b"/ActualText (Hi!)"), 1);
+        assert_eq!(count(&content, b"BDC"), 1);
+        assert_eq!(count(&content, b"EMC"), 1);
+        assert!(ctx.glyph_sets.is_empty());
+    }
+
+    #[test]
+    fn test_pdf_artifact() {
+        let square = || {
+            let shape =
+                Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::BLACK.into());
+            FrameItem::Shape(shape, Span::detached())
+        };
+
+        let mut inner = Frame::soft(Size::splat(Abs::pt(5.0)));
+        inner.push(Point::zero(), FrameItem::Meta(Meta::Artifact, inner.size()));
+        inner.push(Point::zero(), square());
+        let mut frame = Frame::soft(Size::splat(Abs::pt(20.0)));
+        frame.push(Point::zero(), square());
+        frame.push_frame(Point::splat(Abs::pt(10.0)), inner);
+
+        // Only the content after the marker is an artifact.
+        let content = content(&construct(&document(frame)));
+        let content = String::from_utf8_lossy(&content);
+        let (before, after) = content.split_once("/Artifact BMC").unwrap();
+        assert!(before.ends_with("0 0 5 5 re\nf\n"));
+        assert!(after.ends_with("10 10 5 5 re\nf\nEMC"));
+    }
+
+    #[test]
+    fn test_pdf_raw_line_numbers() {
+        let world = TestWorld::new("#set raw(numbers: true)\n```\nfirst\nsecond\n```");
+        let document = typst::compile(&world, &mut Tracer::new()).unwrap();
+
+        // Each line number is an artifact of its own, while the code lines
+        // stay outside of any artifact.
+        let content = content(&construct(&document));
+        let content = String::from_utf8_lossy(&content);
+        let mut rest = content.as_ref();
+        let mut outside = 0;
+        let mut artifacts = 0;
+        while let Some((before, after)) = rest.split_once("/Artifact BMC") {
+            let (inside, after) = after.split_once("EMC").unwrap();
+            assert_eq!(inside.matches("TJ").count(), 1);
+            outside += before.matches("TJ").count();
+            artifacts += 1;
+            rest = after;
+        }
+        outside += rest.matches("TJ").count();
+        assert_eq!(artifacts, 2);
+        assert_eq!(outside, 2);
+    }
+
+    #[test]
+    fn test_pdf_unencodable_image() {
+        let rgb = DynamicImage::ImageRgb8(ImageBuffer::new(2, 2));
+        let (_, filter, has_color) = crate::image::encode_pixels(&rgb, true).unwrap();
+        assert_eq!((filter, has_color), (Filter::DctDecode, true));
+
+        // The JPEG encoder doesn't support 16-bit samples, which is an error
+        // instead of silently switching the encoding.
+        let deep = DynamicImage::ImageRgb16(ImageBuffer::new(2, 2));
+        let error = crate::image::encode_pixels(&deep, true).unwrap_err();
+        assert!(error.contains("failed to encode JPEG"));
+
+        // Without JPEG encoding, such images are flate-encoded.
+        let (data, filter, has_color) =
+            crate::image::encode_pixels(&deep, false).unwrap();
+        assert_eq!((filter, has_color), (Filter::FlateDecode, true));
+        let raw = miniz_oxide::inflate::decompress_to_vec_zlib(&data).unwrap();
+        assert_eq!(raw.len(), 2 * 2 * 3);
+    }
+
+    #[test]
+    fn test_pdf_malformed_font() {
+        // New Computer Modern has CFF outlines, which are extracted.
+        let font = typst_assets::fonts()
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .find(|font| font.info().family == "New Computer Modern")
+            .unwrap();
+        let cff = crate::font::extract_cff(font.data()).unwrap();
+        assert!(cff.len() < font.data().len());
+        assert_eq!(cff[0], 1);
+
+        // Data that can't be parsed is an error instead of being embedded.
+        let error = crate::font::extract_cff(b"not a font").unwrap_err();
+        assert!(error.contains("malformed"));
+    }
+
+    #[test]
+    fn test_pdf_collection_face() {
+        // Bundle two fonts into a collection. The offsets of their tables are
+        // relative to the start of the file, so they are shifted.
+        let fonts: Vec<&[u8]> = typst_assets::fonts().take(2).collect();
+        let mut collection = b"ttcf\0\x01\0\0".to_vec();
+        collection.extend(2u32.to_be_bytes());
+        let mut starts = vec![];
+        let mut start = 20;
+        for data in &fonts {
+            collection.extend((start as u32).to_be_bytes());
+            starts.push(start);
+            start += data.len().next_multiple_of(4);
+        }
+        for (data, start) in fonts.iter().zip(starts) {
+            let mut face = data.to_vec();
+            let count = u16::from_be_bytes([face[4], face[5]]) as usize;
+            for i in 0..count {
+                let at = 12 + 16 * i + 8;
+                let offset = u32::from_be_bytes(face[at..at + 4].try_into().unwrap());
+                face[at..at + 4].copy_from_slice(&(offset + start as u32).to_be_bytes());
+            }
+            face.resize(data.len().next_multiple_of(4), 0);
+            collection.extend(face);
+        }
+
+        // Each face is extracted with exactly its own tables.
+        for (i, data) in fonts.iter().enumerate() {
+            let face = crate::font::extract_face(&collection, i as u32).unwrap();
+            assert!(ttf_parser::Face::parse(&face, 0).is_ok());
+            let extracted = ttf_parser::RawFace::parse(&face, 0).unwrap();
+            let original = ttf_parser::RawFace::parse(data, 0).unwrap();
+            assert_eq!(extracted.table_records.len(), original.table_records.len());
+            for record in original.table_records {
+                assert_eq!(extracted.table(record.tag), original.table(record.tag));
+            }
+        }
+
+        // A standalone font is kept as is.
+        let face = crate::font::extract_face(fonts[0], 0).unwrap();
+        assert!(matches!(face, std::borrow::Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_pdf_pattern_reuse() {
+        let mut tile = Frame::soft(Size::splat(Abs::pt(5.0)));
+        let dot = Geometry::Rect(Size::splat(Abs::pt(1.0))).filled(Color::BLACK.into());
+        tile.push(Point::zero(), FrameItem::Shape(dot, Span::detached()));
+
+        let pattern = |relative| {
+            Paint::Pattern(Pattern::from_frame(
+                tile.clone(),
+                Size::zero(),
+                Smart::Custom(relative),
+            ))
+        };
+
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        for (relative, y) in [
+            (RelativeTo::Parent, 0.0),
+            (RelativeTo::Parent, 25.0),
+            (RelativeTo::Self_, 50.0),
+            (RelativeTo::Self_, 75.0),
+        ] {
+            let shape = Geometry::Rect(Size::new(Abs::pt(50.0), Abs::pt(20.0)))
+                .filled(pattern(relative));
+            frame.push(
+                Point::with_y(Abs::pt(y)),
+                FrameItem::Shape(shape, Span::detached()),
+            );
+        }
+
+        let document = document(frame);
+        let ctx = construct(&document);
+
+        // The shapes with a pattern relative to the page share one tiling
+        // pattern. Those with a pattern relative to themselves each need their
+        // own transform.
+        let content = content(&ctx);
+        assert_eq!(ctx.pattern_map.items().count(), 3);
+        assert_eq!(count(&content, b"/P0 scn"), 2);
+        assert_eq!(count(&content, b"/P1 scn"), 1);
+        assert_eq!(count(&content, b"/P2 scn"), 1);
+
+        let text = export(&document, &PdfOptions::default());
+        assert_eq!(text.matches("/PatternType 1").count(), 3);
+    }
+}
@@ -3,8 +3,8 @@ use std::num::NonZeroUsize;
 
 use ecow::{eco_format, EcoString};
 use pdf_writer::types::{
-    ActionType, AnnotationFlags, AnnotationType, ColorSpaceOperand, LineCapStyle,
-    LineJoinStyle, NumberingStyle, TextRenderingMode,
+    ActionType, AnnotationFlags, AnnotationIcon, AnnotationType, ColorSpaceOperand,
+    LineCapStyle, LineJoinStyle, NumberingStyle, TextRenderingMode,
 };
 use pdf_writer::writers::{PageLabel, Resources};
 use pdf_writer::{Content, Filter, Finish, Name, Rect, Ref, Str, TextStr};
@@ -12,8 +12,9 @@ use typst::introspection::Meta;
 use typst::layout::{
     Abs, Em, Frame, FrameItem, GroupItem, Page, Point, Ratio, Size, Transform,
 };
-use typst::model::{Destination, Numbering};
-use typst::text::{Case, Font, TextItem};
+use typst::model::{Destination, Numbering, PdfMarker, PdfNote};
+use typst::syntax::Span;
+use typst::text::{Case, Font, Glyph, TextItem};
 use typst::util::{Deferred, Numeric};
 use typst::visualize::{
     FixedStroke, Geometry, Image, LineCap, LineJoin, Paint, Path, PathItem, Shape,
@@ -33,6 +34,8 @@ pub(crate) fn construct_pages(ctx: &mut PdfContext, pages: &[Page]) {
             .numbering
             .as_ref()
             .and_then(|num| PdfPageLabel::generate(num, page.number));
+        encoded.bleed = page.bleed;
+        encoded.slug = page.slug;
         ctx.page_refs.push(page_ref);
         ctx.pages.push(encoded);
     }
@@ -53,6 +56,8 @@ pub(crate) fn construct_page(ctx: &mut PdfContext, frame: &Frame) -> (Ref, Encod
         saves: vec![],
         bottom: 0.0,
         links: vec![],
+        dests: vec![],
+        notes: vec![],
         resources: HashMap::default(),
     };
 
@@ -76,8 +81,12 @@ pub(crate) fn construct_page(ctx: &mut PdfContext, frame: &Frame) -> (Ref, Encod
         id: ctx.page_ref,
         uses_opacities: ctx.uses_opacities,
         links: ctx.links,
+        dests: ctx.dests,
+        notes: ctx.notes,
         label: None,
         resources: ctx.resources,
+        bleed: Abs::zero(),
+        slug: Abs::zero(),
     };
 
     (page_ref, page)
@@ -153,6 +162,9 @@ fn write_global_resources(ctx: &mut PdfContext) -> Ref {
     resource_ref
 }
 
+/// The size of the icon of a note in points.
+const NOTE_SIZE: f32 = 20.0;
+
 /// Write a page tree node.
 fn write_page(ctx: &mut PdfContext, i: usize, resources_ref: Ref) {
     let page = &ctx.pages[i];
@@ -164,6 +176,17 @@ fn write_page(ctx: &mut PdfContext, i: usize, resources_ref: Ref) {
     let w = page.size.x.to_f32();
     let h = page.size.y.to_f32();
     page_writer.media_box(Rect::new(0.0, 0.0, w, h));
+
+    // Record where the page is to be cut if it has a bleed or crop marks.
+    if !page.bleed.is_zero() || !page.slug.is_zero() {
+        let inset = |d: Abs| {
+            let d = d.to_f32();
+            Rect::new(d, d, w - d, h - d)
+        };
+        page_writer.bleed_box(inset(page.slug));
+        page_writer.trim_box(inset(page.slug + page.bleed));
+    }
+
     page_writer.contents(content_id);
     page_writer.pair(Name(b"Resources"), resources_ref);
 
@@ -219,6 +242,29 @@ fn write_page(ctx: &mut PdfContext, i: usize, resources_ref: Ref) {
         }
     }
 
+    // Notes hang down from their position, like a sticky note. They are
+    // printed like links, which PDF/A also requires of all annotations.
+    for (note, point) in &page.notes {
+        let (x, y) = (point.x.to_f32(), point.y.to_f32());
+        let mut annotation = annotations.push();
+        annotation
+            .subtype(AnnotationType::Text)
+            .rect(Rect::new(x, y - NOTE_SIZE, x + NOTE_SIZE, y))
+            .contents(TextStr(&note.text))
+            .icon(AnnotationIcon::Note)
+            .flags(
+                AnnotationFlags::PRINT
+                    | AnnotationFlags::NO_ZOOM
+                    | AnnotationFlags::NO_ROTATE,
+            );
+        if let Some(author) = &note.author {
+            annotation.author(TextStr(author));
+        }
+        if let Some(date) = note.date.and_then(|date| crate::pdf_date(date, false)) {
+            annotation.modified(date);
+        }
+    }
+
     annotations.finish();
     page_writer.finish();
 
@@ -363,10 +409,19 @@ pub struct EncodedPage {
     pub uses_opacities: bool,
     /// Links in the PDF coordinate system.
     pub links: Vec<(Destination, Rect)>,
+    /// Named destinations in the PDF coordinate system.
+    pub dests: Vec<(EcoString, Point)>,
+    /// Notes in the PDF coordinate system.
+    pub notes: Vec<(PdfNote, Point)>,
     /// The page's used resources
     pub resources: HashMap<PageResource, usize>,
     /// The page's PDF label.
     label: Option<PdfPageLabel>,
+    /// How far the page extends beyond its trim box on each side, not counting
+    /// the space for crop marks.
+    bleed: Abs,
+    /// The space for crop marks around the bleed box on each side.
+    slug: Abs,
 }
 
 /// Represents a resource being used in a PDF page by its name.
@@ -434,6 +489,8 @@ pub struct PageContext<'a, 'b> {
     bottom: f32,
     uses_opacities: bool,
     links: Vec<(Destination, Rect)>,
+    dests: Vec<(EcoString, Point)>,
+    notes: Vec<(PdfNote, Point)>,
     /// Keep track of the resources being used in the page.
     pub resources: HashMap<PageResource, usize>,
 }
@@ -585,8 +642,10 @@ impl PageContext<'_, '_> {
     }
 
     fn set_fill(&mut self, fill: &Paint, on_text: bool, transforms: Transforms) {
+        // Gradients and patterns depend on the transform of what they paint,
+        // so they are set anew every time.
         if self.state.fill.as_ref() != Some(fill)
-            || matches!(self.state.fill, Some(Paint::Gradient(_)))
+            || matches!(self.state.fill, Some(Paint::Gradient(_) | Paint::Pattern(_)))
         {
             fill.set_as_fill(self, on_text, transforms);
             self.state.fill = Some(fill.clone());
@@ -613,7 +672,7 @@ impl PageContext<'_, '_> {
         if self.state.stroke.as_ref() != Some(stroke)
             || matches!(
                 self.state.stroke.as_ref().map(|s| &s.paint),
-                Some(Paint::Gradient(_))
+                Some(Paint::Gradient(_) | Paint::Pattern(_))
             )
         {
             let FixedStroke { paint, thickness, cap, join, dash, miter_limit } = stroke;
@@ -664,6 +723,7 @@ impl PageContext<'_, '_> {
 
 /// Encode a frame into the content stream.
 fn write_frame(ctx: &mut PageContext, frame: &Frame) {
+    let mut artifact = false;
     for &(pos, ref item) in frame.items() {
         let x = pos.x.to_f32();
         let y = pos.y.to_f32();
@@ -671,14 +731,45 @@ fn write_frame(ctx: &mut PageContext, frame: &Frame) {
             FrameItem::Group(group) => write_group(ctx, pos, group),
             FrameItem::Text(text) => write_text(ctx, pos, text),
             FrameItem::Shape(shape, _) => write_shape(ctx, pos, shape),
-            FrameItem::Image(image, size, _) => write_image(ctx, x, y, image, *size),
+            FrameItem::Image(image, size, span) => {
+                write_image(ctx, x, y, image, *size, *span)
+            }
             FrameItem::Meta(meta, size) => match meta {
                 Meta::Link(dest) => write_link(ctx, pos, dest, *size),
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
+                // The artifact marker precedes the content it applies to. Layout
+                // puts marked content into a group of its own, so the rest of
+                // this frame is exactly that content.
+                Meta::Artifact if !artifact => {
+                    ctx.content.begin_marked_content(Name(b"Artifact"));
+                    artifact = true;
+                }
+                Meta::Artifact => {}
+                Meta::Call(_) => {}
+                Meta::Pdf(PdfMarker::Dest(name)) => {
+                    let point = pos.transform(ctx.state.transform);
+                    ctx.dests.push((name.clone(), point));
+                }
+                Meta::Pdf(PdfMarker::Comment(text)) => {
+                    ctx.content
+                        .marked_content_point_with_properties(Name(b"Comment"))
+                        .properties()
+                        .pair(Name(b"Text"), TextStr(text));
+                }
+                Meta::Pdf(PdfMarker::Note(note)) => {
+                    if !ctx.parent.strip_notes {
+                        let point = pos.transform(ctx.state.transform);
+                        ctx.notes.push((note.clone(), point));
+                    }
+                }
             },
         }
     }
+
+    if artifact {
+        ctx.content.end_marked_content();
+    }
 }
 
 /// Encode a group into the content stream.
@@ -716,10 +807,23 @@ fn write_text(ctx: &mut PageContext, pos: Point, text: &TextItem) {
 
     *ctx.parent.languages.entry(text.lang).or_insert(0) += text.glyphs.len();
 
+    if ctx.parent.outlines(&text.font) && has_outlines(text) {
+        write_outlined_text(ctx, pos, text);
+        return;
+    }
+
     let glyph_set = ctx.parent.glyph_sets.entry(text.font.clone()).or_default();
-    for g in &text.glyphs {
-        let segment = &text.text[g.range()];
-        glyph_set.entry(g.id).or_insert_with(|| segment.into());
+    for (i, g) in text.glyphs.iter().enumerate() {
+        // A cluster can be shaped into multiple glyphs, for instance a base
+        // character and a combining mark. Its glyphs aren't mapped to the
+        // cluster's text, as the same glyphs may appear in other clusters.
+        // Instead, the cluster is marked with its actual text below.
+        let single = (i == 0 || text.glyphs[i - 1].range != g.range)
+            && text.glyphs.get(i + 1).map_or(true, |next| next.range != g.range);
+        let mapped = glyph_set.entry(g.id).or_default();
+        if single && mapped.is_empty() {
+            *mapped = extracted(&text.text[g.range()]).into();
+        }
     }
 
     let fill_transform = ctx.state.transforms(Size::zero(), pos);
@@ -747,14 +851,57 @@ fn write_text(ctx: &mut PageContext, pos: Point, text: &TextItem) {
     // Position the text.
     ctx.content.set_text_matrix([1.0, 0.0, 0.0, -1.0, x, y]);
 
-    let mut positioned = ctx.content.show_positioned();
-    let mut items = positioned.items();
     let mut adjustment = Em::zero();
+    let mut glyphs = text.glyphs.as_slice();
+    while !glyphs.is_empty() {
+        // Glyphs of single-glyph clusters are shown together, while each
+        // multi-glyph cluster is wrapped in a marked-content sequence with its
+        // text as the actual text.
+        let first = cluster_len(glyphs);
+        let mut len = first;
+        if first == 1 {
+            while len < glyphs.len() && cluster_len(&glyphs[len..]) == 1 {
+                len += 1;
+            }
+        }
+
+        let (run, rest) = glyphs.split_at(len);
+        if first > 1 {
+            let actual = extracted(&text.text[run[0].range()]);
+            ctx.content
+                .begin_marked_content_with_properties(Name(b"Span"))
+                .properties()
+                .actual_text(TextStr(actual));
+        }
+
+        write_glyphs(&mut ctx.content, &text.font, run, &mut adjustment);
+
+        if first > 1 {
+            ctx.content.end_marked_content();
+        }
+
+        glyphs = rest;
+    }
+
+    ctx.content.end_text();
+}
+
+/// Show glyphs with kerning adjustments in a single operation.
+///
+/// The adjustment that is still pending after the last glyph is left for the
+/// next glyphs of the run.
+fn write_glyphs(
+    content: &mut Content,
+    font: &Font,
+    glyphs: &[Glyph],
+    adjustment: &mut Em,
+) {
+    let mut positioned = content.show_positioned();
+    let mut items = positioned.items();
     let mut encoded = vec![];
 
-    // Write the glyphs with kerning adjustments.
-    for glyph in &text.glyphs {
-        adjustment += glyph.x_offset;
+    for glyph in glyphs {
+        *adjustment += glyph.x_offset;
 
         if !adjustment.is_zero() {
             if !encoded.is_empty() {
@@ -763,27 +910,157 @@ fn write_text(ctx: &mut PageContext, pos: Point, text: &TextItem) {
             }
 
             items.adjust(-adjustment.to_font_units());
-            adjustment = Em::zero();
+            *adjustment = Em::zero();
         }
 
-        let cid = crate::font::glyph_cid(&text.font, glyph.id);
+        let cid = crate::font::glyph_cid(font, glyph.id);
         encoded.push((cid >> 8) as u8);
         encoded.push((cid & 0xff) as u8);
 
-        if let Some(advance) = text.font.advance(glyph.id) {
-            adjustment += glyph.x_advance - advance;
+        if let Some(advance) = font.advance(glyph.id) {
+            *adjustment += glyph.x_advance - advance;
         }
 
-        adjustment -= glyph.x_offset;
+        *adjustment -= glyph.x_offset;
     }
 
     if !encoded.is_empty() {
         items.show(Str(&encoded));
     }
+}
 
-    items.finish();
-    positioned.finish();
-    ctx.content.end_text();
+/// The number of glyphs that the first glyph's cluster was shaped into.
+fn cluster_len(glyphs: &[Glyph]) -> usize {
+    glyphs.iter().take_while(|g| g.range == glyphs[0].range).count()
+}
+
+/// The text that should be extracted for a cluster.
+///
+/// A soft hyphen that wasn't used for a break is invisible and shouldn't end
+/// up in the extracted text.
+fn extracted(segment: &str) -> &str {
+    if segment == "\u{ad}" {
+        ""
+    } else {
+        segment
+    }
+}
+
+/// Whether all glyphs of a text run can be drawn as outlines.
+///
+/// Glyphs that are drawn as bitmaps, SVG images, or color layers, like emoji,
+/// would vanish or lose their colors, so runs with them embed their font
+/// instead. Glyphs without any representation, like spaces, are fine.
+fn has_outlines(text: &TextItem) -> bool {
+    let ttf = text.font.ttf();
+    text.glyphs.iter().all(|glyph| {
+        let id = ttf_parser::GlyphId(glyph.id);
+        ttf.glyph_raster_image(id, u16::MAX).is_none()
+            && ttf.glyph_svg_image(id).is_none()
+            && !ttf.is_color_glyph(id)
+    })
+}
+
+/// Encode a text run as filled glyph outlines, so that its font doesn't need
+/// to be embedded.
+///
+/// The outlines are wrapped in a marked-content sequence with the run's text as
+/// its actual text, which keeps it extractable.
+fn write_outlined_text(ctx: &mut PageContext, pos: Point, text: &TextItem) {
+    let transforms = ctx.state.transforms(Size::zero(), pos);
+    ctx.set_fill(&text.fill, true, transforms);
+
+    let stroke = text.stroke.as_ref().filter(|stroke| stroke.thickness.to_f32() > 0.0);
+    if let Some(stroke) = stroke {
+        ctx.set_stroke(stroke, true, transforms);
+    }
+
+    ctx.set_opacities(stroke, Some(&text.fill));
+
+    // Unused soft hyphens are invisible and shouldn't be extracted.
+    let actual: String = text.text.chars().filter(|&c| c != '\u{ad}').collect();
+    ctx.content
+        .begin_marked_content_with_properties(Name(b"Span"))
+        .properties()
+        .actual_text(TextStr(&actual));
+
+    let scale = text.size.to_pt() / text.font.units_per_em();
+    let mut x = Abs::zero();
+    for glyph in &text.glyphs {
+        let mut builder = GlyphPath {
+            path: Path::new(),
+            origin: Point::with_x(x + glyph.x_offset.at(text.size)),
+            scale,
+            last: Point::zero(),
+        };
+        x += glyph.x_advance.at(text.size);
+
+        // Glyphs without an outline are blank, as checked by `has_outlines`.
+        let id = ttf_parser::GlyphId(glyph.id);
+        if text.font.ttf().outline_glyph(id, &mut builder).is_none() {
+            continue;
+        }
+
+        write_path(ctx, pos.x.to_f32(), pos.y.to_f32(), &builder.path);
+        if stroke.is_some() {
+            ctx.content.fill_nonzero_and_stroke();
+        } else {
+            ctx.content.fill_nonzero();
+        }
+    }
+
+    ctx.content.end_marked_content();
+}
+
+/// Builds the outline of a glyph as a path in the coordinate system of its
+/// text run.
+struct GlyphPath {
+    path: Path,
+    /// Where the glyph's origin is in the run.
+    origin: Point,
+    /// The size of a font unit in points.
+    scale: f64,
+    /// The current point, needed to turn quadratic curves into cubic ones.
+    last: Point,
+}
+
+impl GlyphPath {
+    /// Convert a point from font units, with the y-axis flipped.
+    fn point(&self, x: f32, y: f32) -> Point {
+        self.origin
+            + Point::new(Abs::pt(x as f64 * self.scale), Abs::pt(-y as f64 * self.scale))
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphPath {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.last = self.point(x, y);
+        self.path.move_to(self.last);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.last = self.point(x, y);
+        self.path.line_to(self.last);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.point(x1, y1);
+        let end = self.point(x, y);
+        let p1 = self.last + (control - self.last) * (2.0 / 3.0);
+        let p2 = end + (control - end) * (2.0 / 3.0);
+        self.path.cubic_to(p1, p2, end);
+        self.last = end;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2) = (self.point(x1, y1), self.point(x2, y2));
+        self.last = self.point(x, y);
+        self.path.cubic_to(p1, p2, self.last);
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
 }
 
 /// Encode a geometrical shape into the content stream.
@@ -868,12 +1145,19 @@ fn write_path(ctx: &mut PageContext, x: f32, y: f32, path: &Path) {
 }
 
 /// Encode a vector or raster image into the content stream.
-fn write_image(ctx: &mut PageContext, x: f32, y: f32, image: &Image, size: Size) {
+fn write_image(
+    ctx: &mut PageContext,
+    x: f32,
+    y: f32,
+    image: &Image,
+    size: Size,
+    span: Span,
+) {
     let index = ctx.parent.image_map.insert(image.clone());
     ctx.parent
         .image_deferred_map
         .entry(index)
-        .or_insert_with(|| deferred_image(image.clone()));
+        .or_insert_with(|| (deferred_image(image.clone()), span));
 
     let name = eco_format!("Im{index}");
     let w = size.x.to_f32();
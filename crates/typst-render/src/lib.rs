@@ -168,6 +168,9 @@ fn render_frame(canvas: &mut sk::Pixmap, state: State, frame: &Frame) {
                 Meta::Link(_) => {}
                 Meta::Elem(_) => {}
                 Meta::Hide => {}
+                Meta::Pdf(_) => {}
+                Meta::Artifact => {}
+                Meta::Call(_) => {}
             },
         }
     }
@@ -103,6 +103,9 @@ impl Lexer<'_> {
         self.error = None;
         let start = self.s.cursor();
         match self.s.eat() {
+            // A leading byte order mark is treated like whitespace, such that
+            // it doesn't end up in the document but still counts for spans.
+            Some(c @ '\u{feff}') if start == 0 => self.whitespace(start, c),
             Some(c) if is_space(c, self.mode) => self.whitespace(start, c),
             Some('/') if self.s.eat_if('/') => self.line_comment(),
             Some('/') if self.s.eat_if('*') => self.block_comment(),
@@ -247,24 +250,48 @@ impl Lexer<'_> {
             backticks += 1;
         }
 
-        // Special case for ``.
-        if backticks == 2 {
-            self.push_raw(SyntaxKind::RawDelim);
-            self.s.jump(start + 1);
-            return SyntaxKind::RawDelim;
-        }
+        // Find the closing fence, a run of exactly as many backticks. A single
+        // backtick can't contain backticks, so any backtick closes it. Once
+        // raw text with three or more backticks spans multiple lines, its
+        // closing fence must start a line.
+        let mut closed = false;
+        let mut multiline = false;
+        let mut line_start = false;
+        while let Some(c) = self.s.eat() {
+            if is_newline(c) {
+                multiline = true;
+                line_start = true;
+                continue;
+            } else if c != '`' {
+                line_start &= c.is_whitespace();
+                continue;
+            }
 
-        // Find end of raw text.
-        let mut found = 0;
-        while found < backticks {
-            match self.s.eat() {
-                Some('`') => found += 1,
-                Some(_) => found = 0,
-                None => break,
+            let run = 1 + self.s.eat_while('`').len();
+            if backticks == 1 {
+                self.s.jump(self.s.cursor() - run + 1);
+                closed = true;
+                break;
+            } else if run == backticks && (backticks < 3 || !multiline || line_start) {
+                closed = true;
+                break;
             }
+            line_start = false;
         }
 
-        if found != backticks {
+        if !closed {
+            // Special case for ``, which is empty raw text unless it is
+            // closed by another ``.
+            if backticks == 2 {
+                self.s.jump(start + 2);
+                self.push_raw(SyntaxKind::RawDelim);
+                self.s.jump(start + 1);
+                return SyntaxKind::RawDelim;
+            }
+
+            // Only the opening fence is an error, so that the rest of the
+            // file is still parsed as usual.
+            self.s.jump(start + backticks);
             return self.error("unclosed raw text");
         }
 
@@ -272,10 +299,7 @@ impl Lexer<'_> {
         if backticks >= 3 {
             self.blocky_raw(start, end, backticks);
         } else {
-            // Single backtick needs no trimming or extra fancyness.
-            self.s.jump(end - backticks);
-            self.push_raw(SyntaxKind::Text);
-            self.s.jump(end);
+            self.inline_raw(start, end, backticks);
         }
 
         // Closing delimiter.
@@ -353,6 +377,30 @@ impl Lexer<'_> {
         self.s.jump(end);
     }
 
+    fn inline_raw(&mut self, start: usize, end: usize, backticks: usize) {
+        // A single space is trimmed on each side where it separates the
+        // fence from a backtick in the content.
+        self.s.jump(start + backticks);
+        let inner_end = end - backticks;
+        if self.s.get(self.s.cursor()..inner_end).starts_with(" `") {
+            self.s.eat();
+            self.push_raw(SyntaxKind::RawTrimmed);
+        }
+
+        let trailing = self.s.get(self.s.cursor()..inner_end).ends_with("` ");
+        let text_end = if trailing { inner_end - 1 } else { inner_end };
+        if self.s.cursor() < text_end {
+            self.s.jump(text_end);
+            self.push_raw(SyntaxKind::Text);
+        }
+
+        if trailing {
+            self.s.jump(inner_end);
+            self.push_raw(SyntaxKind::RawTrimmed);
+        }
+        self.s.jump(end);
+    }
+
     fn push_raw(&mut self, kind: SyntaxKind) {
         let end = self.s.cursor();
         self.raw.push((kind, end));
@@ -669,10 +717,13 @@ impl Lexer<'_> {
         let number = self.s.get(start..suffix_start);
         let suffix = self.s.from(suffix_start);
 
+        let float = if base == 10 { number.parse::<f64>().ok() } else { None };
         let kind = if i64::from_str_radix(number, base).is_ok() {
             SyntaxKind::Int
-        } else if base == 10 && number.parse::<f64>().is_ok() {
+        } else if float.is_some_and(f64::is_finite) {
             SyntaxKind::Float
+        } else if float.is_some() {
+            return self.error(eco_format!("number is too large: {}", number));
         } else {
             return self.error(match base {
                 2 => eco_format!("invalid binary number: 0b{}", number),
@@ -894,3 +945,163 @@ fn is_math_id_start(c: char) -> bool {
 fn is_math_id_continue(c: char) -> bool {
     is_xid_continue(c) && c != '_'
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LinkedNode, Source};
+
+    /// Parse the text and list the kind, text, line, and column of all leaves
+    /// in the syntax tree. Newlines in the text of the leaves are normalized.
+    fn leaves(text: &str) -> Vec<(SyntaxKind, String, usize, usize)> {
+        fn walk(
+            source: &Source,
+            node: LinkedNode,
+            leaves: &mut Vec<(SyntaxKind, String, usize, usize)>,
+        ) {
+            if node.children().len() == 0 {
+                let offset = node.offset();
+                leaves.push((
+                    node.kind(),
+                    split_newlines(node.text()).join("\n"),
+                    source.byte_to_line(offset).unwrap(),
+                    source.byte_to_column(offset).unwrap(),
+                ));
+            }
+            for child in node.children() {
+                walk(source, child, leaves);
+            }
+        }
+
+        let source = Source::detached(text);
+        let mut leaves = vec![];
+        walk(&source, LinkedNode::new(source.root()), &mut leaves);
+        leaves
+    }
+
+    #[test]
+    fn test_lex_newlines() {
+        const LF: &str = "= Heading\n\nA paragraph // comment\nwith *two* lines.\n\n\
+                          #let x = {\n  1 + 2\n}\n\n\
+                          ```rust\nfn main() {}\n```\n\n\
+                          $ a\n  + b $\n\n\
+                          - item\n  continued\n";
+
+        let lf = leaves(LF);
+        assert_eq!(leaves(&LF.replace('\n', "\r\n")), lf);
+        assert_eq!(leaves(&LF.replace('\n', "\r")), lf);
+        assert_eq!(leaves(&LF.replace('\n', "\u{2028}")), lf);
+
+        // Mixed newlines.
+        let mut newlines = ["\n", "\r\n", "\r", "\u{2029}", "\x0c"].iter().cycle();
+        let mixed: String = LF
+            .split_inclusive('\n')
+            .map(|line| line.replace('\n', newlines.next().unwrap()))
+            .collect();
+        assert_eq!(leaves(&mixed), lf);
+    }
+
+    #[test]
+    fn test_lex_bom() {
+        let kinds = |text| {
+            leaves(text)
+                .into_iter()
+                .map(|(kind, text, line, _)| (kind, text, line))
+                .collect::<Vec<_>>()
+        };
+        let with_bom = kinds("\u{feff}= Heading\n\nText");
+        assert_eq!(with_bom[0], (SyntaxKind::Space, "\u{feff}".into(), 0));
+        assert_eq!(with_bom[1..], kinds("= Heading\n\nText")[..]);
+
+        // Only a leading BOM is special.
+        let inner = leaves("Te\u{feff}xt");
+        assert_eq!(inner, [(SyntaxKind::Text, "Te\u{feff}xt".into(), 0, 0)]);
+    }
+
+    #[test]
+    fn test_lex_raw() {
+        use SyntaxKind::*;
+        let raw = |text: &str| {
+            leaves(text)
+                .into_iter()
+                .map(|(kind, text, _, column)| (kind, text, column))
+                .collect::<Vec<_>>()
+        };
+        let leaf = |kind, text: &str, column| (kind, text.to_string(), column);
+
+        // The fence is closed by a run of exactly as many backticks.
+        assert_eq!(
+            raw("````typ ```a``` ````"),
+            [
+                leaf(RawDelim, "````", 0),
+                leaf(RawLang, "typ", 4),
+                leaf(RawTrimmed, " ", 7),
+                leaf(Text, "```a```", 8),
+                leaf(RawTrimmed, " ", 15),
+                leaf(RawDelim, "````", 16),
+            ]
+        );
+
+        // Spaces are only trimmed next to a backtick.
+        assert_eq!(
+            raw("`` `a ``"),
+            [
+                leaf(RawDelim, "``", 0),
+                leaf(RawTrimmed, " ", 2),
+                leaf(Text, "`a ", 3),
+                leaf(RawDelim, "``", 6),
+            ]
+        );
+        assert_eq!(
+            raw("` a `"),
+            [leaf(RawDelim, "`", 0), leaf(Text, " a ", 1), leaf(RawDelim, "`", 4)]
+        );
+
+        // Only three or more backticks have a language tag.
+        assert_eq!(
+            raw("``js``"),
+            [leaf(RawDelim, "``", 0), leaf(Text, "js", 2), leaf(RawDelim, "``", 4)]
+        );
+
+        // A single backtick is closed by any backtick and two unclosed
+        // backticks are empty raw text.
+        assert_eq!(raw("`a``b`").len(), 6);
+        assert_eq!(
+            raw("`` a ```"),
+            [
+                leaf(RawDelim, "`", 0),
+                leaf(RawDelim, "`", 1),
+                leaf(Space, " ", 2),
+                leaf(Text, "a", 3),
+                leaf(Space, " ", 4),
+                leaf(Error, "```", 5),
+            ]
+        );
+
+        // Once raw text with three backticks spans multiple lines, its closing
+        // fence must start a line.
+        assert_eq!(
+            raw("```\na```\n  ```"),
+            [
+                leaf(RawDelim, "```", 0),
+                leaf(RawTrimmed, "\n", 3),
+                leaf(Text, "a```", 0),
+                leaf(RawTrimmed, "\n  ", 4),
+                leaf(RawDelim, "```", 2),
+            ]
+        );
+
+        // An unclosed fence is an error that ends with the fence.
+        assert_eq!(
+            raw("```js\n*a*"),
+            [
+                leaf(Error, "```", 0),
+                leaf(Text, "js", 3),
+                leaf(Space, "\n", 5),
+                leaf(Star, "*", 0),
+                leaf(Text, "a", 1),
+                leaf(Star, "*", 2),
+            ]
+        );
+    }
+}
@@ -28,7 +28,9 @@ pub fn parse_code(text: &str) -> SyntaxNode {
 /// Parses top-level math.
 pub fn parse_math(text: &str) -> SyntaxNode {
     let mut p = Parser::new(text, 0, LexMode::Math);
-    math(&mut p, |_| false);
+    let m = p.marker();
+    math_exprs(&mut p, |_| false);
+    p.wrap_all(m, SyntaxKind::Math);
     p.finish().into_iter().next().unwrap()
 }
 
@@ -40,6 +42,12 @@ fn markup(
     mut stop: impl FnMut(&Parser) -> bool,
 ) {
     let m = p.marker();
+    let depth = p.depth;
+    if !p.descend() {
+        p.wrap(m, SyntaxKind::Markup);
+        return;
+    }
+
     let mut nesting: usize = 0;
     while !p.eof() {
         match p.current() {
@@ -59,11 +67,13 @@ fn markup(
         }
 
         if p.at_set(set::MARKUP_EXPR) {
+            p.exceeded = false;
             markup_expr(p, &mut at_start);
         } else {
             p.unexpected();
         }
     }
+    p.depth = depth;
     p.wrap(m, SyntaxKind::Markup);
 }
 
@@ -270,16 +280,22 @@ fn equation(p: &mut Parser) {
 }
 
 /// Parses the contents of a mathematical equation: `x^2 + 1`.
-fn math(p: &mut Parser, mut stop: impl FnMut(&Parser) -> bool) {
+fn math(p: &mut Parser, stop: impl FnMut(&Parser) -> bool) {
     let m = p.marker();
+    math_exprs(p, stop);
+    p.wrap(m, SyntaxKind::Math);
+}
+
+/// Parses a sequence of math expressions.
+fn math_exprs(p: &mut Parser, mut stop: impl FnMut(&Parser) -> bool) {
     while !p.eof() && !stop(p) {
         if p.at_set(set::MATH_EXPR) {
+            p.exceeded = false;
             math_expr(p);
         } else {
             p.unexpected();
         }
     }
-    p.wrap(m, SyntaxKind::Math);
 }
 
 /// Parses a single math expression: This includes math elements like
@@ -290,6 +306,12 @@ fn math_expr(p: &mut Parser) {
 
 /// Parses a math expression with at least the given precedence.
 fn math_expr_prec(p: &mut Parser, min_prec: usize, stop: SyntaxKind) {
+    let depth = p.depth;
+    let chain = p.chain;
+    if !p.descend() {
+        return;
+    }
+
     let m = p.marker();
     let mut continuable = false;
     match p.current() {
@@ -369,15 +391,21 @@ fn math_expr_prec(p: &mut Parser, min_prec: usize, stop: SyntaxKind) {
     // Whether there were _any_ primes in the loop.
     let mut primed = false;
 
-    while !p.eof() && !p.at(stop) {
+    while !p.eof() && !p.at(stop) && !p.exceeded {
         if p.directly_at(SyntaxKind::Text) && p.current_text() == "!" {
+            if !p.extend() {
+                break;
+            }
             p.eat();
             p.wrap(m, SyntaxKind::Math);
             continue;
         }
 
         let prime_marker = p.marker();
-        if p.eat_if_direct(SyntaxKind::Prime) {
+        if p.directly_at(SyntaxKind::Prime) {
+            if !p.extend() {
+                break;
+            }
             // Eat as many primes as possible.
             while p.eat_if_direct(SyntaxKind::Prime) {}
             p.wrap(prime_marker, SyntaxKind::MathPrimes);
@@ -409,7 +437,7 @@ fn math_expr_prec(p: &mut Parser, min_prec: usize, stop: SyntaxKind) {
             p.wrap(m, SyntaxKind::MathAttach);
         }
 
-        if prec < min_prec {
+        if prec < min_prec || !p.extend() {
             break;
         }
 
@@ -435,6 +463,9 @@ fn math_expr_prec(p: &mut Parser, min_prec: usize, stop: SyntaxKind) {
 
         p.wrap(m, kind);
     }
+
+    p.depth = depth;
+    p.chain = chain;
 }
 
 fn maybe_delimited(p: &mut Parser) -> bool {
@@ -617,6 +648,7 @@ fn code_exprs(p: &mut Parser, mut stop: impl FnMut(&Parser) -> bool) {
 
         let at_expr = p.at_set(set::CODE_EXPR);
         if at_expr {
+            p.exceeded = false;
             code_expr(p);
             if !p.eof() && !stop(p) && !p.eat_if(SyntaxKind::Semicolon) {
                 p.expected("semicolon or line break");
@@ -664,6 +696,12 @@ fn embedded_code_expr(p: &mut Parser) {
 
 /// Parses a code expression with at least the given precedence.
 fn code_expr_prec(p: &mut Parser, atomic: bool, min_prec: usize) {
+    let depth = p.depth;
+    let chain = p.chain;
+    if !p.descend() {
+        return;
+    }
+
     let m = p.marker();
     if !atomic && p.at_set(set::UNARY_OP) {
         let op = ast::UnOp::from_kind(p.current()).unwrap();
@@ -674,9 +712,12 @@ fn code_expr_prec(p: &mut Parser, atomic: bool, min_prec: usize) {
         code_primary(p, atomic);
     }
 
-    loop {
+    while !p.exceeded {
         if p.directly_at(SyntaxKind::LeftParen) || p.directly_at(SyntaxKind::LeftBracket)
         {
+            if !p.extend() {
+                break;
+            }
             args(p);
             p.wrap(m, SyntaxKind::FuncCall);
             continue;
@@ -689,7 +730,11 @@ fn code_expr_prec(p: &mut Parser, atomic: bool, min_prec: usize) {
             break;
         }
 
-        if p.eat_if(SyntaxKind::Dot) {
+        if p.at(SyntaxKind::Dot) {
+            if !p.extend() {
+                break;
+            }
+            p.eat();
             p.expect(SyntaxKind::Ident);
             p.wrap(m, SyntaxKind::FieldAccess);
             continue;
@@ -720,6 +765,9 @@ fn code_expr_prec(p: &mut Parser, atomic: bool, min_prec: usize) {
                 ast::Assoc::Right => {}
             }
 
+            if !p.extend() {
+                break;
+            }
             p.eat();
             code_expr_prec(p, false, prec);
             p.wrap(m, SyntaxKind::Binary);
@@ -728,6 +776,9 @@ fn code_expr_prec(p: &mut Parser, atomic: bool, min_prec: usize) {
 
         break;
     }
+
+    p.depth = depth;
+    p.chain = chain;
 }
 
 /// Parses an primary in a code expression. These are the atoms that unary and
@@ -1471,6 +1522,18 @@ fn pattern_leaf<'s>(
     }
 }
 
+/// The maximum nesting depth of markup, math, and code expressions.
+///
+/// Deeper nesting is reported as an error instead of overflowing the stack.
+const MAX_DEPTH: usize = 256;
+
+/// The maximum length of chains of calls, field accesses, and operators,
+/// including those in nested expressions.
+///
+/// Each link wraps the expression so far, so long chains produce deeply
+/// nested trees, too. They are far more common than deep nesting, though.
+const MAX_CHAIN: usize = 512;
+
 /// Manages parsing of a stream of tokens.
 struct Parser<'s> {
     text: &'s str,
@@ -1479,6 +1542,9 @@ struct Parser<'s> {
     current_start: usize,
     current: SyntaxKind,
     balanced: bool,
+    depth: usize,
+    chain: usize,
+    exceeded: bool,
     nodes: Vec<SyntaxNode>,
     modes: Vec<LexMode>,
     newline_modes: Vec<NewlineMode>,
@@ -1521,6 +1587,9 @@ impl<'s> Parser<'s> {
             current_start: offset,
             current,
             balanced: true,
+            depth: 0,
+            chain: 0,
+            exceeded: false,
             nodes: vec![],
             modes: vec![],
             newline_modes: vec![],
@@ -1815,6 +1884,70 @@ impl<'s> Parser<'s> {
         self.eat_and_get().unexpected();
     }
 
+    /// Enter a nested expression. If the maximum nesting depth is reached,
+    /// consumes the rest of the enclosing group with an error and returns
+    /// `false`.
+    ///
+    /// Callers restore the previous depth once they are done.
+    fn descend(&mut self) -> bool {
+        if self.depth < MAX_DEPTH {
+            self.depth += 1;
+            return true;
+        }
+
+        self.exceed();
+        false
+    }
+
+    /// Continue a chain of calls, field accesses, or operators. If the maximum
+    /// chain length is reached, consumes the rest of the enclosing group with
+    /// an error and returns `false`.
+    ///
+    /// Callers restore the previous chain length once they are done.
+    fn extend(&mut self) -> bool {
+        if self.chain < MAX_CHAIN {
+            self.chain += 1;
+            return true;
+        }
+
+        self.exceed();
+        false
+    }
+
+    /// Turn the rest of the enclosing group into a single error, as its tokens
+    /// would otherwise each exceed the limit again. Operator and postfix chains
+    /// stop until the next expression in the surrounding sequence starts.
+    fn exceed(&mut self) {
+        self.exceeded = true;
+        self.trim_errors();
+
+        let m = self.marker();
+        let mut open = 0usize;
+        while !self.eof() && !self.at(SyntaxKind::Dollar) {
+            match self.current {
+                SyntaxKind::LeftBracket
+                | SyntaxKind::LeftBrace
+                | SyntaxKind::LeftParen => open += 1,
+                SyntaxKind::RightBracket
+                | SyntaxKind::RightBrace
+                | SyntaxKind::RightParen => {
+                    if open == 0 {
+                        break;
+                    }
+                    open -= 1;
+                }
+                _ => {}
+            }
+            self.eat();
+        }
+
+        let end = self.before_trivia();
+        let text: EcoString =
+            self.nodes.drain(m.0..end.0).map(SyntaxNode::into_text).collect();
+        self.nodes
+            .insert(m.0, SyntaxNode::error("maximum nesting depth exceeded", text));
+    }
+
     /// Remove trailing errors with zero length.
     fn trim_errors(&mut self) {
         let Marker(end) = self.before_trivia();
@@ -1842,3 +1975,214 @@ impl IndexMut<Marker> for Parser<'_> {
         &mut self.nodes[m.0]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse, parse_code, parse_math, LinkedNode, Source, SyntaxNode};
+
+    /// Check that the tree covers the whole text and that the children of
+    /// each node are contiguous.
+    #[track_caller]
+    fn check(text: &str, root: &SyntaxNode) {
+        assert_eq!(root.len(), text.len(), "{text:?}");
+        let mut stack = vec![LinkedNode::new(root)];
+        while let Some(node) = stack.pop() {
+            let mut offset = node.offset();
+            for child in node.children() {
+                assert_eq!(child.offset(), offset, "{text:?}");
+                offset += child.len();
+                stack.push(child);
+            }
+            if node.children().len() > 0 {
+                assert_eq!(offset, node.range().end, "{text:?}");
+            }
+        }
+    }
+
+    /// Parse the text in all modes and check the resulting trees.
+    #[track_caller]
+    fn check_all(text: &str) {
+        check(text, &parse(text));
+        check(text, &parse_code(text));
+        check(text, &parse_math(text));
+    }
+
+    /// Whether parsing the text reports an error with the given message.
+    fn has_error(text: &str, message: &str) -> bool {
+        parse(text).errors().iter().any(|error| error.message == message)
+    }
+
+    #[test]
+    fn test_parse_token_soup() {
+        const TOKENS: &[&str] = &[
+            "#",
+            "(",
+            ")",
+            "[",
+            "]",
+            "{",
+            "}",
+            "$",
+            "*",
+            "_",
+            "`",
+            "```",
+            "\"",
+            "\\",
+            "/",
+            "//",
+            "/*",
+            "*/",
+            "\n",
+            "\r\n",
+            "\r",
+            " ",
+            "=",
+            "-",
+            "+",
+            "^",
+            ".",
+            "..",
+            ",",
+            ":",
+            ";",
+            "=>",
+            "<a>",
+            "@a",
+            "&",
+            "'",
+            "let",
+            "set",
+            "show",
+            "if",
+            "else",
+            "for",
+            "in",
+            "not",
+            "import",
+            "x",
+            "f",
+            "1",
+            "1.5",
+            "1e999999",
+            "0x",
+            "12pt",
+            "em",
+            "é",
+            "e\u{301}",
+            "👨‍👩‍👧‍👦",
+        ];
+
+        // A small xorshift generator keeps the test deterministic.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as usize
+        };
+
+        for _ in 0..2000 {
+            let len = next() % 40;
+            let text: String = (0..len).map(|_| TOKENS[next() % TOKENS.len()]).collect();
+            check_all(&text);
+
+            // Each node's span must point back to its own range.
+            let source = Source::detached(text.as_str());
+            let mut stack = vec![LinkedNode::new(source.root())];
+            while let Some(node) = stack.pop() {
+                assert_eq!(source.range(node.span()), Some(node.range()), "{text:?}");
+                stack.extend(node.children());
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_eof() {
+        for text in [
+            "#let f(\r\n",
+            "#{\r\n(",
+            "$a_\r\n",
+            "#[\r\n*",
+            "#import \"a\": \r\n",
+            "#(a: \r\n",
+            "#\"\\u{",
+            "```\r\n",
+            "#{ let (a, ..b) = \r\n",
+            "# \n",
+        ] {
+            check_all(text);
+        }
+    }
+
+    #[test]
+    fn test_parse_deep_nesting() {
+        const N: usize = 5000;
+        for text in [
+            format!("#{}", "(".repeat(N)),
+            format!("#{}{}", "(".repeat(N), ")".repeat(N)),
+            format!("#{}", "{".repeat(N)),
+            format!("#{}", "f(".repeat(N)),
+            format!("#{}", "(1+".repeat(N)),
+            format!("#{}", "[#".repeat(N)),
+            format!("#{}", "let x = ".repeat(N)),
+            format!("${}", "(".repeat(N)),
+            format!("${}", "sqrt(".repeat(N)),
+            format!("${}", "a^".repeat(N)),
+            format!("${}", "1/(".repeat(N)),
+            "*_".repeat(N),
+        ] {
+            check_all(&text);
+            Source::detached(text.as_str());
+            assert!(
+                has_error(&text, "maximum nesting depth exceeded"),
+                "{:?}",
+                &text[..20]
+            );
+        }
+
+        // Moderate nesting is fine.
+        let text = format!("#{}1{}", "(".repeat(100), ")".repeat(100));
+        assert!(!parse(&text).erroneous());
+
+        // Flat chains count separately from nesting, so long ones are fine.
+        const M: usize = 300;
+        for text in [
+            format!("#a{}", ".b".repeat(M)),
+            format!("#f{}", "()".repeat(M)),
+            format!("#(1{})", "+1".repeat(M)),
+            format!("#(1{})", " and true".repeat(M)),
+            format!("$1{}$", "/1".repeat(M)),
+            format!("$a{}$", "+a".repeat(M)),
+        ] {
+            check_all(&text);
+            assert!(!parse(&text).erroneous(), "{:?}", &text[..20]);
+        }
+        // But they are limited, too, as they produce deeply nested trees.
+        const L: usize = 200_000;
+        for text in [
+            format!("#a{}", ".b".repeat(L)),
+            format!("#f{}", "()".repeat(L)),
+            format!("#(1{})", "+1".repeat(L)),
+            format!("#(1{})", " and true".repeat(L)),
+            format!("#(1{})", "+(1".repeat(L)),
+            format!("$1{}$", "/1".repeat(L)),
+            format!("$a{}$", "!".repeat(L)),
+        ] {
+            check_all(&text);
+            assert!(
+                has_error(&text, "maximum nesting depth exceeded"),
+                "{:?}",
+                &text[..20]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_number_too_large() {
+        assert!(has_error("#1e999999", "number is too large: 1e999999"));
+        assert!(has_error("#1e400pt", "number is too large: 1e400"));
+        assert!(!parse("#1e300").erroneous());
+        assert!(!parse("#99999999999999999999").erroneous());
+    }
+}
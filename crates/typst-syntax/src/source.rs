@@ -198,7 +198,10 @@ impl Source {
     /// Return the index of the column at the byte index.
     ///
     /// The column is defined as the number of characters in the line before the
-    /// byte index.
+    /// byte index. A grapheme cluster made up of multiple characters, like an
+    /// emoji sequence or a letter with a combining mark, thus spans multiple
+    /// columns. For editors that count UTF-16 code units, use
+    /// [`byte_to_utf16`](Self::byte_to_utf16) instead.
     pub fn byte_to_column(&self, byte_idx: usize) -> Option<usize> {
         let line = self.byte_to_line(byte_idx)?;
         let start = self.line_to_byte(line)?;
@@ -370,6 +373,16 @@ mod tests {
         assert_eq!(source.byte_to_column(12), Some(2));
     }
 
+    #[test]
+    fn test_source_file_clusters() {
+        // An emoji sequence, a combining mark, and a Devanagari conjunct.
+        let source = Source::detached("👨‍👩‍👧‍👦 e\u{301} क्षि x");
+        assert_eq!(source.byte_to_column(26), Some(8));
+        assert_eq!(source.byte_to_column(43), Some(16));
+        assert_eq!(source.byte_to_utf16(43), Some(20));
+        assert_eq!(source.line_column_to_byte(0, 16), Some(43));
+    }
+
     #[test]
     fn test_source_file_utf16() {
         #[track_caller]
@@ -0,0 +1,236 @@
+//! Extraction of plain text from laid out Typst documents.
+//!
+//! The extractor recovers the text in reading order from the positions of the
+//! text runs in the frames: Runs are grouped into lines by their baseline and
+//! ordered from left to right within a line. Words that were hyphenated during
+//! line breaking are joined back together.
+
+use typst::introspection::Meta;
+use typst::layout::{Abs, Frame, FrameItem, Point, Transform};
+use typst::model::Document;
+use typst::text::TextItem;
+
+/// Extract the plain text of each page of a document.
+#[typst_macros::time(name = "text")]
+pub fn text(document: &Document) -> Vec<String> {
+    document.pages.iter().map(|page| frame_text(&page.frame)).collect()
+}
+
+/// Extract the plain text of a single frame.
+pub fn frame_text(frame: &Frame) -> String {
+    let mut runs = vec![];
+    let mut artifacts = vec![];
+    collect_runs(&mut runs, &mut artifacts, frame, Transform::identity());
+    runs.retain(|run| !artifacts.iter().any(|area| area.contains(run.pos)));
+    runs.sort_by(|a, b| a.pos.y.cmp(&b.pos.y).then(a.pos.x.cmp(&b.pos.x)));
+
+    let mut lines: Vec<Line> = vec![];
+    for run in runs {
+        match lines.last_mut() {
+            Some(line) if line.accepts(&run) => line.push(run),
+            _ => lines.push(Line::new(run)),
+        }
+    }
+
+    let mut output = String::new();
+    let mut joined = false;
+    for line in &mut lines {
+        line.runs.sort_by_key(|run| run.pos.x);
+        if !output.is_empty() && !joined {
+            output.push('\n');
+        }
+        let text = line.text();
+        output.push_str(if joined { text.trim_start() } else { &text });
+        joined = line.hyphenated();
+    }
+    output
+}
+
+/// A text run positioned on the page.
+struct Run {
+    /// The position of the run's baseline start.
+    pos: Point,
+    /// The width of the run.
+    width: Abs,
+    /// The font size of the run.
+    size: Abs,
+    /// The text of the run, without soft hyphens.
+    text: String,
+    /// Whether the run ends with a hyphen that was inserted by hyphenation.
+    hyphenated: bool,
+}
+
+/// A line of runs sharing a baseline.
+struct Line {
+    /// The baseline of the first run.
+    y: Abs,
+    /// The largest font size in the line.
+    size: Abs,
+    /// The runs in the line.
+    runs: Vec<Run>,
+}
+
+impl Line {
+    /// Start a new line with a run.
+    fn new(run: Run) -> Self {
+        Self { y: run.pos.y, size: run.size, runs: vec![run] }
+    }
+
+    /// Whether the run's baseline is close enough to be part of this line.
+    fn accepts(&self, run: &Run) -> bool {
+        (run.pos.y - self.y).abs() <= 0.3 * self.size.max(run.size)
+    }
+
+    /// Add a run to the line.
+    fn push(&mut self, run: Run) {
+        self.size.set_max(run.size);
+        self.runs.push(run);
+    }
+
+    /// Whether the line ends in a hyphenated word.
+    fn hyphenated(&self) -> bool {
+        self.runs.last().is_some_and(|run| run.hyphenated)
+    }
+
+    /// The text of the line, with spaces inserted between runs that are
+    /// visibly apart.
+    fn text(&self) -> String {
+        let mut text = String::new();
+        let mut end: Option<Abs> = None;
+        for run in &self.runs {
+            if let Some(end) = end {
+                let gap = run.pos.x - end;
+                let spaced = text.ends_with(char::is_whitespace)
+                    || run.text.starts_with(char::is_whitespace);
+                if gap > 0.15 * run.size && !spaced {
+                    text.push(' ');
+                }
+            }
+            text.push_str(&run.text);
+            end = Some(run.pos.x + run.width);
+        }
+        text.trim_end().into()
+    }
+}
+
+/// An area on the page whose text is not part of the document's text.
+struct Artifact {
+    /// The top-left corner of the area.
+    min: Point,
+    /// The bottom-right corner of the area.
+    max: Point,
+}
+
+impl Artifact {
+    /// Whether the point lies within the area.
+    fn contains(&self, point: Point) -> bool {
+        (self.min.x..self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+    }
+}
+
+/// Collect the text runs and artifact areas in a frame and its nested groups.
+fn collect_runs(
+    runs: &mut Vec<Run>,
+    artifacts: &mut Vec<Artifact>,
+    frame: &Frame,
+    ts: Transform,
+) {
+    for (pos, item) in frame.items() {
+        match item {
+            FrameItem::Group(group) => {
+                let ts = ts
+                    .pre_concat(Transform::translate(pos.x, pos.y))
+                    .pre_concat(group.transform);
+                collect_runs(runs, artifacts, &group.frame, ts);
+            }
+            FrameItem::Meta(Meta::Artifact, size) => {
+                let a = pos.transform(ts);
+                let b = (*pos + size.to_point()).transform(ts);
+                artifacts.push(Artifact {
+                    min: Point::new(a.x.min(b.x), a.y.min(b.y)),
+                    max: Point::new(a.x.max(b.x), a.y.max(b.y)),
+                });
+            }
+            FrameItem::Text(text) => runs.push(Run {
+                pos: pos.transform(ts),
+                width: text.width() * ts.sx.get().abs(),
+                size: text.size * ts.sy.get().abs(),
+                text: text.text.as_str().replace('\u{ad}', ""),
+                hyphenated: is_hyphenated(text),
+            }),
+            _ => {}
+        }
+    }
+}
+
+/// Whether a text item ends with a hyphen that has no counterpart in the text.
+///
+/// Such a glyph can only have been inserted by hyphenation, which maps it to
+/// an empty cluster at the end of the text.
+fn is_hyphenated(text: &TextItem) -> bool {
+    text.glyphs.last().is_some_and(|glyph| {
+        glyph.range.is_empty() && usize::from(glyph.range.start) == text.text.len()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use typst::testing::TestWorld;
+
+    use super::*;
+
+    /// Collapse all whitespace into single spaces.
+    fn normalize(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_text_extraction() {
+        let first = "Extraction recovers the reading order of a document from the \
+                     positions of its text runs, including extraordinarily \
+                     hyphenated words.";
+        let second = "A second paragraph follows after a break and should come \
+                      out after the first one.";
+        let source = format!(
+            "#set page(width: 120pt, height: auto, margin: 10pt)\n\
+             #set par(justify: true)\n\
+             #set text(lang: \"en\", hyphenate: true)\n\
+             {first}\n\n{second}\n"
+        );
+
+        let world = TestWorld::new(&source);
+        let document = typst::compile(&world, &mut Default::default()).unwrap();
+        let pages = text(&document);
+        assert_eq!(pages.len(), 1);
+        assert!(pages[0].lines().count() > 4);
+        assert_eq!(normalize(&pages[0]), normalize(&format!("{first} {second}")));
+    }
+
+    #[test]
+    fn test_text_extraction_soft_hyphens() {
+        let source = "#set page(width: 40pt, height: auto, margin: 0pt)\n\
+                      Bar-?an-?kauf Ab-?bau";
+        let world = TestWorld::new(source);
+        let document = typst::compile(&world, &mut Default::default()).unwrap();
+        assert!(document.pages[0].frame.height() > Abs::pt(30.0));
+        assert_eq!(text(&document), ["Barankauf Abbau"]);
+    }
+
+    #[test]
+    fn test_text_extraction_clusters() {
+        let source = "👨‍👩‍👧‍👦 e\u{301} क्षि";
+        let world = TestWorld::new(source);
+        let document = typst::compile(&world, &mut Default::default()).unwrap();
+        assert_eq!(text(&document), [source]);
+    }
+
+    #[test]
+    fn test_text_extraction_skips_artifacts() {
+        let source = "#set raw(numbers: true)\n\
+                      ```\nlet x = 1;\nlet y = 2;\n```\n";
+        let world = TestWorld::new(source);
+        let document = typst::compile(&world, &mut Default::default()).unwrap();
+        assert_eq!(text(&document), ["let x = 1;\nlet y = 2;"]);
+    }
+}
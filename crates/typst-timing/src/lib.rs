@@ -1,5 +1,6 @@
 //! Performance timing for Typst.
 
+use std::collections::HashMap;
 use std::hash::Hash;
 use std::io::Write;
 use std::thread::ThreadId;
@@ -165,6 +166,51 @@ macro_rules! timed {
     }};
 }
 
+/// Sums up the time spent in the recorded scopes whose name satisfies the
+/// given predicate.
+///
+/// Scopes nested in another matching scope on the same thread are not counted
+/// separately.
+pub fn total(mut f: impl FnMut(&str) -> bool) -> Duration {
+    let recorder = RECORDER.lock();
+    let mut open = HashMap::<ThreadId, (usize, SystemTime)>::new();
+    let mut total = Duration::ZERO;
+    for event in recorder.events.iter().filter(|event| f(event.name)) {
+        let (depth, start) = open.entry(event.thread_id).or_insert((0, event.timestamp));
+        match event.kind {
+            EventKind::Start => {
+                if *depth == 0 {
+                    *start = event.timestamp;
+                }
+                *depth += 1;
+            }
+            EventKind::End => {
+                *depth = depth.saturating_sub(1);
+                if *depth == 0 {
+                    total += event.timestamp.duration_since(*start).unwrap_or_default();
+                }
+            }
+        }
+    }
+    total
+}
+
+/// Counts how often a scope with the given name was entered on the current
+/// thread since the events were last cleared.
+pub fn count(name: &str) -> usize {
+    let thread_id = std::thread::current().id();
+    RECORDER
+        .lock()
+        .events
+        .iter()
+        .filter(|event| {
+            event.kind == EventKind::Start
+                && event.thread_id == thread_id
+                && event.name == name
+        })
+        .count()
+}
+
 /// Export data as JSON for Chrome's tracing tool.
 ///
 /// The `source` function is called for each span to get the source code
@@ -1,5 +1,6 @@
 //! Diagnostics.
 
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter};
 use std::io;
 use std::path::{Path, PathBuf};
@@ -8,9 +9,10 @@ use std::string::FromUtf8Error;
 
 use comemo::Tracked;
 use ecow::{eco_vec, EcoVec};
+use serde::{Deserialize, Serialize};
 
 use crate::syntax::package::PackageSpec;
-use crate::syntax::{Span, Spanned, SyntaxError};
+use crate::syntax::{FileId, Source, Span, Spanned, SyntaxError};
 use crate::{World, WorldExt};
 
 /// Early-return with a [`StrResult`] or [`SourceResult`].
@@ -141,7 +143,21 @@ pub struct SourceDiagnostic {
 }
 
 /// The severity of a [`SourceDiagnostic`].
-#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+///
+/// Errors are ordered before warnings.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize
+)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     /// A fatal error.
     Error,
@@ -202,6 +218,144 @@ impl From<SyntaxError> for SourceDiagnostic {
     }
 }
 
+/// Bring diagnostics into the order in which they should be presented.
+///
+/// Diagnostics are sorted by file and then by their position in the file, with
+/// errors before warnings at the same position. Diagnostics without a
+/// position come first. Apart from that, the original order is kept. Exact
+/// duplicates, which arise when a problem is reported through different
+/// paths, are removed.
+pub fn sort_diagnostics(world: &dyn World, diags: &mut Vec<SourceDiagnostic>) {
+    let mut unique = HashSet::new();
+    diags.retain(|diag| unique.insert(crate::util::hash128(diag)));
+    diags.sort_by_cached_key(|diag| {
+        let file = diag.span.id().map(|id| {
+            (
+                id.package().map(ToString::to_string),
+                id.vpath().as_rootless_path().to_owned(),
+            )
+        });
+        let start = diag
+            .span
+            .id()
+            .and_then(|id| world.source(id).ok()?.range(diag.span))
+            .map(|range| range.start);
+        (file, start, diag.severity)
+    });
+}
+
+/// A diagnostic in a machine-readable format that can be serialized to JSON.
+///
+/// Unlike a [`SourceDiagnostic`], it does not refer to spans, but to file
+/// names and line-column positions, so that it can be interpreted without
+/// access to the world.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonDiagnostic {
+    /// Whether the diagnostic is an error or a warning.
+    pub severity: Severity,
+    /// A diagnostic message describing the problem.
+    pub message: EcoString,
+    /// Additional hints to the user.
+    pub hints: Vec<EcoString>,
+    /// The name of the file the problem occurred in, if any.
+    pub file: Option<String>,
+    /// The range in the file the problem occurred at, if any.
+    pub range: Option<JsonRange>,
+    /// The trace of function calls leading to the problem.
+    pub trace: Vec<JsonTracepoint>,
+}
+
+impl JsonDiagnostic {
+    /// Convert a diagnostic, naming its files with the given function.
+    pub fn new(
+        world: &dyn World,
+        name: &dyn Fn(FileId) -> Option<String>,
+        diagnostic: &SourceDiagnostic,
+    ) -> Self {
+        let (file, range) = json_location(world, name, diagnostic.span);
+        Self {
+            severity: diagnostic.severity,
+            message: diagnostic.message.clone(),
+            hints: diagnostic.hints.to_vec(),
+            file,
+            range,
+            trace: diagnostic
+                .trace
+                .iter()
+                .map(|point| {
+                    let (file, range) = json_location(world, name, point.span);
+                    JsonTracepoint { message: point.v.to_string(), file, range }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A stacktrace-like entry of a [`JsonDiagnostic`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonTracepoint {
+    /// A description of the call.
+    pub message: String,
+    /// The name of the file the call occurred in, if any.
+    pub file: Option<String>,
+    /// The range of the call in the file, if any.
+    pub range: Option<JsonRange>,
+}
+
+/// A range in a source file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonRange {
+    /// The position at which the range starts.
+    pub start: JsonPosition,
+    /// The position at which the range ends.
+    pub end: JsonPosition,
+}
+
+/// A position in a source file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JsonPosition {
+    /// The zero-based byte offset.
+    pub byte: usize,
+    /// The one-based line number.
+    pub line: usize,
+    /// The one-based column, counted in characters.
+    pub column: usize,
+    /// The zero-based column, counted in UTF-16 code units.
+    pub utf16_column: usize,
+}
+
+impl JsonPosition {
+    /// Determine the position of a byte offset in a source file.
+    pub fn new(source: &Source, byte: usize) -> Option<Self> {
+        let line = source.byte_to_line(byte)?;
+        let line_start = source.line_to_byte(line)?;
+        Some(Self {
+            byte,
+            line: line + 1,
+            column: source.byte_to_column(byte)? + 1,
+            utf16_column: source.byte_to_utf16(byte)?
+                - source.byte_to_utf16(line_start)?,
+        })
+    }
+}
+
+/// Resolve the file name and range of a span for JSON output.
+fn json_location(
+    world: &dyn World,
+    name: &dyn Fn(FileId) -> Option<String>,
+    span: Span,
+) -> (Option<String>, Option<JsonRange>) {
+    let Some(id) = span.id() else { return (None, None) };
+    let range = world.source(id).ok().and_then(|source| {
+        let range = source.range(span)?;
+        Some(JsonRange {
+            start: JsonPosition::new(&source, range.start)?,
+            end: JsonPosition::new(&source, range.end)?,
+        })
+    });
+    (name(id), range)
+}
+
 /// A part of a diagnostic's [trace](SourceDiagnostic::trace).
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum Tracepoint {
@@ -508,3 +662,113 @@ pub fn format_xml_like_error(format: &str, error: roxmltree::Error) -> EcoString
         err => eco_format!("failed to parse {format} ({err})"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eval::Tracer;
+    use crate::testing::TestWorld;
+
+    #[test]
+    fn test_sort_diagnostics() {
+        // The warnings are emitted during evaluation, but the error only
+        // during layout.
+        let world = TestWorld::new("**\n#context (1 + \"a\")\n__");
+
+        let mut tracer = Tracer::new();
+        let errors = crate::compile(&world, &mut tracer).unwrap_err();
+        let warnings = tracer.warnings();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(warnings.len(), 2);
+
+        // A warning at the error's position and a duplicate.
+        let mut diags: Vec<_> = warnings.iter().cloned().collect();
+        diags.push(SourceDiagnostic::warning(errors[0].span, "at the error"));
+        diags.extend(errors.iter().cloned());
+        diags.push(warnings[0].clone());
+
+        sort_diagnostics(&world, &mut diags);
+        let messages: Vec<_> = diags
+            .iter()
+            .map(|diag| (diag.severity, diag.message.as_str()))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                (Severity::Warning, "no text within stars"),
+                (Severity::Error, "cannot add integer and string"),
+                (Severity::Warning, "at the error"),
+                (Severity::Warning, "no text within underscores"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_diagnostic_round_trip() {
+        let world = TestWorld::new("#let f(x) = x + \"a\"\n😀 ä #f(1)");
+        let errors = crate::compile(&world, &mut Tracer::new()).unwrap_err();
+        let name = |id: FileId| Some(id.vpath().as_rootless_path().display().to_string());
+        let json = JsonDiagnostic::new(&world, &name, &errors[0]);
+
+        let position = |byte, line, column, utf16_column| JsonPosition {
+            byte,
+            line,
+            column,
+            utf16_column,
+        };
+        let range = |start, end| Some(JsonRange { start, end });
+        assert_eq!(
+            json,
+            JsonDiagnostic {
+                severity: Severity::Error,
+                message: "cannot add integer and string".into(),
+                hints: vec![],
+                file: Some("main.typ".into()),
+                range: range(position(12, 1, 13, 12), position(19, 1, 20, 19)),
+                trace: vec![JsonTracepoint {
+                    message: "error occurred in this call of function `f`".into(),
+                    file: Some("main.typ".into()),
+                    // The emoji takes two UTF-16 code units.
+                    range: range(position(29, 2, 6, 6), position(33, 2, 10, 10)),
+                }],
+            }
+        );
+
+        // Each diagnostic is printed on a single line and parses back into
+        // the same diagnostic.
+        let line = serde_json::to_string(&json).unwrap();
+        assert!(!line.contains('\n'));
+        assert!(line.contains(r#""severity":"error""#));
+        assert_eq!(serde_json::from_str::<JsonDiagnostic>(&line).unwrap(), json);
+    }
+
+    #[test]
+    fn test_json_diagnostic_round_trip_warning() {
+        let world = TestWorld::new("Hi\n😀 **");
+        let mut tracer = Tracer::new();
+        crate::compile(&world, &mut tracer).unwrap();
+        let warnings = tracer.warnings();
+        assert_eq!(warnings.len(), 1);
+
+        let name = |id: FileId| Some(id.vpath().as_rootless_path().display().to_string());
+        let json = JsonDiagnostic::new(&world, &name, &warnings[0]);
+        assert_eq!(json.severity, Severity::Warning);
+        assert_eq!(json.message, "no text within stars");
+        assert_eq!(json.file.as_deref(), Some("main.typ"));
+        assert!(json.trace.is_empty());
+
+        let position = |byte, line, column, utf16_column| JsonPosition {
+            byte,
+            line,
+            column,
+            utf16_column,
+        };
+        let range = json.range.as_ref().unwrap();
+        assert_eq!(range.start, position(8, 2, 3, 3));
+        assert_eq!(range.end, position(10, 2, 5, 5));
+
+        let line = serde_json::to_string(&json).unwrap();
+        assert!(line.contains(r#""severity":"warning""#));
+        assert_eq!(serde_json::from_str::<JsonDiagnostic>(&line).unwrap(), json);
+    }
+}
@@ -3,6 +3,7 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use comemo::{Track, Tracked, TrackedMut, Validate};
+use ecow::EcoVec;
 
 use crate::diag::SourceResult;
 use crate::eval::Tracer;
@@ -142,6 +143,13 @@ impl<'a> Route<'a> {
         self.id == Some(id) || self.outer.is_some_and(|outer| outer.contains(id))
     }
 
+    /// The files on the route, starting with the outermost one.
+    pub fn files(&self) -> EcoVec<FileId> {
+        let mut files = self.outer.map(|outer| outer.files()).unwrap_or_default();
+        files.extend(self.id);
+        files
+    }
+
     /// Whether the route's depth is less than or equal to the given depth.
     pub fn within(&self, depth: usize) -> bool {
         use Ordering::Relaxed;
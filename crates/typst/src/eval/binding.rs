@@ -1,6 +1,6 @@
 use std::collections::HashSet;
 
-use crate::diag::{bail, At, SourceResult};
+use crate::diag::{bail, warning, At, SourceResult};
 use crate::eval::{Access, Eval, Vm};
 use crate::foundations::{Array, Dict, Value};
 use crate::syntax::ast::{self, AstNode};
@@ -18,7 +18,17 @@ impl Eval for ast::LetBinding<'_> {
         }
 
         match self.kind() {
-            ast::LetBindingKind::Normal(pattern) => destructure(vm, pattern, value)?,
+            ast::LetBindingKind::Normal(pattern) => {
+                let shadowed: Vec<_> = pattern
+                    .bindings()
+                    .into_iter()
+                    .filter(|ident| defines_func(vm, ident))
+                    .collect();
+                destructure(vm, pattern, value)?;
+                for ident in shadowed {
+                    check_shadowing(vm, ident);
+                }
+            }
             ast::LetBindingKind::Closure(ident) => vm.define(ident, value),
         }
 
@@ -26,6 +36,29 @@ impl Eval for ast::LetBinding<'_> {
     }
 }
 
+/// Whether the identifier currently refers to a function, either one defined
+/// in the document or one from the standard library.
+fn defines_func(vm: &Vm, ident: &ast::Ident) -> bool {
+    matches!(vm.scopes.get(ident), Ok(Value::Func(_)))
+}
+
+/// Warns when a binding shadows a function with something that isn't a
+/// function, as later calls of the function would fail confusingly.
+///
+/// Wrapping or redefining a function is a common idiom and thus not reported.
+fn check_shadowing(vm: &mut Vm, ident: ast::Ident) {
+    if matches!(vm.scopes.top.get(&ident), Some(Value::Func(_))) {
+        return;
+    }
+
+    vm.engine.tracer.warn(warning!(
+        ident.span(),
+        "this shadows the function `{}`",
+        ident.as_str();
+        hint: "consider using a different name for this variable",
+    ));
+}
+
 impl Eval for ast::DestructAssignment<'_> {
     type Output = Value;
 
@@ -609,4 +609,24 @@ mod tests {
         test("#{ (body, a) = (y, 1) }", &["y"]);
         test("#(x.at(y) = 5)", &["x", "y"])
     }
+
+    #[test]
+    fn test_captures_shadowing() {
+        // Parameters of nested closures shadow outer names.
+        test("#(x => (x => x))", &[]);
+        test("#let f(x) = (y => x + y + z)", &["z"]);
+        test("#(a => (b => (c => a + b + c + z)))", &["z"]);
+
+        // A let binding shadows only after its initializer.
+        test("#(x => { let x = x; x })", &[]);
+        test("#{ let y = x; let x = y; x }", &["x"]);
+
+        // Bindings in one block don't leak into another.
+        test("#{ let x = 1; (y => (x => x + y)) }; #x", &["x"]);
+        test("#(x => [#let y = x] + [#y])", &["y"]);
+
+        // Deep nesting.
+        let text = format!("#{}z{}", "(x => ".repeat(100), ")".repeat(100));
+        test(&text, &["z"]);
+    }
 }
@@ -1,7 +1,8 @@
 use unicode_segmentation::UnicodeSegmentation;
 
 use crate::diag::{bail, error, At, SourceDiagnostic, SourceResult};
-use crate::eval::{destructure, ops, Eval, Vm};
+use crate::eval::ops::{self, Budget};
+use crate::eval::{destructure, Eval, Vm};
 use crate::foundations::{IntoValue, Value};
 use crate::syntax::ast::{self, AstNode};
 use crate::syntax::{Span, SyntaxKind, SyntaxNode};
@@ -60,6 +61,7 @@ impl Eval for ast::WhileLoop<'_> {
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let flow = vm.flow.take();
         let mut output = Value::None;
+        let mut budget = Budget::new(&vm.engine);
         let mut i = 0;
 
         let condition = self.condition();
@@ -76,6 +78,7 @@ impl Eval for ast::WhileLoop<'_> {
             }
 
             let value = body.eval(vm)?;
+            spend(&mut budget, &value, body.span())?;
             output = ops::join(output, value).at(body.span())?;
 
             match vm.flow {
@@ -106,6 +109,7 @@ impl Eval for ast::ForLoop<'_> {
     fn eval(self, vm: &mut Vm) -> SourceResult<Self::Output> {
         let flow = vm.flow.take();
         let mut output = Value::None;
+        let mut budget = Budget::new(&vm.engine);
 
         macro_rules! iter {
             (for $pat:ident in $iterable:expr) => {{
@@ -117,6 +121,7 @@ impl Eval for ast::ForLoop<'_> {
 
                     let body = self.body();
                     let value = body.eval(vm)?;
+                    spend(&mut budget, &value, body.span())?;
                     output = ops::join(output, value).at(body.span())?;
 
                     match vm.flow {
@@ -206,6 +211,18 @@ impl Eval for ast::FuncReturn<'_> {
     }
 }
 
+/// Spend the elements of content produced by a loop iteration and fail if
+/// the loop produced too many.
+fn spend(budget: &mut Budget, value: &Value, span: Span) -> SourceResult<()> {
+    if !budget.spend(value) {
+        bail!(
+            span, "loop produces too much content";
+            hint: "a loop can produce at most {} elements", budget.limit()
+        );
+    }
+    Ok(())
+}
+
 /// Whether the expression always evaluates to the same value.
 fn is_invariant(expr: &SyntaxNode) -> bool {
     match expr.cast() {
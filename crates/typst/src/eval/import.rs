@@ -163,7 +163,15 @@ fn import_file(vm: &mut Vm, path: &str, span: Span) -> SourceResult<Module> {
 
     // Prevent cyclic importing.
     if vm.engine.route.contains(source.id()) {
-        bail!(span, "cyclic import");
+        let files = vm.engine.route.files();
+        let start = files.iter().position(|&file| file == id).unwrap_or_default();
+        let chain = files[start..]
+            .iter()
+            .chain([&id])
+            .map(|&file| display_file(file))
+            .collect::<Vec<_>>()
+            .join(" → ");
+        bail!(span, "cyclic import"; hint: "import cycle: {chain}");
     }
 
     // Evaluate the file.
@@ -176,3 +184,57 @@ fn import_file(vm: &mut Vm, path: &str, span: Span) -> SourceResult<Module> {
     )
     .trace(world, point, span)
 }
+
+/// Format a file for display in an import chain. Always separates the
+/// components of the path with `/`, independently of the platform.
+fn display_file(id: FileId) -> EcoString {
+    let path = id
+        .vpath()
+        .as_rootless_path()
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    match id.package() {
+        Some(package) => eco_format!("{package}/{path}"),
+        None => path.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::Tracer;
+    use crate::testing::TestWorld;
+    use crate::WorldExt;
+
+    #[test]
+    fn test_cyclic_import_chain() {
+        let world = TestWorld::new("#import \"chapters/a.typ\"")
+            .with_source("chapters/a.typ", "#import \"/util.typ\"")
+            .with_source("util.typ", "#let x = 1\n#import \"chapters/a.typ\"");
+
+        let errors = crate::compile(&world, &mut Tracer::new()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+
+        // The error is in the file that closes the cycle and names the whole
+        // chain in import order.
+        let error = &errors[0];
+        assert_eq!(error.message, "cyclic import");
+        assert_eq!(error.hints.len(), 1);
+        assert_eq!(
+            error.hints[0],
+            "import cycle: chapters/a.typ → util.typ → chapters/a.typ"
+        );
+        let id = error.span.id().unwrap();
+        assert_eq!(id.vpath().as_rootless_path(), std::path::Path::new("util.typ"));
+        assert_eq!(world.range(error.span), Some(19..35));
+
+        // Each import on the way there is traced back.
+        let traced: Vec<_> = error
+            .trace
+            .iter()
+            .map(|point| point.span.id().unwrap().vpath().as_rootless_path().to_owned())
+            .collect();
+        assert_eq!(traced, ["chapters/a.typ", "main.typ"].map(std::path::PathBuf::from));
+    }
+}
@@ -5,13 +5,15 @@ use std::cmp::Ordering;
 use ecow::eco_format;
 
 use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::engine::Engine;
 use crate::eval::{access_dict, Access, Eval, Vm};
-use crate::foundations::{format_str, Datetime, IntoValue, Regex, Repr, Value};
+use crate::foundations::{format_str, Content, Datetime, IntoValue, Regex, Repr, Value};
 use crate::layout::{Alignment, Length, Rel};
 use crate::syntax::ast::{self, AstNode};
 use crate::text::TextElem;
 use crate::util::Numeric;
 use crate::visualize::Stroke;
+use crate::World;
 
 impl Eval for ast::Unary<'_> {
     type Output = Value;
@@ -34,7 +36,10 @@ impl Eval for ast::Binary<'_> {
         match self.op() {
             ast::BinOp::Add => apply_binary(self, vm, add),
             ast::BinOp::Sub => apply_binary(self, vm, sub),
-            ast::BinOp::Mul => apply_binary(self, vm, mul),
+            ast::BinOp::Mul => {
+                let max = vm.engine.world.library().max_elements;
+                apply_binary(self, vm, |lhs, rhs| mul(lhs, rhs, max))
+            }
             ast::BinOp::Div => apply_binary(self, vm, div),
             ast::BinOp::And => apply_binary(self, vm, and),
             ast::BinOp::Or => apply_binary(self, vm, or),
@@ -49,7 +54,10 @@ impl Eval for ast::Binary<'_> {
             ast::BinOp::Assign => apply_assignment(self, vm, |_, b| Ok(b)),
             ast::BinOp::AddAssign => apply_assignment(self, vm, add),
             ast::BinOp::SubAssign => apply_assignment(self, vm, sub),
-            ast::BinOp::MulAssign => apply_assignment(self, vm, mul),
+            ast::BinOp::MulAssign => {
+                let max = vm.engine.world.library().max_elements;
+                apply_assignment(self, vm, |lhs, rhs| mul(lhs, rhs, max))
+            }
             ast::BinOp::DivAssign => apply_assignment(self, vm, div),
         }
     }
@@ -59,7 +67,7 @@ impl Eval for ast::Binary<'_> {
 fn apply_binary(
     binary: ast::Binary,
     vm: &mut Vm,
-    op: fn(Value, Value) -> StrResult<Value>,
+    op: impl FnOnce(Value, Value) -> StrResult<Value>,
 ) -> SourceResult<Value> {
     let lhs = binary.lhs().eval(vm)?;
 
@@ -78,7 +86,7 @@ fn apply_binary(
 fn apply_assignment(
     binary: ast::Binary,
     vm: &mut Vm,
-    op: fn(Value, Value) -> StrResult<Value>,
+    op: impl FnOnce(Value, Value) -> StrResult<Value>,
 ) -> SourceResult<Value> {
     let rhs = binary.rhs().eval(vm)?;
     let lhs = binary.lhs();
@@ -284,8 +292,9 @@ pub fn sub(lhs: Value, rhs: Value) -> StrResult<Value> {
     })
 }
 
-/// Compute the product of two values.
-pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
+/// Compute the product of two values. Repeated content may have at most
+/// `max_elements` elements.
+pub fn mul(lhs: Value, rhs: Value, max_elements: usize) -> StrResult<Value> {
     use Value::*;
     Ok(match (lhs, rhs) {
         (Int(a), Int(b)) => Int(a.checked_mul(b).ok_or_else(too_large)?),
@@ -331,8 +340,8 @@ pub fn mul(lhs: Value, rhs: Value) -> StrResult<Value> {
         (Int(a), Str(b)) => Str(b.repeat(Value::Int(a).cast()?)?),
         (Array(a), Int(b)) => Array(a.repeat(Value::Int(b).cast()?)?),
         (Int(a), Array(b)) => Array(b.repeat(Value::Int(a).cast()?)?),
-        (Content(a), b @ Int(_)) => Content(a.repeat(b.cast()?)),
-        (a @ Int(_), Content(b)) => Content(b.repeat(a.cast()?)),
+        (Content(a), b @ Int(_)) => Content(repeat_content(&a, b.cast()?, max_elements)?),
+        (a @ Int(_), Content(b)) => Content(repeat_content(&b, a.cast()?, max_elements)?),
 
         (Int(a), Duration(b)) => Duration(b * (a as f64)),
         (Float(a), Duration(b)) => Duration(b * a),
@@ -388,6 +397,46 @@ pub fn div(lhs: Value, rhs: Value) -> StrResult<Value> {
     })
 }
 
+/// Repeat content, unless the result would have more than `max` elements.
+fn repeat_content(content: &Content, count: usize, max: usize) -> StrResult<Content> {
+    let limit = max / count.max(1);
+    if count > max || content.weigh(limit) > limit {
+        bail!("cannot repeat this content {count} times (too many elements)");
+    }
+    Ok(content.repeat(count))
+}
+
+/// Counts the content elements that evaluation joins into one value, so that
+/// it can stop before producing more of them than the library allows.
+pub(crate) struct Budget {
+    limit: usize,
+    spent: usize,
+}
+
+impl Budget {
+    /// Create a budget with the library's element limit.
+    pub fn new(engine: &Engine) -> Self {
+        Self {
+            limit: engine.world.library().max_elements,
+            spent: 0,
+        }
+    }
+
+    /// The maximum number of elements.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Spend the elements of a value that becomes part of the joined value.
+    /// Returns `false` once more elements were spent than allowed.
+    pub fn spend(&mut self, value: &Value) -> bool {
+        if let Value::Content(content) = value {
+            self.spent += content.weigh(self.limit.saturating_sub(self.spent));
+        }
+        self.spent <= self.limit
+    }
+}
+
 /// Whether a value is a numeric zero.
 fn is_zero(v: &Value) -> bool {
     use Value::*;
@@ -1,13 +1,17 @@
 use std::collections::HashSet;
+use std::sync::Arc;
 
+use comemo::Prehashed;
 use ecow::EcoVec;
 
 use crate::diag::SourceDiagnostic;
 use crate::foundations::{Styles, Value};
+use crate::layout::Page;
 use crate::syntax::{FileId, Span};
 use crate::util::hash128;
 
-/// Traces warnings and which values existed for an expression at a span.
+/// Traces warnings, which values existed for an expression at a span, and
+/// pages as they are laid out.
 #[derive(Default, Clone)]
 pub struct Tracer {
     inspected: Option<Span>,
@@ -15,8 +19,12 @@ pub struct Tracer {
     warnings_set: HashSet<u128>,
     delayed: EcoVec<SourceDiagnostic>,
     values: EcoVec<(Value, Option<Styles>)>,
+    observer: Option<Arc<PageObserver>>,
 }
 
+/// A function that is notified about laid out pages.
+type PageObserver = dyn Fn(usize, &Page) + Send + Sync;
+
 impl Tracer {
     /// The maximum number of inspected values.
     pub const MAX_VALUES: usize = 10;
@@ -46,6 +54,20 @@ impl Tracer {
     pub fn values(self) -> EcoVec<(Value, Option<Styles>)> {
         self.values
     }
+
+    /// Observe pages as soon as they are laid out, for example to show them
+    /// in a preview before the whole document is done.
+    ///
+    /// The observer is called with the zero-based index of each page, in
+    /// order. This also happens for pages whose layout was cached. Since layout
+    /// is repeated until introspection converges, the same index may be
+    /// reported multiple times. A later report supersedes an earlier one.
+    pub fn observe_pages(
+        &mut self,
+        observer: impl Fn(usize, &Page) + Send + Sync + 'static,
+    ) {
+        self.observer = Some(Arc::new(observer));
+    }
 }
 
 #[comemo::track]
@@ -79,4 +101,55 @@ impl Tracer {
             self.values.push((value, styles));
         }
     }
+
+    /// Whether someone observes pages as they are laid out.
+    pub fn observes_pages(&self) -> bool {
+        self.observer.is_some()
+    }
+
+    /// Notify the page observer about a laid out page.
+    ///
+    /// The page is prehashed because the call is recorded for replay.
+    pub fn page(&mut self, index: usize, page: Prehashed<Page>) {
+        if let Some(observer) = &self.observer {
+            observer(index, &page);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::layout::Abs;
+    use crate::testing::TestWorld;
+
+    #[test]
+    fn test_observe_pages() {
+        let world = TestWorld::new(
+            "#for i in range(1, 6) { page(width: i * 10pt, height: 10pt)[] }",
+        );
+
+        // The second compilation is served from the cache, but must still
+        // report every page.
+        for _ in 0..2 {
+            let seen = Arc::new(Mutex::new(vec![]));
+            let sink = seen.clone();
+            let mut tracer = Tracer::new();
+            tracer.observe_pages(move |i, page| {
+                sink.lock().unwrap().push((i, page.frame.width()));
+            });
+
+            let document = crate::compile(&world, &mut tracer).unwrap();
+            assert_eq!(document.pages.len(), 5);
+
+            // Each layout iteration reports all pages again.
+            let expected: Vec<_> =
+                (0..5).map(|i| (i, Abs::pt(10.0 * (i + 1) as f64))).collect();
+            let seen = seen.lock().unwrap();
+            assert!(!seen.is_empty());
+            assert!(seen.chunks(5).all(|chunk| chunk == expected));
+        }
+    }
 }
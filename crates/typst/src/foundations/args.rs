@@ -234,16 +234,73 @@ impl Args {
     /// Return an "unexpected argument" error if there is any remaining
     /// argument.
     pub fn finish(self) -> SourceResult<()> {
-        if let Some(arg) = self.items.first() {
-            match &arg.name {
-                Some(name) => bail!(arg.span, "unexpected argument: {name}"),
-                _ => bail!(arg.span, "unexpected argument"),
+        self.finish_with([])
+    }
+
+    /// Like [`finish`](Self::finish), but the error for an unexpected named
+    /// argument hints at the closest of the given parameter names or, if none
+    /// is close and there are only a few, lists all of them.
+    pub fn finish_with<'a>(
+        self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> SourceResult<()> {
+        let Some(arg) = self.items.first() else { return Ok(()) };
+        let Some(name) = &arg.name else { bail!(arg.span, "unexpected argument") };
+
+        let mut diag = error!(arg.span, "unexpected argument: {name}");
+        let names: Vec<&str> = names.into_iter().collect();
+
+        // A known parameter can still be unexpected, for example if it
+        // conflicts with another one. Then, there is nothing to suggest.
+        if names.contains(&name.as_str()) {
+            return Err(eco_vec![diag]);
+        }
+
+        let closest = names
+            .iter()
+            .map(|&candidate| (edit_distance(name, candidate), candidate))
+            .filter(|&(distance, _)| distance <= (name.len() / 3).max(1))
+            .min_by_key(|&(distance, _)| distance);
+
+        match (closest, names.as_slice()) {
+            (Some((_, candidate)), _) => {
+                diag.hint(eco_format!("did you mean `{candidate}`?"));
+            }
+            (None, [only]) => {
+                diag.hint(eco_format!("the only valid named argument is `{only}`"));
+            }
+            (None, _) if (2..=8).contains(&names.len()) => {
+                let names: Vec<_> =
+                    names.iter().map(|name| eco_format!("`{name}`")).collect();
+                diag.hint(eco_format!(
+                    "valid named arguments are {}",
+                    repr::separated_list(&names, "and")
+                ));
             }
+            _ => {}
         }
-        Ok(())
+
+        Err(eco_vec![diag])
     }
 }
 
+/// The number of single-character edits needed to turn one string into
+/// another.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &y) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(x != y);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 #[scope]
 impl Args {
     /// Construct spreadable arguments in place.
@@ -368,3 +425,83 @@ where
         Args::new(fallback, self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ecow::{eco_format, EcoString};
+
+    use crate::eval::Tracer;
+    use crate::foundations::{Func, Scope, Value};
+    use crate::testing::TestWorld;
+
+    /// Calls every function of the standard library with a bogus named
+    /// argument, and uses it in a set rule if it is an element. Each call must
+    /// result in exactly one error. For set rules and for functions without
+    /// required parameters, it must be about the bogus argument.
+    #[test]
+    fn test_unexpected_named_argument_sweep() {
+        let mut funcs = vec![];
+        collect(&mut funcs, "", crate::Library::default().global.scope(), 0);
+        assert!(funcs.len() > 100);
+
+        let mut failures = vec![];
+        let mut check = |call: EcoString, required: bool| {
+            let world = TestWorld::new(&call);
+            let Err(errors) = crate::compile(&world, &mut Tracer::new()) else {
+                failures.push(eco_format!("{call}: no error"));
+                return;
+            };
+
+            if errors.len() != 1 {
+                failures.push(eco_format!("{call}: {} errors", errors.len()));
+            } else if !required && errors[0].message != "unexpected argument: bogus" {
+                failures.push(eco_format!("{call}: {}", errors[0].message));
+            }
+        };
+
+        for (path, func) in funcs {
+            if func.element().is_some() {
+                check(eco_format!("#set {path}(bogus: 1)"), false);
+            }
+
+            // The first captures all arguments, the second can only be set.
+            if path == "arguments" || path == "document" {
+                continue;
+            }
+
+            let required = func.params().unwrap_or_default().iter().any(|p| p.required);
+            check(eco_format!("#{path}(bogus: 1)"), required);
+        }
+
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+
+    /// Collect the functions in a scope along with their paths.
+    fn collect(
+        funcs: &mut Vec<(EcoString, Func)>,
+        prefix: &str,
+        scope: &Scope,
+        depth: usize,
+    ) {
+        for (name, value) in scope.iter() {
+            let path = eco_format!("{prefix}{name}");
+            match value {
+                Value::Func(func) => {
+                    funcs.push((path.clone(), func.clone()));
+                    if let Some(scope) = func.scope() {
+                        collect(funcs, &eco_format!("{path}."), scope, depth + 1);
+                    }
+                }
+                Value::Type(ty) => {
+                    if let Ok(func) = ty.constructor() {
+                        funcs.push((path, func));
+                    }
+                }
+                Value::Module(module) if depth < 2 => {
+                    collect(funcs, &eco_format!("{path}."), module.scope(), depth + 1);
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -8,14 +8,15 @@ use ecow::{eco_format, EcoString, EcoVec};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
-use crate::diag::{At, SourceResult, StrResult};
+use crate::diag::{bail, At, SourceResult, StrResult};
 use crate::engine::Engine;
-use crate::eval::ops;
+use crate::eval::ops::{self, Budget};
 use crate::foundations::{
     cast, func, repr, scope, ty, Args, Bytes, CastInfo, Context, FromValue, Func,
     IntoValue, Reflect, Repr, Value, Version,
 };
 use crate::syntax::Span;
+use crate::World;
 
 /// Create a new [`Array`] from values.
 #[macro_export]
@@ -558,17 +559,24 @@ impl Array {
     #[func]
     pub fn sum(
         self,
+        /// The engine.
+        engine: &mut Engine,
         /// What to return if the array is empty. Must be set if the array can
         /// be empty.
         #[named]
         default: Option<Value>,
     ) -> StrResult<Value> {
+        let mut budget = Budget::new(engine);
         let mut iter = self.into_iter();
         let mut acc = iter
             .next()
             .or(default)
             .ok_or("cannot calculate sum of empty array with no default")?;
+        budget.spend(&acc);
         for item in iter {
+            if !budget.spend(&item) {
+                bail!("cannot sum this content (too many elements)");
+            }
             acc = ops::add(acc, item)?;
         }
         Ok(acc)
@@ -579,6 +587,8 @@ impl Array {
     #[func]
     pub fn product(
         self,
+        /// The engine.
+        engine: &mut Engine,
         /// What to return if the array is empty. Must be set if the array can
         /// be empty.
         #[named]
@@ -590,7 +600,7 @@ impl Array {
             .or(default)
             .ok_or("cannot calculate product of empty array with no default")?;
         for item in iter {
-            acc = ops::mul(acc, item)?;
+            acc = ops::mul(acc, item, engine.world.library().max_elements)?;
         }
         Ok(acc)
     }
@@ -672,6 +682,8 @@ impl Array {
     #[func]
     pub fn join(
         self,
+        /// The engine.
+        engine: &mut Engine,
         /// A value to insert between each item of the array.
         #[default]
         separator: Option<Value>,
@@ -682,18 +694,26 @@ impl Array {
         let len = self.0.len();
         let separator = separator.unwrap_or(Value::None);
 
+        let mut budget = Budget::new(engine);
+        let mut join = |result: Value, value: Value| {
+            if !budget.spend(&value) {
+                bail!("cannot join this content (too many elements)");
+            }
+            ops::join(result, value)
+        };
+
         let mut last = last;
         let mut result = Value::None;
         for (i, value) in self.into_iter().enumerate() {
             if i > 0 {
                 if i + 1 == len && last.is_some() {
-                    result = ops::join(result, last.take().unwrap())?;
+                    result = join(result, last.take().unwrap())?;
                 } else {
-                    result = ops::join(result, separator.clone())?;
+                    result = join(result, separator.clone())?;
                 }
             }
 
-            result = ops::join(result, value)?;
+            result = join(result, value)?;
         }
 
         Ok(result)
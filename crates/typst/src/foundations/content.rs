@@ -91,6 +91,10 @@ struct Inner<T: ?Sized + 'static> {
     /// - If bit n is set, the element is guarded against the n-th show rule
     ///   recipe from the top of the style chain (counting from 1).
     lifecycle: BitSet,
+    /// The calls of user-defined functions that returned this content,
+    /// starting with the innermost one.
+    #[cfg(feature = "debug")]
+    calls: ecow::EcoVec<Span>,
     /// The element's raw data.
     elem: LazyHash<T>,
 }
@@ -103,6 +107,8 @@ impl Content {
                 label: None,
                 location: None,
                 lifecycle: BitSet::new(),
+                #[cfg(feature = "debug")]
+                calls: ecow::EcoVec::new(),
                 elem: elem.into(),
             }),
             span: Span::detached(),
@@ -158,6 +164,23 @@ impl Content {
         self.make_mut().location = Some(location);
     }
 
+    /// The calls of user-defined functions that returned this content,
+    /// starting with the innermost one.
+    #[cfg(feature = "debug")]
+    pub fn calls(&self) -> &[Span] {
+        &self.inner.calls
+    }
+
+    /// Remember that the call at the span returned this content. Only the
+    /// innermost [`Meta::MAX_CALLS`] calls are kept.
+    #[cfg(feature = "debug")]
+    pub fn traced(mut self, span: Span) -> Self {
+        if self.inner.calls.len() < Meta::MAX_CALLS {
+            self.make_mut().calls.push(span);
+        }
+        self
+    }
+
     /// Disable a show rule recipe.
     pub fn guarded(mut self, index: RecipeIndex) -> Self {
         self.make_mut().lifecycle.insert(index.0);
@@ -361,6 +384,25 @@ impl Content {
         Self::sequence(std::iter::repeat_with(|| self.clone()).take(count))
     }
 
+    /// Count the elements in this content, looking into sequences and styled
+    /// content. Stops counting once the count exceeds `limit`.
+    pub(crate) fn weigh(&self, limit: usize) -> usize {
+        if let Some(sequence) = self.to_packed::<SequenceElem>() {
+            let mut weight = 0;
+            for child in &sequence.children {
+                weight += child.weigh(limit - weight);
+                if weight > limit {
+                    break;
+                }
+            }
+            weight
+        } else if let Some(styled) = self.to_packed::<StyledElem>() {
+            styled.child.weigh(limit)
+        } else {
+            1
+        }
+    }
+
     /// Style this content with a style entry.
     pub fn styled(mut self, style: impl Into<Style>) -> Self {
         if let Some(style_elem) = self.to_packed_mut::<StyledElem>() {
@@ -711,6 +753,8 @@ impl<T: NativeElement> Bounds for T {
                 label: inner.label,
                 location: inner.location,
                 lifecycle: inner.lifecycle.clone(),
+                #[cfg(feature = "debug")]
+                calls: inner.calls.clone(),
                 elem: LazyHash::with_hash(self.clone(), inner.elem.hash()),
             }),
             span,
@@ -2,16 +2,21 @@ use std::cmp::Ordering;
 use std::hash::Hash;
 use std::ops::{Add, Sub};
 
+use comemo::Tracked;
 use ecow::{eco_format, EcoString, EcoVec};
 use time::error::{Format, InvalidFormatDescription};
+use time::format_description::OwnedFormatItem;
+use time::formatting::Formattable;
 use time::macros::format_description;
 use time::{format_description, Month, PrimitiveDateTime};
 
 use crate::diag::{bail, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, func, repr, scope, ty, Dict, Duration, Repr, Smart, Str, Value,
+    cast, func, repr, scope, ty, Context, Dict, Duration, Repr, Smart, Str, Value,
 };
+use crate::syntax::Span;
+use crate::text::{Lang, Locale};
 use crate::World;
 
 /// Represents a date, a time, or a combination of both.
@@ -71,9 +76,8 @@ use crate::World;
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     month is padded.
 ///   - `repr`: Can be either `numerical`, `long` or `short`. Specifies if the
-///     month should be displayed as a number or a word. Unfortunately, when
-///     choosing the word representation, it can currently only display the
-///     English version. In the future, it is planned to support localization.
+///     month should be displayed as a number or a word. The word is displayed
+///     in the language passed to [`display`]($datetime.display).
 /// - `day`: Displays the day of the datetime.
 ///   - `padding`: Can be either `zero`, `space` or `none`. Specifies how the
 ///     day is padded.
@@ -85,10 +89,10 @@ use crate::World;
 ///      and 53.
 /// - `weekday`: Displays the weekday of the date.
 ///   - `repr` Can be either `long`, `short`, `sunday` or `monday`. In the case
-///     of `long` and `short`, the corresponding English name will be displayed
-///     (same as for the month, other languages are currently not supported). In
-///     the case of `sunday` and `monday`, the numerical value will be displayed
-///     (assuming Sunday and Monday as the first day of the week, respectively).
+///     of `long` and `short`, the name of the weekday will be displayed (in the
+///     same language as the month). In the case of `sunday` and `monday`, the
+///     numerical value will be displayed (assuming Sunday and Monday as the
+///     first day of the week, respectively).
 ///   - `one_indexed`: Can be either `true` or `false`. Defines whether the
 ///     numerical representation of the week starts with 0 or 1.
 /// - `hour`: Displays the hour of the date.
@@ -207,6 +211,46 @@ impl Datetime {
             Datetime::Time(_) => "time",
         }
     }
+
+    /// Format the datetime with the `time` crate.
+    fn format(&self, format: &(impl Formattable + ?Sized)) -> Result<String, Format> {
+        match self {
+            Self::Date(date) => date.format(format),
+            Self::Time(time) => time.format(format),
+            Self::Datetime(datetime) => datetime.format(format),
+        }
+    }
+
+    /// Format the datetime, taking the names of months and weekdays from the
+    /// locale.
+    fn format_localized(
+        &self,
+        item: &OwnedFormatItem,
+        locale: &mut dyn FnMut() -> &'static Locale,
+        out: &mut String,
+    ) -> Result<(), Format> {
+        match item {
+            OwnedFormatItem::Compound(items) => {
+                for item in items.iter() {
+                    self.format_localized(item, locale, out)?;
+                }
+            }
+            OwnedFormatItem::Optional(item) => {
+                self.format_localized(item, locale, out)?
+            }
+            OwnedFormatItem::Component(_) => {
+                // The names of months and weekdays are the only words that
+                // components display in English.
+                let text = self.format(item)?;
+                match Locale::ENGLISH.translate(&text) {
+                    Some(_) => out.push_str(locale().translate(&text).unwrap_or(&text)),
+                    None => out.push_str(&text),
+                }
+            }
+            _ => out.push_str(&self.format(item)?),
+        }
+        Ok(())
+    }
 }
 
 #[scope]
@@ -319,12 +363,32 @@ impl Datetime {
     /// `[[year]-[month]-[day] [hour]:[minute]:[second]]`.
     ///
     /// See the [format syntax]($datetime/#format) for more information.
+    ///
+    /// ```example
+    /// #let date = datetime(year: 2024, month: 3, day: 1)
+    /// #date.display("[weekday], [day padding:none] [month repr:long]") \
+    /// #date.display("[weekday], [day padding:none]. [month repr:long]", lang: "de")
+    /// ```
     #[func]
     pub fn display(
         &self,
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The callsite span.
+        span: Span,
         /// The format used to display the datetime.
         #[default]
         pattern: Smart<DisplayPattern>,
+        /// The language in which to display the names of months and weekdays.
+        ///
+        /// Defaults to the [text language]($text.lang) if the context is
+        /// known and to English otherwise. English, German, and French are
+        /// supported. Other languages fall back to English with a warning.
+        #[named]
+        #[default]
+        lang: Smart<Lang>,
     ) -> StrResult<EcoString> {
         let pat = |s| format_description::parse_borrowed::<2>(s).unwrap();
         let result = match pattern {
@@ -336,11 +400,19 @@ impl Datetime {
                 }
             },
 
-            Smart::Custom(DisplayPattern(_, format)) => match self {
-                Self::Date(date) => date.format(&format),
-                Self::Time(time) => time.format(&format),
-                Self::Datetime(datetime) => datetime.format(&format),
-            },
+            Smart::Custom(DisplayPattern(_, format)) => {
+                // Only look up the language once a name is displayed, so that
+                // there are no warnings for purely numerical formats.
+                let mut resolved = None;
+                let mut locale = || {
+                    *resolved.get_or_insert_with(|| {
+                        Locale::resolve(engine, context, span, lang)
+                    })
+                };
+
+                let mut out = String::new();
+                self.format_localized(&format, &mut locale, &mut out).map(|_| out)
+            }
         };
         result.map(EcoString::from).map_err(format_time_format_error)
     }
@@ -46,7 +46,11 @@ pub use crate::__dict as dict;
 ///
 /// You can iterate over the pairs in a dictionary using a [for
 /// loop]($scripting/#loops). This will iterate in the order the pairs were
-/// inserted / declared.
+/// inserted / declared. The same order is used when a dictionary is displayed
+/// and by its `keys`, `values`, and `pairs` methods, and removing a pair keeps
+/// the order of the others. This makes the output of a document independent
+/// of how the dictionary is stored internally. Two dictionaries are equal if
+/// they contain the same pairs, regardless of their order.
 ///
 /// # Example
 /// ```example
@@ -283,20 +283,41 @@ impl Func {
             }
             Repr::Element(func) => {
                 let value = func.construct(engine, &mut args)?;
-                args.finish()?;
+                args.finish_with(
+                    func.params()
+                        .iter()
+                        .filter(|param| param.named)
+                        .map(|param| param.name),
+                )?;
                 Ok(Value::Content(value))
             }
-            Repr::Closure(closure) => crate::eval::call_closure(
-                self,
-                closure,
-                engine.world,
-                engine.introspector,
-                engine.route.track(),
-                engine.locator.track(),
-                TrackedMut::reborrow_mut(&mut engine.tracer),
-                context,
-                args,
-            ),
+            Repr::Closure(closure) => {
+                #[cfg(feature = "debug")]
+                let span = args.span;
+                let value = crate::eval::call_closure(
+                    self,
+                    closure,
+                    engine.world,
+                    engine.introspector,
+                    engine.route.track(),
+                    engine.locator.track(),
+                    TrackedMut::reborrow_mut(&mut engine.tracer),
+                    context,
+                    args,
+                )?;
+
+                // The call site is stored out-of-band, so that the returned
+                // content still compares and prints as without the feature.
+                #[cfg(feature = "debug")]
+                let value = match value {
+                    Value::Content(content) if !span.is_detached() => {
+                        Value::Content(content.traced(span))
+                    }
+                    value => value,
+                };
+
+                Ok(value)
+            }
             Repr::With(with) => {
                 args.items = with.1.items.iter().cloned().chain(args.items).collect();
                 with.0.call(engine, context, args)
@@ -25,6 +25,7 @@ mod methods;
 mod module;
 mod none;
 mod plugin;
+mod rng;
 mod scope;
 mod selector;
 mod str;
@@ -54,6 +55,7 @@ pub use self::module::*;
 pub use self::none::*;
 pub use self::plugin::*;
 pub use self::repr::Repr;
+pub use self::rng::*;
 pub use self::scope::*;
 pub use self::selector::*;
 pub use self::str::*;
@@ -85,7 +87,7 @@ use crate::syntax::Spanned;
 pub static FOUNDATIONS: Category;
 
 /// Hook up all `foundations` definitions.
-pub(super) fn define(global: &mut Scope, inputs: Dict) {
+pub(super) fn define(global: &mut Scope, inputs: Dict, variant: Option<EcoString>) {
     global.category(FOUNDATIONS);
     global.define_type::<bool>();
     global.define_type::<i64>();
@@ -106,13 +108,14 @@ pub(super) fn define(global: &mut Scope, inputs: Dict) {
     global.define_type::<Duration>();
     global.define_type::<Version>();
     global.define_type::<Plugin>();
+    global.define_type::<Rng>();
     global.define_func::<repr::repr>();
     global.define_func::<panic>();
     global.define_func::<assert>();
     global.define_func::<eval>();
     global.define_func::<style>();
     global.define_module(calc::module());
-    global.define_module(sys::module(inputs));
+    global.define_module(sys::module(inputs, variant));
 }
 
 /// Fails with an error.
@@ -0,0 +1,175 @@
+use ecow::EcoString;
+
+use crate::diag::{bail, StrResult};
+use crate::foundations::{array, func, scope, ty, Array, Repr, Value};
+
+/// A seeded pseudo-random number generator.
+///
+/// Generating randomness in a document is only useful if it is reproducible:
+/// The same source should always yield the same output. That's why Typst has
+/// no global random state. Instead, you create a generator from a seed and
+/// explicitly thread it through your code. Each method returns an array with
+/// the advanced generator and the generated result, which you can conveniently
+/// destructure.
+///
+/// The generator implements the _xoshiro256\*\*_ algorithm, seeded through
+/// _SplitMix64_. Its output only depends on the seed and is stable across
+/// platforms.
+///
+/// # Example
+/// ```example
+/// #let r = rng(42)
+/// #let (r, die) = r.int(1, 7)
+/// #let (r, coin) = r.pick(("heads", "tails"))
+/// #let (r, order) = r.shuffle((1, 2, 3, 4))
+///
+/// I rolled a #die, the coin
+/// shows #coin, and the order
+/// is #order.map(str).join(", ").
+/// ```
+#[ty(scope)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Rng([u64; 4]);
+
+impl Rng {
+    /// Create a new generator from a seed.
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed;
+        let mut next = || {
+            state = state.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        };
+        Self([next(), next(), next(), next()])
+    }
+
+    /// Produce the next 64 random bits.
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.0;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Produce a uniformly distributed integer in `0..bound`.
+    ///
+    /// Uses rejection sampling to avoid modulo bias. The bound must not be
+    /// zero.
+    fn below(&mut self, bound: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return x % bound;
+            }
+        }
+    }
+
+    /// Produce a uniformly distributed float in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[scope]
+impl Rng {
+    /// Creates a new generator from a seed.
+    ///
+    /// ```example
+    /// #let (_, x) = rng(1).int(0, 100)
+    /// #let (_, y) = rng(1).int(0, 100)
+    /// #(x == y)
+    /// ```
+    #[func(constructor)]
+    pub fn construct(
+        /// The seed. The same seed always produces the same sequence of
+        /// values.
+        seed: i64,
+    ) -> Rng {
+        Self::new(seed as u64)
+    }
+
+    /// Generates a random integer between `min` (inclusive) and `max`
+    /// (exclusive).
+    ///
+    /// Returns an array with the advanced generator and the integer.
+    #[func]
+    pub fn int(
+        self,
+        /// The lower bound (inclusive).
+        min: i64,
+        /// The upper bound (exclusive).
+        max: i64,
+    ) -> StrResult<Array> {
+        if min >= max {
+            bail!("lower bound must be smaller than upper bound");
+        }
+        let mut rng = self;
+        let span = max.abs_diff(min);
+        let value = min.wrapping_add_unsigned(rng.below(span));
+        Ok(array![rng, value])
+    }
+
+    /// Generates a random float between `{0.0}` (inclusive) and `{1.0}`
+    /// (exclusive).
+    ///
+    /// Returns an array with the advanced generator and the float.
+    #[func]
+    pub fn float(self) -> Array {
+        let mut rng = self;
+        let value = rng.unit();
+        array![rng, value]
+    }
+
+    /// Picks a random item from an array.
+    ///
+    /// Returns an array with the advanced generator and the item. Fails if the
+    /// array is empty.
+    #[func]
+    pub fn pick(
+        self,
+        /// The array to pick from.
+        array: Array,
+    ) -> StrResult<Array> {
+        if array.is_empty() {
+            bail!("cannot pick from an empty array");
+        }
+        let mut rng = self;
+        let index = rng.below(array.len() as u64) as usize;
+        let value = array.as_slice()[index].clone();
+        Ok(array![rng, value])
+    }
+
+    /// Randomly shuffles the items of an array.
+    ///
+    /// Returns an array with the advanced generator and the shuffled array.
+    #[func]
+    pub fn shuffle(
+        self,
+        /// The array to shuffle.
+        array: Array,
+    ) -> Array {
+        let mut rng = self;
+        let mut items: Vec<Value> = array.into_iter().collect();
+        for i in (1..items.len()).rev() {
+            let j = rng.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+        let shuffled: Array = items.into_iter().collect();
+        array![rng, shuffled]
+    }
+}
+
+impl Repr for Rng {
+    fn repr(&self) -> EcoString {
+        "rng(..)".into()
+    }
+}
@@ -835,6 +835,13 @@ fn string_is_empty() -> EcoString {
 /// [See here](https://docs.rs/regex/latest/regex/#syntax) for a specification
 /// of the supported syntax.
 ///
+/// In show rules, the expression is matched against each run of text on its
+/// own. A match thus can't span text that is styled differently, like in
+/// `[Ty*pst*]`, or that is interrupted by other content. The text of
+/// [raw]($raw) elements is only matched by show rules that are defined within
+/// a show rule for `raw`, unless its [`text-rules`]($raw.text-rules) setting is
+/// enabled. A single run of text is transformed at most 10 000 times.
+///
 /// # Example
 /// ```example
 /// // Works with string methods.
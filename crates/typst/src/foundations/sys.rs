@@ -1,9 +1,11 @@
 //! System-related things.
 
+use ecow::EcoString;
+
 use crate::foundations::{Dict, Module, Scope, Version};
 
 /// A module with system-related things.
-pub fn module(inputs: Dict) -> Module {
+pub fn module(inputs: Dict, variant: Option<EcoString>) -> Module {
     let mut scope = Scope::deduplicating();
     scope.define(
         "version",
@@ -14,5 +16,34 @@ pub fn module(inputs: Dict) -> Module {
         ]),
     );
     scope.define("inputs", inputs);
+    scope.define("variant", variant);
     Module::new("sys", scope)
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::Tracer;
+    use crate::testing::TestWorld;
+    use crate::Library;
+
+    #[test]
+    fn test_sys_variant() {
+        let pages = |library: Library| {
+            let world = TestWorld::new(
+                "#page[]\n\
+                 #if sys.variant == \"solutions\" { page[] }\n\
+                 #if sys.variant == none { page[]; page[] }",
+            )
+            .with_library(library);
+            crate::compile(&world, &mut Tracer::new()).unwrap().pages.len()
+        };
+
+        // Alternate between the variants so that a result cached for one of
+        // them would be reused for the other.
+        for _ in 0..2 {
+            assert_eq!(pages(Library::builder().with_variant("exam").build()), 1);
+            assert_eq!(pages(Library::builder().with_variant("solutions").build()), 2);
+            assert_eq!(pages(Library::default()), 3);
+        }
+    }
+}
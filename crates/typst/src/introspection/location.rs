@@ -67,7 +67,9 @@ impl Location {
 
     /// Returns a dictionary with the page number and the x, y position for this
     /// location. The page number starts at one and the coordinates are measured
-    /// from the top-left of the page.
+    /// from the top-left of the page. If the page has a
+    /// [bleed]($page.bleed) or [crop marks]($page.marks), the coordinates
+    /// include the extra space they take up around the trimmed page.
     ///
     /// If you only need the page number, use `page()` instead as it allows
     /// Typst to skip unnecessary work.
@@ -31,8 +31,9 @@ use smallvec::SmallVec;
 use crate::foundations::{
     category, elem, ty, Category, Content, Packed, Repr, Scope, Unlabellable,
 };
-use crate::model::Destination;
+use crate::model::{Destination, PdfMarker};
 use crate::realize::{Behave, Behaviour};
+use crate::syntax::Span;
 
 /// Interactions between document parts.
 ///
@@ -98,6 +99,22 @@ pub enum Meta {
     /// in the final frames as it is removed alongside the content that should
     /// be hidden.
     Hide,
+    /// Data for the PDF exporter.
+    Pdf(PdfMarker),
+    /// Indicates that content is not part of the document's text, like line
+    /// numbers. Text extraction skips it and the PDF exporter marks it as an
+    /// artifact.
+    Artifact,
+    /// The call of a user-defined function that produced the content within
+    /// the area this metadata is attached to. Only recorded with the `debug`
+    /// feature.
+    Call(Span),
+}
+
+impl Meta {
+    /// The maximum number of calls attached to a frame. The innermost ones
+    /// are kept.
+    pub const MAX_CALLS: usize = 8;
 }
 
 impl Debug for Meta {
@@ -106,6 +123,9 @@ impl Debug for Meta {
             Self::Link(dest) => write!(f, "Link({dest:?})"),
             Self::Elem(content) => write!(f, "Elem({:?})", content.func()),
             Self::Hide => f.pad("Hide"),
+            Self::Pdf(marker) => write!(f, "Pdf({marker:?})"),
+            Self::Artifact => f.pad("Artifact"),
+            Self::Call(span) => write!(f, "Call({span:?})"),
         }
     }
 }
@@ -163,17 +163,24 @@ impl LayoutMultiple for Packed<ColumnsElem> {
 #[elem(title = "Column Break", Behave)]
 pub struct ColbreakElem {
     /// If `{true}`, the column break is skipped if the current column is
-    /// already empty.
+    /// already empty. Placed elements do not count as content here.
+    ///
+    /// ```example
+    /// #set page(height: 60pt, columns: 2)
+    /// First
+    /// #colbreak()
+    /// #colbreak(weak: true)
+    /// Second
+    /// ```
     #[default(false)]
     pub weak: bool,
 }
 
 impl Behave for Packed<ColbreakElem> {
     fn behaviour(&self) -> Behaviour {
-        if self.weak(StyleChain::default()) {
-            Behaviour::Weak(1)
-        } else {
-            Behaviour::Destructive
-        }
+        // Weak column breaks are also destructive so that they survive next
+        // to fractional spacing. Whether they take effect is only known
+        // during layout.
+        Behaviour::Destructive
     }
 }
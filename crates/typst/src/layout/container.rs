@@ -1,4 +1,4 @@
-use crate::diag::SourceResult;
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, AutoValue, Content, Packed, Resolve, Smart, StyleChain, Value,
@@ -7,7 +7,9 @@ use crate::layout::{
     Abs, Axes, Corners, Em, Fr, Fragment, Frame, FrameKind, LayoutMultiple, Length,
     Ratio, Regions, Rel, Sides, Size, Spacing, VElem,
 };
-use crate::util::Numeric;
+use crate::syntax::{Span, Spanned};
+use crate::text::TextElem;
+use crate::util::{Numeric, Scalar};
 use crate::visualize::{clip_rect, Paint, Stroke};
 
 /// An inline-level container that sizes content.
@@ -45,6 +47,30 @@ pub struct BoxElem {
     /// The height of the box.
     pub height: Smart<Rel<Length>>,
 
+    /// The ratio of the box's width to its height.
+    ///
+    /// When only one of `width` and `height` is set, the other one is derived
+    /// from it. When neither is set, the box takes the full available width.
+    /// If that width is unbounded, as in an auto-sized grid column, the aspect
+    /// ratio has no effect. If both are set, the aspect ratio is ignored. A
+    /// box that would not fit into the available space is scaled down while
+    /// keeping its aspect ratio.
+    ///
+    /// ```example
+    /// #box(aspect: 16/9, width: 80pt, fill: aqua)
+    /// #box(aspect: 1, height: 45pt, fill: eastern)
+    /// ```
+    #[parse({
+        let aspect = args.named::<Spanned<Option<Scalar>>>("aspect")?;
+        if let Some(Spanned { v: Some(ratio), span }) = aspect {
+            if !(ratio.get().is_finite() && ratio.get() > 0.0) {
+                bail!(span, "aspect ratio must be positive");
+            }
+        }
+        aspect.map(|aspect| aspect.v)
+    })]
+    pub aspect: Option<Scalar>,
+
     /// An amount to shift the box's baseline by.
     ///
     /// ```example
@@ -130,6 +156,20 @@ impl Packed<BoxElem> {
             .resolve(styles)
             .zip_map(regions.base(), |s, b| s.map(|v| v.relative_to(b)))
             .unwrap_or(regions.base());
+        let size = clamp_negative_size(engine, size, "box", self.span());
+
+        // Derive the unspecified dimension from the aspect ratio.
+        let (size, expand) = match self.aspect(styles) {
+            Some(aspect) => apply_aspect(
+                engine,
+                size,
+                expand,
+                aspect.get(),
+                regions.size,
+                self.span(),
+            ),
+            None => (size, expand),
+        };
 
         // Apply inset.
         let mut body = self.body(styles).unwrap_or_default();
@@ -141,7 +181,8 @@ impl Packed<BoxElem> {
         // Select the appropriate base and expansion for the child depending
         // on whether it is automatically or relatively sized.
         let pod = Regions::one(size, expand);
-        let mut frame = body.layout(engine, styles, pod)?.into_frame();
+        let backed = TextElem::backed(&self.fill(styles), styles);
+        let mut frame = body.layout(engine, styles.chain(&backed), pod)?.into_frame();
 
         // Enforce correct size.
         *frame.size_mut() = expand.select(size, frame.size());
@@ -343,6 +384,13 @@ pub struct BlockElem {
     #[default(false)]
     #[ghost]
     pub sticky: bool,
+
+    /// Whether the first baseline of this block snaps to the paragraph
+    /// baseline grid.
+    #[internal]
+    #[default(false)]
+    #[ghost]
+    pub grid_snap: bool,
 }
 
 impl LayoutMultiple for Packed<BlockElem> {
@@ -363,17 +411,20 @@ impl LayoutMultiple for Packed<BlockElem> {
         // Resolve the sizing to a concrete size.
         let sizing = Axes::new(self.width(styles), self.height(styles));
         let mut expand = sizing.as_ref().map(Smart::is_custom);
-        let mut size = sizing
+        let size = sizing
             .resolve(styles)
             .zip_map(regions.base(), |s, b| s.map(|v| v.relative_to(b)))
             .unwrap_or(regions.base());
+        let mut size = clamp_negative_size(engine, size, "block", self.span());
 
         // Layout the child.
+        let backed = TextElem::backed(&self.fill(styles), styles);
+        let inner = styles.chain(&backed);
         let mut frames = if self.breakable(styles) {
             // Measure to ensure frames for all regions have the same width.
             if sizing.x == Smart::Auto {
                 let pod = Regions::one(size, Axes::splat(false));
-                let frame = body.measure(engine, styles, pod)?.into_frame();
+                let frame = body.measure(engine, inner, pod)?.into_frame();
                 size.x = frame.width();
                 expand.x = true;
             }
@@ -390,11 +441,17 @@ impl LayoutMultiple for Packed<BlockElem> {
             let mut heights = vec![];
             if sizing.y.is_custom() {
                 let mut remaining = size.y;
-                for region in regions.iter() {
-                    let limited = region.y.min(remaining);
+                for (i, region) in regions.iter().enumerate() {
+                    let limited = region.y.min(remaining).max(Abs::zero());
                     heights.push(limited);
                     remaining -= limited;
-                    if Abs::zero().fits(remaining) {
+
+                    // A repeated last region without any space would never
+                    // take up the remaining height, so it goes into the
+                    // first of them.
+                    let repeated = i > regions.backlog.len();
+                    if Abs::zero().fits(remaining) || (repeated && limited <= Abs::zero())
+                    {
                         break;
                     }
                 }
@@ -408,7 +465,7 @@ impl LayoutMultiple for Packed<BlockElem> {
                 pod.last = None;
             }
 
-            let mut frames = body.layout(engine, styles, pod)?.into_frames();
+            let mut frames = body.layout(engine, inner, pod)?.into_frames();
             for (frame, &height) in frames.iter_mut().zip(&heights) {
                 *frame.size_mut() =
                     expand.select(Size::new(size.x, height), frame.size());
@@ -416,7 +473,7 @@ impl LayoutMultiple for Packed<BlockElem> {
             frames
         } else {
             let pod = Regions::one(size, expand);
-            let mut frames = body.layout(engine, styles, pod)?.into_frames();
+            let mut frames = body.layout(engine, inner, pod)?.into_frames();
             *frames[0].size_mut() = expand.select(size, frames[0].size());
             frames
         };
@@ -468,6 +525,58 @@ impl LayoutMultiple for Packed<BlockElem> {
     }
 }
 
+/// Clamp a negative resolved container size to zero, warning about it.
+///
+/// Negative sizes typically result from length arithmetic and would otherwise
+/// produce inverted frames.
+fn clamp_negative_size(engine: &mut Engine, size: Size, name: &str, span: Span) -> Size {
+    let clamped = size.map(|v| v.max(Abs::zero()));
+    if clamped != size {
+        engine.tracer.warn(warning!(
+            span, "{} has a negative size", name;
+            hint: "negative widths and heights are treated as zero"
+        ));
+    }
+    clamped
+}
+
+/// Complete a size from an aspect ratio and the dimensions that were given
+/// explicitly, scaling it down to fit into the available space.
+///
+/// Returns the completed size and along which axes the box should expand.
+/// Nothing is derived from an infinite dimension, e.g. the width of a box in
+/// an auto-sized grid column.
+fn apply_aspect(
+    engine: &mut Engine,
+    mut size: Size,
+    given: Axes<bool>,
+    aspect: f64,
+    available: Size,
+    span: Span,
+) -> (Size, Axes<bool>) {
+    match (given.x, given.y) {
+        (true, true) => {
+            engine.tracer.warn(warning!(
+                span, "aspect ratio is ignored";
+                hint: "the box already has a width and a height"
+            ));
+            return (size, given);
+        }
+        (_, false) if size.x.is_finite() => size.y = size.x / aspect,
+        (false, true) if size.y.is_finite() => size.x = size.y * aspect,
+        _ => return (size, given),
+    }
+
+    if available.x.is_finite() && size.x > available.x {
+        size = size * (available.x / size.x);
+    }
+    if available.y.is_finite() && size.y > available.y {
+        size = size * (available.y / size.y);
+    }
+
+    (size, Axes::splat(true))
+}
+
 /// Defines how to size a grid cell along an axis.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum Sizing {
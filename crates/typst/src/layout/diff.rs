@@ -0,0 +1,564 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::Range;
+
+use ecow::{eco_format, EcoString, EcoVec};
+
+use crate::introspection::Meta;
+use crate::layout::{
+    dump_abs, dump_num, dump_paint, dump_point, dump_size, dump_stroke, Abs, Frame,
+    FrameItem, Page, Point,
+};
+use crate::visualize::Geometry;
+
+/// Compare two frames and list how the second differs from the first.
+///
+/// Items are matched up by their kind and, for text, by their content, so
+/// that inserting an item doesn't make all following items look changed.
+/// Matched items are compared position by position, ignoring shifts up to a
+/// hundredth of a point, and groups are compared recursively. An empty list
+/// means that the frames are equal up to such shifts.
+pub fn diff(a: &Frame, b: &Frame) -> Vec<FrameDiff> {
+    let mut diffs = vec![];
+    if dump_size(a.size()) != dump_size(b.size()) {
+        diffs.push(FrameDiff {
+            path: EcoVec::new(),
+            kind: FrameDiffKind::Changed(dump_size(a.size()), dump_size(b.size())),
+        });
+    }
+    diff_items(&mut diffs, &EcoVec::new(), a, b);
+    diffs
+}
+
+/// Compare the pages of two documents and list how the second differs from
+/// the first.
+///
+/// Pages are compared by their number with [`diff`] and the paths of all
+/// differences start with the page, like `page 2 > group 3`. Pages that only
+/// exist in one of the documents are reported as added or removed.
+pub fn diff_pages(a: &[Page], b: &[Page]) -> Vec<FrameDiff> {
+    let mut diffs = vec![];
+    for i in 0..a.len().max(b.len()) {
+        let mut path = EcoVec::new();
+        path.push(eco_format!("page {}", i + 1));
+        match (a.get(i), b.get(i)) {
+            (Some(p), Some(q)) => {
+                diffs.extend(diff(&p.frame, &q.frame).into_iter().map(|mut diff| {
+                    diff.path = path.iter().cloned().chain(diff.path).collect();
+                    diff
+                }));
+            }
+            (Some(_), None) => {
+                diffs.push(FrameDiff { path, kind: FrameDiffKind::Removed })
+            }
+            (None, _) => diffs.push(FrameDiff { path, kind: FrameDiffKind::Added }),
+        }
+    }
+    diffs
+}
+
+/// A difference between two frames.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct FrameDiff {
+    /// The path to the item in the frame tree, for example
+    /// `["group 3", "text 1 \"Hello\""]`. When comparing documents, the path
+    /// starts with the page, like `"page 2"`. Each segment names an item along
+    /// with its number among the items of its frame, starting at one, and the
+    /// text of text items. The numbers and texts are those in the second frame,
+    /// except for removed items. An empty path refers to the frame itself.
+    pub path: EcoVec<EcoString>,
+    /// How the item differs.
+    pub kind: FrameDiffKind,
+}
+
+/// How an item differs between two frames.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum FrameDiffKind {
+    /// The item only exists in the second frame.
+    Added,
+    /// The item only exists in the first frame.
+    Removed,
+    /// The item's position changed from the first to the second point.
+    Moved(Point, Point),
+    /// The text of a text item changed.
+    Text(EcoString, EcoString),
+    /// Other properties of the item changed, like its size, font, or fill.
+    /// They are formatted like in [`Frame::dump`].
+    Changed(EcoString, EcoString),
+}
+
+impl Display for FrameDiff {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            f.write_str("frame")?;
+        } else {
+            f.write_str(&self.path.join(" > "))?;
+        }
+
+        match &self.kind {
+            FrameDiffKind::Added => write!(f, ": added"),
+            FrameDiffKind::Removed => write!(f, ": removed"),
+            FrameDiffKind::Moved(from, to) => {
+                write!(f, ": moved from {} to {}", dump_point(*from), dump_point(*to))
+            }
+            FrameDiffKind::Text(from, to) => {
+                write!(f, ": text changed from {from:?} to {to:?}")
+            }
+            FrameDiffKind::Changed(from, to) => {
+                write!(f, ": changed from {from} to {to}")
+            }
+        }
+    }
+}
+
+/// Compare the items of two frames.
+fn diff_items(
+    diffs: &mut Vec<FrameDiff>,
+    path: &EcoVec<EcoString>,
+    a: &Frame,
+    b: &Frame,
+) {
+    let a: Vec<_> = a.items().collect();
+    let b: Vec<_> = b.items().collect();
+    let a_keys: Vec<_> = a.iter().map(|(_, item)| key(item)).collect();
+    let b_keys: Vec<_> = b.iter().map(|(_, item)| key(item)).collect();
+
+    let at = |i: usize, item: &FrameItem| {
+        let mut path = path.clone();
+        path.push(match item {
+            FrameItem::Text(text) => eco_format!("text {} {:?}", i + 1, text.text),
+            _ => eco_format!("{} {}", name(item), i + 1),
+        });
+        path
+    };
+
+    // Pair up the items, first by equal keys and within the remaining gaps
+    // by equal kinds.
+    let mut pairs = vec![];
+    let mut removed = vec![];
+    let mut added = vec![];
+    let (mut i, mut j) = (0, 0);
+    for (k, l) in common(&a_keys, &b_keys).into_iter().chain([(a.len(), b.len())]) {
+        let mut gap: Vec<usize> = (j..l).collect();
+        for (x, (_, item)) in a.iter().enumerate().take(k).skip(i) {
+            let kind = name(item);
+            if let Some(p) = gap.iter().position(|&y| name(&b[y].1) == kind) {
+                pairs.push((x, gap.remove(p)));
+            } else {
+                removed.push(x);
+            }
+        }
+        added.extend(gap);
+        if k < a.len() {
+            pairs.push((k, l));
+        }
+        (i, j) = (k + 1, l + 1);
+    }
+
+    for x in removed {
+        let path = at(x, &a[x].1);
+        diffs.push(FrameDiff { path, kind: FrameDiffKind::Removed });
+    }
+
+    // Report the remaining items in the order of the second frame.
+    let mut items: Vec<(usize, Option<usize>)> =
+        pairs.into_iter().map(|(x, y)| (y, Some(x))).collect();
+    items.extend(added.into_iter().map(|y| (y, None)));
+    items.sort_by_key(|&(y, _)| y);
+
+    for (y, x) in items {
+        let (q, other) = b[y];
+        let path = at(y, other);
+        let Some(x) = x else {
+            diffs.push(FrameDiff { path, kind: FrameDiffKind::Added });
+            continue;
+        };
+
+        let (p, item) = a[x];
+        let delta = *q - *p;
+        if delta.x.abs().max(delta.y.abs()) > Abs::pt(0.01) {
+            let kind = FrameDiffKind::Moved(*p, *q);
+            diffs.push(FrameDiff { path: path.clone(), kind });
+        }
+
+        if let (FrameItem::Text(t), FrameItem::Text(u)) = (item, other) {
+            if t.text != u.text {
+                let kind = FrameDiffKind::Text(t.text.clone(), u.text.clone());
+                diffs.push(FrameDiff { path: path.clone(), kind });
+            }
+        }
+
+        let (from, to) = (properties(item), properties(other));
+        if from != to {
+            let kind = FrameDiffKind::Changed(from, to);
+            diffs.push(FrameDiff { path: path.clone(), kind });
+        }
+
+        if let (FrameItem::Group(g), FrameItem::Group(h)) = (item, other) {
+            diff_items(diffs, &path, &g.frame, &h.frame);
+        }
+    }
+}
+
+/// The indices of a longest common subsequence of two lists.
+///
+/// This uses the linear space variant of Myers' algorithm, which takes
+/// O((n + m) · d) time for lists that differ in d items. Frames mostly differ
+/// in few items, so this is much faster than filling a table of all pairs.
+fn common(a: &[EcoString], b: &[EcoString]) -> Vec<(usize, usize)> {
+    let mut pairs = vec![];
+    let bound = (a.len() + b.len()).div_ceil(2) + 1;
+    let mut forward = Diagonals::new(bound);
+    let mut backward = Diagonals::new(bound);
+    bisect(a, 0..a.len(), b, 0..b.len(), &mut forward, &mut backward, &mut pairs);
+    pairs
+}
+
+/// Find the common items of two ranges by splitting them at the middle of a
+/// shortest edit path and recursing into both halves.
+fn bisect(
+    a: &[EcoString],
+    mut x: Range<usize>,
+    b: &[EcoString],
+    mut y: Range<usize>,
+    forward: &mut Diagonals,
+    backward: &mut Diagonals,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    // Equal items at the start and end are always part of the subsequence.
+    while !x.is_empty() && !y.is_empty() && a[x.start] == b[y.start] {
+        pairs.push((x.start, y.start));
+        x.start += 1;
+        y.start += 1;
+    }
+
+    let mut suffix = 0;
+    while !x.is_empty() && !y.is_empty() && a[x.end - 1] == b[y.end - 1] {
+        x.end -= 1;
+        y.end -= 1;
+        suffix += 1;
+    }
+
+    if !x.is_empty() && !y.is_empty() {
+        let (i, j) = middle(a, x.clone(), b, y.clone(), forward, backward);
+        bisect(a, x.start..i, b, y.start..j, forward, backward, pairs);
+        bisect(a, i..x.end, b, j..y.end, forward, backward, pairs);
+    }
+
+    pairs.extend((0..suffix).map(|k| (x.end + k, y.end + k)));
+}
+
+/// Find a point on a shortest edit path between two non-empty ranges, where
+/// the paths searched from the start and from the end meet.
+fn middle(
+    a: &[EcoString],
+    x: Range<usize>,
+    b: &[EcoString],
+    y: Range<usize>,
+    forward: &mut Diagonals,
+    backward: &mut Diagonals,
+) -> (usize, usize) {
+    let (n, m) = (x.len() as isize, y.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let equal = |i: isize, j: isize| a[x.start + i as usize] == b[y.start + j as usize];
+
+    forward[1] = 0;
+    backward[1] = 0;
+    for d in 0..=(n + m + 1) / 2 {
+        // Extend the furthest paths from the start by one edit.
+        for k in (-d..=d).rev().step_by(2) {
+            let mut i = if k == -d || (k != d && forward[k - 1] < forward[k + 1]) {
+                forward[k + 1]
+            } else {
+                forward[k - 1] + 1
+            };
+            let start = (i, i - k);
+            while i < n && i - k < m && equal(i, i - k) {
+                i += 1;
+            }
+            forward[k] = i;
+            if odd && (k - delta).abs() < d && i + backward[delta - k] >= n {
+                return (x.start + start.0 as usize, y.start + start.1 as usize);
+            }
+        }
+
+        // Extend the furthest paths from the end by one edit.
+        for k in (-d..=d).rev().step_by(2) {
+            let mut i = if k == -d || (k != d && backward[k - 1] < backward[k + 1]) {
+                backward[k + 1]
+            } else {
+                backward[k - 1] + 1
+            };
+            while i < n && i - k < m && equal(n - i - 1, m - (i - k) - 1) {
+                i += 1;
+            }
+            backward[k] = i;
+            if !odd && (k - delta).abs() <= d && i + forward[delta - k] >= n {
+                return (x.start + (n - i) as usize, y.start + (m - (i - k)) as usize);
+            }
+        }
+    }
+
+    unreachable!("paths from both ends always meet")
+}
+
+/// The furthest reaching paths on each diagonal, indexed by the diagonal.
+struct Diagonals {
+    offset: isize,
+    vec: Vec<isize>,
+}
+
+impl Diagonals {
+    fn new(bound: usize) -> Self {
+        Self {
+            offset: bound as isize + 1,
+            vec: vec![0; 2 * bound + 3],
+        }
+    }
+}
+
+impl std::ops::Index<isize> for Diagonals {
+    type Output = isize;
+
+    fn index(&self, k: isize) -> &isize {
+        &self.vec[(k + self.offset) as usize]
+    }
+}
+
+impl std::ops::IndexMut<isize> for Diagonals {
+    fn index_mut(&mut self, k: isize) -> &mut isize {
+        &mut self.vec[(k + self.offset) as usize]
+    }
+}
+
+/// The kind of an item.
+fn name(item: &FrameItem) -> &'static str {
+    match item {
+        FrameItem::Group(_) => "group",
+        FrameItem::Text(_) => "text",
+        FrameItem::Shape(..) => "shape",
+        FrameItem::Image(..) => "image",
+        FrameItem::Meta(..) => "meta",
+    }
+}
+
+/// What identifies an item when matching up items.
+fn key(item: &FrameItem) -> EcoString {
+    match item {
+        FrameItem::Text(text) => eco_format!("text {:?}", text.text),
+        _ => name(item).into(),
+    }
+}
+
+/// The properties of an item besides its position, children, and text.
+fn properties(item: &FrameItem) -> EcoString {
+    let mut out = EcoString::new();
+    match item {
+        FrameItem::Group(group) => {
+            out.push_str(&dump_size(group.frame.size()));
+            if !group.transform.is_identity() {
+                let t = group.transform;
+                out.push_str(&eco_format!(
+                    " transform=({}, {}, {}, {}, {}, {})",
+                    dump_num(t.sx.get()),
+                    dump_num(t.ky.get()),
+                    dump_num(t.kx.get()),
+                    dump_num(t.sy.get()),
+                    dump_abs(t.tx),
+                    dump_abs(t.ty),
+                ));
+            }
+            if group.clip_path.is_some() {
+                out.push_str(" clipped");
+            }
+        }
+        FrameItem::Text(text) => {
+            out.push_str(&eco_format!(
+                "font={:?} size={} fill={}",
+                text.font.info().family,
+                dump_abs(text.size),
+                dump_paint(&text.fill),
+            ));
+            if let Some(stroke) = &text.stroke {
+                out.push_str(&eco_format!(" stroke={}", dump_stroke(stroke)));
+            }
+        }
+        FrameItem::Shape(shape, _) => {
+            out.push_str(&match &shape.geometry {
+                Geometry::Line(to) => eco_format!("line to={}", dump_point(*to)),
+                Geometry::Rect(size) => eco_format!("rect {}", dump_size(*size)),
+                Geometry::Path(path) => eco_format!("path items={}", path.0.len()),
+            });
+            if let Some(fill) = &shape.fill {
+                out.push_str(&eco_format!(" fill={}", dump_paint(fill)));
+            }
+            if let Some(stroke) = &shape.stroke {
+                out.push_str(&eco_format!(" stroke={}", dump_stroke(stroke)));
+            }
+        }
+        FrameItem::Image(_, size, _) => out.push_str(&dump_size(*size)),
+        FrameItem::Meta(meta, size) => {
+            out.push_str(&dump_size(*size));
+            if let Meta::Elem(content) = meta {
+                out.push_str(&eco_format!(" elem {}", content.func().name()));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::Bytes;
+    use crate::layout::{GroupItem, Page, Size};
+    use crate::syntax::Span;
+    use crate::text::{Font, Lang, TextItem};
+    use crate::visualize::Color;
+
+    fn text(text: &str) -> FrameItem {
+        let font =
+            Font::new(Bytes::from_static(typst_assets::fonts().next().unwrap()), 0);
+        FrameItem::Text(TextItem {
+            font: font.unwrap(),
+            size: Abs::pt(10.0),
+            fill: Color::BLACK.into(),
+            stroke: None,
+            lang: Lang::ENGLISH,
+            text: text.into(),
+            glyphs: vec![],
+        })
+    }
+
+    fn rect() -> FrameItem {
+        let shape = Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::RED.into());
+        FrameItem::Shape(shape, Span::detached())
+    }
+
+    fn frame(items: Vec<(f64, FrameItem)>) -> Frame {
+        let mut frame = Frame::soft(Size::splat(Abs::pt(100.0)));
+        for (y, item) in items {
+            frame.push(Point::with_y(Abs::pt(y)), item);
+        }
+        frame
+    }
+
+    fn test(a: &Frame, b: &Frame) -> Vec<String> {
+        diff(a, b).iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_diff_shift() {
+        let group = |y| {
+            let inner = frame(vec![(0.0, text("Hi")), (y, text("there"))]);
+            FrameItem::Group(GroupItem::new(inner))
+        };
+        let a = frame(vec![(0.0, rect()), (10.0, group(12.0))]);
+        let b = frame(vec![(0.0, rect()), (10.005, group(15.0))]);
+        assert!(test(&a, &a).is_empty());
+        assert_eq!(
+            test(&a, &b),
+            ["group 2 > text 2 \"there\": moved from (0pt, 12pt) to (0pt, 15pt)"]
+        );
+    }
+
+    #[test]
+    fn test_diff_text() {
+        let a = frame(vec![(0.0, text("Hello")), (10.0, rect())]);
+        let b = frame(vec![(0.0, text("World")), (10.0, rect())]);
+        assert_eq!(
+            test(&a, &b),
+            ["text 1 \"World\": text changed from \"Hello\" to \"World\""]
+        );
+    }
+
+    #[test]
+    fn test_diff_count() {
+        let a = frame(vec![(0.0, text("A")), (10.0, text("C"))]);
+        let b = frame(vec![(0.0, text("A")), (10.0, text("B")), (20.0, text("C"))]);
+        assert_eq!(
+            test(&a, &b),
+            [
+                "text 2 \"B\": added",
+                "text 3 \"C\": moved from (0pt, 10pt) to (0pt, 20pt)"
+            ]
+        );
+        assert_eq!(
+            test(&b, &a),
+            [
+                "text 2 \"B\": removed",
+                "text 2 \"C\": moved from (0pt, 20pt) to (0pt, 10pt)"
+            ]
+        );
+
+        let c = frame(vec![(0.0, text("A")), (10.0, rect()), (20.0, text("C"))]);
+        assert_eq!(
+            test(&a, &c),
+            ["shape 2: added", "text 3 \"C\": moved from (0pt, 10pt) to (0pt, 20pt)"]
+        );
+    }
+
+    #[test]
+    fn test_diff_pages() {
+        let page = |frame| Page {
+            frame,
+            numbering: None,
+            number: 1,
+            bleed: Abs::zero(),
+            slug: Abs::zero(),
+        };
+        let a = [page(frame(vec![(0.0, text("A"))])), page(frame(vec![]))];
+        let b = [page(frame(vec![(5.0, text("A"))]))];
+        assert_eq!(
+            diff_pages(&a, &b).iter().map(ToString::to_string).collect::<Vec<_>>(),
+            [
+                "page 1 > text 1 \"A\": moved from (0pt, 0pt) to (0pt, 5pt)",
+                "page 2: removed",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_common() {
+        // The length of a longest common subsequence, computed naively.
+        fn naive(a: &[EcoString], b: &[EcoString]) -> usize {
+            let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+            for i in (0..a.len()).rev() {
+                for j in (0..b.len()).rev() {
+                    table[i][j] = if a[i] == b[j] {
+                        table[i + 1][j + 1] + 1
+                    } else {
+                        table[i + 1][j].max(table[i][j + 1])
+                    };
+                }
+            }
+            table[0][0]
+        }
+
+        let mut seed = 1u64;
+        let mut next = move |max: u64| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % max
+        };
+
+        for _ in 0..500 {
+            let mut list = |len: u64| -> Vec<EcoString> {
+                (0..next(len)).map(|_| eco_format!("{}", next(4))).collect()
+            };
+            let (a, b) = (list(12), list(12));
+            let pairs = common(&a, &b);
+            assert_eq!(pairs.len(), naive(&a, &b), "{a:?} {b:?}");
+            assert!(pairs.iter().all(|&(i, j)| a[i] == b[j]));
+            assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0 && w[0].1 < w[1].1));
+        }
+
+        // Long lists with few differences are fast.
+        let a: Vec<EcoString> = (0..100_000).map(|i| eco_format!("{i}")).collect();
+        let mut b = a.clone();
+        b.remove(500);
+        b.insert(90_000, "new".into());
+        assert_eq!(common(&a, &b).len(), a.len() - 1);
+    }
+}
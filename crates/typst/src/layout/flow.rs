@@ -1,6 +1,6 @@
 use std::fmt::{self, Debug, Formatter};
 
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     elem, Content, NativeElement, Packed, Resolve, Smart, StyleChain, StyledElem,
@@ -55,7 +55,11 @@ impl LayoutMultiple for Packed<FlowElem> {
                 layouter.layout_spacing(engine, elem, styles)?;
             } else if let Some(placed) = child.to_packed::<PlaceElem>() {
                 layouter.layout_placed(engine, placed, styles)?;
-            } else if child.is::<ColbreakElem>() {
+            } else if let Some(colbreak) = child.to_packed::<ColbreakElem>() {
+                if colbreak.weak(styles) && layouter.is_region_empty() {
+                    continue;
+                }
+
                 if !layouter.regions.backlog.is_empty() || layouter.regions.last.is_some()
                 {
                     layouter.finish_region(engine, true)?;
@@ -95,6 +99,9 @@ struct FlowLayouter<'a> {
     /// The initial size of `regions.size` that was available before we started
     /// subtracting.
     initial: Size,
+    /// The offset from the top of the region at which the next item will be
+    /// placed, as far as it is known before the region is finished.
+    cursor: Abs,
     /// Whether the last block was a paragraph.
     last_was_par: bool,
     /// Spacing and layouted blocks for the current region.
@@ -180,6 +187,7 @@ impl<'a> FlowLayouter<'a> {
             styles,
             expand,
             initial: regions.size,
+            cursor: Abs::zero(),
             last_was_par: false,
             items: vec![],
             pending_floats: vec![],
@@ -193,6 +201,13 @@ impl<'a> FlowLayouter<'a> {
         }
     }
 
+    /// Whether the current region has no in-flow content yet.
+    fn is_region_empty(&self) -> bool {
+        self.items.iter().all(|item| {
+            item.is_out_of_flow() || matches!(item, FlowItem::Absolute(_, true))
+        })
+    }
+
     /// Place explicit metadata into the flow.
     fn layout_meta(&mut self, styles: StyleChain) {
         let mut frame = Frame::soft(Size::zero());
@@ -212,16 +227,29 @@ impl<'a> FlowLayouter<'a> {
         v: &Packed<VElem>,
         styles: StyleChain,
     ) -> SourceResult<()> {
-        self.layout_item(
-            engine,
-            match v.amount() {
-                Spacing::Rel(rel) => FlowItem::Absolute(
-                    rel.resolve(styles).relative_to(self.initial.y),
-                    v.weakness(styles) > 0,
-                ),
-                Spacing::Fr(fr) => FlowItem::Fractional(*fr),
-            },
-        )
+        let item = match v.amount() {
+            Spacing::Rel(rel) => {
+                let mut amount = rel.resolve(styles).relative_to(self.initial.y);
+                let weak = v.weakness(styles) > 0;
+
+                // Negative spacing moves the cursor backwards and may even pull
+                // content above the start of the region, but not so far that
+                // it would leave the region entirely.
+                let used = self.regions.full - self.regions.size.y;
+                let limit = used + self.regions.full;
+                if !weak && amount < -limit {
+                    engine.tracer.warn(warning!(
+                        v.span(), "negative spacing was clamped";
+                        hint: "it would have moved content out of the region entirely"
+                    ));
+                    amount = -limit;
+                }
+
+                FlowItem::Absolute(amount, weak)
+            }
+            Spacing::Fr(fr) => FlowItem::Fractional(*fr),
+        };
+        self.layout_item(engine, item)
     }
 
     /// Layout a paragraph.
@@ -233,6 +261,7 @@ impl<'a> FlowLayouter<'a> {
     ) -> SourceResult<()> {
         let align = AlignElem::alignment_in(styles).resolve(styles);
         let leading = ParElem::leading_in(styles);
+        let grid = self.grid(styles);
         let consecutive = self.last_was_par;
         let lines = par
             .layout(
@@ -241,6 +270,7 @@ impl<'a> FlowLayouter<'a> {
                 consecutive,
                 self.regions.base(),
                 self.regions.expand.x,
+                grid,
             )?
             .into_frames();
 
@@ -274,6 +304,10 @@ impl<'a> FlowLayouter<'a> {
                 self.layout_item(engine, FlowItem::Absolute(leading, true))?;
             }
 
+            if let Some(grid) = grid {
+                self.snap_to_grid(engine, &frame, frame.baseline(), grid)?;
+            }
+
             self.layout_item(
                 engine,
                 FlowItem::Frame { frame, align, sticky: false, movable: true },
@@ -296,6 +330,7 @@ impl<'a> FlowLayouter<'a> {
         let pod = Regions::one(self.regions.base(), Axes::splat(false));
         let mut frame = layoutable.layout(engine, styles, pod)?;
         frame.meta(styles, false);
+        self.snap_block_to_grid(engine, &frame, styles)?;
         self.layout_item(
             engine,
             FlowItem::Frame { frame, align, sticky, movable: true },
@@ -368,6 +403,8 @@ impl<'a> FlowLayouter<'a> {
 
             if i > 0 {
                 self.finish_region(engine, false)?;
+            } else {
+                self.snap_block_to_grid(engine, &frame, styles)?;
             }
 
             frame.meta(styles, false);
@@ -386,6 +423,56 @@ impl<'a> FlowLayouter<'a> {
         Ok(())
     }
 
+    /// The baseline grid that lines snap to, if any.
+    ///
+    /// Only the root flow snaps because nested flows don't know where they
+    /// end up in relation to the grid.
+    fn grid(&self, styles: StyleChain) -> Option<Abs> {
+        ParElem::baseline_grid_in(styles)
+            .filter(|grid| self.root && *grid > Abs::zero() && grid.is_finite())
+    }
+
+    /// Snap the first baseline of a block to the baseline grid if it wants to.
+    fn snap_block_to_grid(
+        &mut self,
+        engine: &mut Engine,
+        frame: &Frame,
+        styles: StyleChain,
+    ) -> SourceResult<()> {
+        let Some(grid) = self.grid(styles) else { return Ok(()) };
+        if !BlockElem::grid_snap_in(styles) {
+            return Ok(());
+        }
+        match first_baseline(frame) {
+            Some(baseline) => self.snap_to_grid(engine, frame, baseline, grid),
+            None => Ok(()),
+        }
+    }
+
+    /// Add spacing in front of a frame so that the given baseline in it lands
+    /// on the next line of the baseline grid. Moves on to the next region if
+    /// the frame doesn't fit anymore.
+    fn snap_to_grid(
+        &mut self,
+        engine: &mut Engine,
+        frame: &Frame,
+        baseline: Abs,
+        grid: Abs,
+    ) -> SourceResult<()> {
+        loop {
+            let delta = grid_gap(self.cursor + baseline, grid);
+            if self.regions.size.y.fits(delta + frame.height()) || self.regions.in_last()
+            {
+                if delta > Abs::zero() {
+                    self.layout_item(engine, FlowItem::Absolute(delta, false))?;
+                }
+                return Ok(());
+            }
+
+            self.finish_region(engine, false)?;
+        }
+    }
+
     /// Layout a finished frame.
     fn layout_item(
         &mut self,
@@ -402,7 +489,8 @@ impl<'a> FlowLayouter<'a> {
                 {
                     return Ok(());
                 }
-                self.regions.size.y -= v
+                self.regions.size.y -= v;
+                self.cursor += v;
             }
             FlowItem::Fractional(_) => {}
             FlowItem::Frame { ref frame, movable, .. } => {
@@ -412,6 +500,7 @@ impl<'a> FlowLayouter<'a> {
                 }
 
                 self.regions.size.y -= height;
+                self.cursor += height;
                 if self.root && movable {
                     let mut notes = Vec::new();
                     find_footnotes(&mut notes, frame);
@@ -421,6 +510,7 @@ impl<'a> FlowLayouter<'a> {
                         self.finish_region(engine, false)?;
                         self.items.extend(item);
                         self.regions.size.y -= height;
+                        self.cursor += height;
                         self.handle_footnotes(engine, &mut notes, true, true)?;
                     }
                     return Ok(());
@@ -465,6 +555,9 @@ impl<'a> FlowLayouter<'a> {
                 }
 
                 self.regions.size.y -= frame.height();
+                if *y_align == Smart::Custom(Some(FixedAlignment::Start)) {
+                    self.cursor += frame.height();
+                }
 
                 // Find footnotes in the frame.
                 if self.root {
@@ -493,6 +586,7 @@ impl<'a> FlowLayouter<'a> {
             self.finished.push(Frame::soft(self.initial));
             self.regions.next();
             self.initial = self.regions.size;
+            self.cursor = Abs::zero();
             return Ok(());
         }
 
@@ -618,6 +712,7 @@ impl<'a> FlowLayouter<'a> {
         self.finished.push(output);
         self.regions.next();
         self.initial = self.regions.size;
+        self.cursor = Abs::zero();
         self.has_footnotes = false;
 
         // Try to place floats into the next region.
@@ -752,6 +847,26 @@ impl FlowLayouter<'_> {
     }
 }
 
+/// The distance from `y` down to the next line of a baseline grid, which is
+/// zero if `y` is already on the grid.
+pub(crate) fn grid_gap(y: Abs, grid: Abs) -> Abs {
+    let gap = grid * (y / grid).ceil() - y;
+    if gap.approx_eq(grid) || gap.approx_eq(Abs::zero()) {
+        Abs::zero()
+    } else {
+        gap
+    }
+}
+
+/// Finds the baseline of the first line of text in the frame.
+fn first_baseline(frame: &Frame) -> Option<Abs> {
+    frame.items().find_map(|(pos, item)| match item {
+        FrameItem::Group(group) => first_baseline(&group.frame).map(|y| pos.y + y),
+        FrameItem::Text(_) => Some(pos.y),
+        _ => None,
+    })
+}
+
 /// Finds all footnotes in the frame.
 fn find_footnotes(notes: &mut Vec<Packed<FootnoteElem>>, frame: &Frame) {
     for (_, item) in frame.items() {
@@ -1,19 +1,23 @@
 //! Finished documents.
 
-use std::fmt::{self, Debug, Formatter};
+use std::fmt::{self, Debug, Formatter, Write};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 
-use crate::foundations::{cast, dict, Dict, StyleChain, Value};
+use ecow::{eco_format, EcoString};
+
+use crate::foundations::{cast, dict, repr, Dict, StyleChain, Value};
 use crate::introspection::{Meta, MetaElem};
 use crate::layout::{
     Abs, Axes, Corners, FixedAlignment, Length, Point, Rel, Sides, Size, Transform,
 };
+use crate::model::{Destination, PdfMarker};
 use crate::syntax::Span;
 use crate::text::TextItem;
 use crate::util::Numeric;
 use crate::visualize::{
-    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, Paint, Path, Shape,
+    ellipse, styled_rect, Color, FixedStroke, Geometry, Image, ImageFormat, Paint, Path,
+    Shape,
 };
 
 /// A finished layout with items at fixed positions.
@@ -290,19 +294,44 @@ impl Frame {
 
     /// Attach metadata from an iterator.
     pub fn meta_iter(&mut self, iter: impl IntoIterator<Item = Meta>) {
+        #[cfg(feature = "debug")]
+        let iter = limit_calls(iter);
+
         let mut hide = false;
+        let mut artifact = false;
         let size = self.size;
         self.prepend_multiple(iter.into_iter().filter_map(|meta| {
-            if matches!(meta, Meta::Hide) {
-                hide = true;
-                None
-            } else {
-                Some((Point::zero(), FrameItem::Meta(meta, size)))
+            match meta {
+                Meta::Hide => {
+                    hide = true;
+                    return None;
+                }
+                Meta::Artifact => artifact = true,
+                _ => {}
             }
+            Some((Point::zero(), FrameItem::Meta(meta, size)))
         }));
         if hide {
             self.hide();
         }
+
+        // An artifact marker applies to the rest of its frame, so the marked
+        // items must not be inlined into the parent along with it.
+        if artifact {
+            self.group_items();
+        }
+    }
+
+    /// Move all items into a group, so that they stay separate from the
+    /// parent's items when the frame is inlined.
+    fn group_items(&mut self) {
+        let inner = Frame {
+            size: self.size,
+            baseline: self.baseline,
+            items: std::mem::take(&mut self.items),
+            kind: FrameKind::Soft,
+        };
+        self.push(Point::zero(), FrameItem::Group(GroupItem::new(inner)));
     }
 
     /// Hide all content in the frame, but keep metadata.
@@ -378,6 +407,20 @@ impl Frame {
 
 /// Tools for debugging.
 impl Frame {
+    /// Dump the frame tree into a stable, line-based textual format.
+    ///
+    /// Each item is written on its own line, indented by its nesting depth,
+    /// together with its position and properties. Lengths are rounded to
+    /// hundredths of a point and colors are written in hex, so the output
+    /// doesn't depend on platform-specific floating point noise. This makes
+    /// it suitable for golden tests and external tooling.
+    pub fn dump(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "frame {}", dump_size(self.size)).unwrap();
+        dump_items(&mut out, self, 1);
+        out
+    }
+
     /// Add a full size aqua background and a red baseline for debugging.
     pub fn mark_box(mut self) -> Self {
         self.mark_box_in_place();
@@ -439,6 +482,151 @@ impl Debug for Frame {
     }
 }
 
+/// Drop all but the innermost [`Meta::MAX_CALLS`] calls from metadata, which
+/// lists the outermost ones first.
+#[cfg(feature = "debug")]
+fn limit_calls(iter: impl IntoIterator<Item = Meta>) -> Vec<Meta> {
+    let mut metas: Vec<_> = iter.into_iter().collect();
+    let calls = metas.iter().filter(|meta| matches!(meta, Meta::Call(_))).count();
+    let mut excess = calls.saturating_sub(Meta::MAX_CALLS);
+    metas.retain(|meta| {
+        let drop = excess > 0 && matches!(meta, Meta::Call(_));
+        excess -= usize::from(drop);
+        !drop
+    });
+    metas
+}
+
+/// Write the items of a frame for [`Frame::dump`].
+fn dump_items(out: &mut String, frame: &Frame, depth: usize) {
+    for (pos, item) in frame.items() {
+        let indent = "  ".repeat(depth);
+        let pos = dump_point(*pos);
+        match item {
+            FrameItem::Group(group) => {
+                write!(out, "{indent}group {pos} {}", dump_size(group.frame.size()))
+                    .unwrap();
+                if !group.transform.is_identity() {
+                    let Transform { sx, ky, kx, sy, tx, ty } = group.transform;
+                    write!(
+                        out,
+                        " transform=({}, {}, {}, {}, {}, {})",
+                        dump_num(sx.get()),
+                        dump_num(ky.get()),
+                        dump_num(kx.get()),
+                        dump_num(sy.get()),
+                        dump_abs(tx),
+                        dump_abs(ty),
+                    )
+                    .unwrap();
+                }
+                if group.clip_path.is_some() {
+                    out.push_str(" clipped");
+                }
+                out.push('\n');
+                dump_items(out, &group.frame, depth + 1);
+            }
+            FrameItem::Text(text) => {
+                write!(
+                    out,
+                    "{indent}text {pos} {:?} font={:?} size={} fill={}",
+                    text.text.as_str(),
+                    text.font.info().family,
+                    dump_abs(text.size),
+                    dump_paint(&text.fill),
+                )
+                .unwrap();
+                if let Some(stroke) = &text.stroke {
+                    write!(out, " stroke={}", dump_stroke(stroke)).unwrap();
+                }
+                out.push('\n');
+            }
+            FrameItem::Shape(shape, _) => {
+                write!(out, "{indent}shape {pos} ").unwrap();
+                match &shape.geometry {
+                    Geometry::Line(to) => write!(out, "line to={}", dump_point(*to)),
+                    Geometry::Rect(size) => write!(out, "rect {}", dump_size(*size)),
+                    Geometry::Path(path) => write!(out, "path items={}", path.0.len()),
+                }
+                .unwrap();
+                if let Some(fill) = &shape.fill {
+                    write!(out, " fill={}", dump_paint(fill)).unwrap();
+                }
+                if let Some(stroke) = &shape.stroke {
+                    write!(out, " stroke={}", dump_stroke(stroke)).unwrap();
+                }
+                out.push('\n');
+            }
+            FrameItem::Image(image, size, _) => {
+                let format = match image.format() {
+                    ImageFormat::Raster(format) => format!("{format:?}"),
+                    ImageFormat::Vector(format) => format!("{format:?}"),
+                };
+                writeln!(
+                    out,
+                    "{indent}image {pos} {} format={}",
+                    dump_size(*size),
+                    format.to_lowercase(),
+                )
+                .unwrap();
+            }
+            FrameItem::Meta(meta, size) => {
+                let kind = match meta {
+                    Meta::Link(Destination::Url(url)) => format!("link url={url:?}"),
+                    Meta::Link(_) => "link".into(),
+                    Meta::Elem(content) => format!("elem {}", content.func().name()),
+                    Meta::Hide => "hide".into(),
+                    Meta::Artifact => "artifact".into(),
+                    Meta::Call(span) => format!("call {span:?}"),
+                    Meta::Pdf(PdfMarker::Dest(name)) => format!("pdf dest {name:?}"),
+                    Meta::Pdf(PdfMarker::Comment(text)) => {
+                        format!("pdf comment {text:?}")
+                    }
+                    Meta::Pdf(PdfMarker::Note(note)) => {
+                        format!("pdf note {:?}", note.text)
+                    }
+                };
+                writeln!(out, "{indent}meta {pos} {} {kind}", dump_size(*size)).unwrap();
+            }
+        }
+    }
+}
+
+/// Format a number for [`Frame::dump`], rounded to two decimal places.
+pub(super) fn dump_num(value: f64) -> EcoString {
+    // Adding zero turns a negative zero into a positive one.
+    repr::format_float((value * 100.0).round() / 100.0 + 0.0, None, false, "")
+}
+
+/// Format a length for [`Frame::dump`].
+pub(super) fn dump_abs(abs: Abs) -> EcoString {
+    eco_format!("{}pt", dump_num(abs.to_pt()))
+}
+
+/// Format a point for [`Frame::dump`].
+pub(super) fn dump_point(point: Point) -> EcoString {
+    eco_format!("({}, {})", dump_abs(point.x), dump_abs(point.y))
+}
+
+/// Format a size for [`Frame::dump`].
+pub(super) fn dump_size(size: Size) -> EcoString {
+    eco_format!("{}x{}", dump_abs(size.x), dump_abs(size.y))
+}
+
+/// Format a paint for [`Frame::dump`].
+pub(super) fn dump_paint(paint: &Paint) -> EcoString {
+    match paint {
+        Paint::Solid(color) => color.to_hex(),
+        Paint::Gradient(_) => "gradient".into(),
+        Paint::Pattern(_) => "pattern".into(),
+    }
+}
+
+/// Format a stroke for [`Frame::dump`].
+pub(super) fn dump_stroke(stroke: &FixedStroke) -> EcoString {
+    eco_format!("{}/{}", dump_abs(stroke.thickness), dump_paint(&stroke.paint))
+}
+
 /// The hardness of a frame.
 ///
 /// This corresponds to whether or not the frame is considered to be the
@@ -530,6 +718,9 @@ pub struct Position {
     /// The page, starting at 1.
     pub page: NonZeroUsize,
     /// The exact coordinates on the page (from the top left, as usual).
+    ///
+    /// These are relative to the page's whole frame, so they include the
+    /// page's bleed and the space for its crop marks, if any.
     pub point: Point,
 }
 
@@ -554,3 +745,36 @@ impl From<Position> for Dict {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Ratio;
+
+    #[test]
+    fn test_frame_dump() {
+        let mut inner = Frame::soft(Size::new(Abs::pt(20.0), Abs::pt(10.0)));
+        inner.push(
+            Point::with_y(Abs::pt(1.0 / 3.0)),
+            FrameItem::Shape(
+                Geometry::Rect(Size::splat(Abs::pt(5.0))).filled(Color::RED.into()),
+                Span::detached(),
+            ),
+        );
+
+        let mut group = GroupItem::new(inner);
+        group.transform = Transform::scale(Ratio::new(2.0), Ratio::one());
+
+        let mut frame = Frame::hard(Size::new(Abs::pt(100.0), Abs::pt(50.5)));
+        frame.push(Point::new(Abs::pt(10.0), Abs::pt(-0.001)), FrameItem::Group(group));
+
+        let dump = frame.dump();
+        assert_eq!(dump, frame.clone().dump());
+        assert_eq!(
+            dump,
+            "frame 100ptx50.5pt\n  \
+             group (10pt, 0pt) 20ptx10pt transform=(2, 0, 0, 1, 0pt, 0pt)\n    \
+             shape (0pt, 0.33pt) rect 5ptx5pt fill=#ff4136\n"
+        );
+    }
+}
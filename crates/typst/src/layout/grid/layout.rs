@@ -217,7 +217,8 @@ impl LayoutMultiple for Cell {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Fragment> {
-        self.body.layout(engine, styles, regions)
+        let backed = TextElem::backed(&self.fill, styles);
+        self.body.layout(engine, styles.chain(&backed), regions)
     }
 }
 
@@ -0,0 +1,265 @@
+use std::num::NonZeroUsize;
+
+use crate::diag::{bail, StrResult};
+use crate::layout::{
+    Abs, Frame, FrameItem, GroupItem, Page, Point, Ratio, Size, Transform,
+};
+use crate::syntax::Span;
+use crate::visualize::{FixedStroke, Geometry, Path};
+
+/// Settings for arranging several pages on each sheet of paper.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Imposition {
+    /// The number of pages next to each other on a sheet.
+    pub cols: NonZeroUsize,
+    /// The number of pages above each other on a sheet.
+    pub rows: NonZeroUsize,
+    /// The size of the sheets, for example that of
+    /// [`Paper::A4`](crate::layout::Paper::A4).
+    pub sheet: Size,
+    /// The space around the edges of the sheet and between the pages.
+    pub margin: Abs,
+    /// A stroke to draw around each page, if any.
+    pub border: Option<FixedStroke>,
+    /// The order in which the pages fill the sheets.
+    pub order: ImpositionOrder,
+}
+
+/// The order in which pages are arranged on sheets.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum ImpositionOrder {
+    /// The pages fill the sheets in reading order, left-to-right and then
+    /// top-to-bottom.
+    #[default]
+    Sequential,
+    /// The pages are ordered such that the sheets, printed double-sided with
+    /// two pages per side and folded in the middle, form a booklet. Blank
+    /// pages are added at the end to get a multiple of four.
+    ///
+    /// Only works with exactly two pages per sheet side, i.e. two columns and
+    /// one row or one column and two rows.
+    Booklet,
+}
+
+/// Arrange the pages of a document on sheets, several pages per sheet.
+///
+/// Each page is scaled down uniformly to fit its cell and centered in it. The
+/// pages are placed as groups, so their content stays vector graphics and
+/// this works with any exporter. Bleeds and crop marks are cut off. Cells
+/// for which there is no page stay empty.
+///
+/// Fails if the margins leave no space for the pages or if booklet order is
+/// requested with a grid that doesn't have exactly two cells.
+pub fn impose(pages: &[Page], imposition: &Imposition) -> StrResult<Vec<Page>> {
+    let cols = imposition.cols.get();
+    let rows = imposition.rows.get();
+    let margin = imposition.margin;
+    let area = imposition.sheet - Size::splat(2.0 * margin);
+    let cell = Size::new(
+        (area.x - (cols - 1) as f64 * margin) / cols as f64,
+        (area.y - (rows - 1) as f64 * margin) / rows as f64,
+    );
+
+    if !(cell.x > Abs::zero() && cell.y > Abs::zero()) {
+        bail!("the margins leave no space for the pages on the sheet");
+    }
+
+    if imposition.order == ImpositionOrder::Booklet && cols * rows != 2 {
+        bail!("booklet order requires exactly two pages per sheet side");
+    }
+
+    let order = match imposition.order {
+        ImpositionOrder::Sequential => (0..pages.len()).map(Some).collect(),
+        ImpositionOrder::Booklet => booklet(pages.len()),
+    };
+
+    let mut sheets = vec![];
+    for (i, chunk) in order.chunks(cols * rows).enumerate() {
+        let mut frame = Frame::hard(imposition.sheet);
+        for (j, index) in chunk.iter().enumerate() {
+            let Some(page) = index.map(|index| &pages[index]) else { continue };
+            let (trimmed, size) = trim(page);
+            if size.x <= Abs::zero() || size.y <= Abs::zero() {
+                continue;
+            }
+
+            let scale = (cell.x / size.x).min(cell.y / size.y);
+            let fitted = size * scale;
+            let pos = Point::new(
+                margin + (j % cols) as f64 * (cell.x + margin),
+                margin + (j / cols) as f64 * (cell.y + margin),
+            ) + ((cell - fitted) / 2.0).to_point();
+
+            let mut group = GroupItem::new(trimmed);
+            group.transform = Transform::scale(Ratio::new(scale), Ratio::new(scale));
+            frame.push(pos, FrameItem::Group(group));
+
+            if let Some(stroke) = &imposition.border {
+                let shape = Geometry::Rect(fitted).stroked(stroke.clone());
+                frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+            }
+        }
+
+        sheets.push(Page {
+            frame,
+            numbering: None,
+            number: i + 1,
+            bleed: Abs::zero(),
+            slug: Abs::zero(),
+        });
+    }
+
+    Ok(sheets)
+}
+
+/// The frame of a page without its bleed and crop marks, along with its
+/// trimmed size.
+fn trim(page: &Page) -> (Frame, Size) {
+    let offset = page.bleed + page.slug;
+    let size = page.frame.size() - Size::splat(2.0 * offset);
+    let mut frame = page.frame.clone();
+    if offset > Abs::zero() {
+        frame.translate(Point::splat(-offset));
+        frame.set_size(size);
+        frame.clip(Path::rect(size));
+    }
+    (frame, size)
+}
+
+/// The page indices in booklet order, with `None` for blank pages.
+fn booklet(count: usize) -> Vec<Option<usize>> {
+    let n = count.div_ceil(4) * 4;
+    let mut order = Vec::with_capacity(n);
+    for k in 0..n / 4 {
+        // The front and back of the k-th sheet.
+        order.extend([n - 1 - 2 * k, 2 * k, 2 * k + 1, n - 2 - 2 * k]);
+    }
+    order.into_iter().map(|i| (i < count).then_some(i)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Paper;
+    use crate::visualize::Color;
+
+    fn pages(count: usize, size: Size) -> Vec<Page> {
+        (1..=count)
+            .map(|number| Page {
+                frame: Frame::soft(size),
+                numbering: None,
+                number,
+                bleed: Abs::zero(),
+                slug: Abs::zero(),
+            })
+            .collect()
+    }
+
+    fn imposition(cols: usize, rows: usize, order: ImpositionOrder) -> Imposition {
+        Imposition {
+            cols: NonZeroUsize::new(cols).unwrap(),
+            rows: NonZeroUsize::new(rows).unwrap(),
+            sheet: Size::new(Paper::A4.width(), Paper::A4.height()),
+            margin: Abs::mm(5.0),
+            border: None,
+            order,
+        }
+    }
+
+    /// The positions and scale factors of the pages on a sheet.
+    fn cells(sheet: &Page) -> Vec<(Point, Transform)> {
+        sheet
+            .frame
+            .items()
+            .filter_map(|(pos, item)| match item {
+                FrameItem::Group(group) => Some((*pos, group.transform)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_impose_grid() {
+        let a5 = Size::new(Paper::A5.width(), Paper::A5.height());
+        let sheets =
+            impose(&pages(5, a5), &imposition(2, 2, ImpositionOrder::Sequential))
+                .unwrap();
+        assert_eq!(sheets.len(), 2);
+        assert_eq!(
+            sheets[0].frame.size(),
+            Size::new(Paper::A4.width(), Paper::A4.height())
+        );
+
+        // The last sheet only has one page, in the top left cell.
+        let first = cells(&sheets[0]);
+        let last = cells(&sheets[1]);
+        assert_eq!(first.len(), 4);
+        assert_eq!(last.len(), 1);
+        assert_eq!(last[0].0, first[0].0);
+
+        // Pages are arranged left-to-right, then top-to-bottom.
+        assert!(first[0].0.x < first[1].0.x && first[0].0.y == first[1].0.y);
+        assert!(first[0].0.x == first[2].0.x && first[0].0.y < first[2].0.y);
+
+        // The scaling preserves the aspect ratio and fits the pages into
+        // their cells.
+        for (_, transform) in first {
+            assert_eq!(transform.sx, transform.sy);
+            assert!(a5.x * transform.sx.get() < Paper::A4.width() / 2.0);
+        }
+    }
+
+    #[test]
+    fn test_impose_aspect_ratio() {
+        // A wide page is scaled to the width of its cell and centered
+        // vertically.
+        let wide = Size::new(Abs::pt(400.0), Abs::pt(100.0));
+        let mut imposition = imposition(1, 2, ImpositionOrder::Sequential);
+        imposition.border = Some(FixedStroke::from_pair(Color::BLACK, Abs::pt(0.5)));
+        let sheets = impose(&pages(1, wide), &imposition).unwrap();
+        let (pos, transform) = cells(&sheets[0])[0];
+        let width = Paper::A4.width() - Abs::mm(10.0);
+        let scale = transform.sx.get();
+        assert_eq!(transform.sy.get(), scale);
+        assert!((wide.x * scale - width).abs() < Abs::pt(0.01));
+        assert!(pos.y > Abs::mm(5.0));
+
+        // The border surrounds the page.
+        let border = sheets[0].frame.items().find_map(|(p, item)| match item {
+            FrameItem::Shape(shape, _) => Some((*p, shape.geometry.clone())),
+            _ => None,
+        });
+        assert_eq!(border, Some((pos, Geometry::Rect(wide * scale))));
+    }
+
+    #[test]
+    fn test_impose_booklet() {
+        assert_eq!(
+            booklet(6),
+            [None, Some(0), Some(1), None, Some(5), Some(2), Some(3), Some(4)]
+        );
+
+        let a5 = Size::new(Paper::A5.width(), Paper::A5.height());
+        let sheets =
+            impose(&pages(8, a5), &imposition(2, 1, ImpositionOrder::Booklet)).unwrap();
+        assert_eq!(sheets.len(), 4);
+
+        // Booklets need two pages per sheet side.
+        let error = impose(&pages(8, a5), &imposition(2, 2, ImpositionOrder::Booklet));
+        assert_eq!(
+            error.unwrap_err(),
+            "booklet order requires exactly two pages per sheet side"
+        );
+    }
+
+    #[test]
+    fn test_impose_large_margin() {
+        let a5 = Size::new(Paper::A5.width(), Paper::A5.height());
+        let mut imposition = imposition(4, 1, ImpositionOrder::Sequential);
+        imposition.margin = Abs::mm(60.0);
+        assert_eq!(
+            impose(&pages(1, a5), &imposition).unwrap_err(),
+            "the margins leave no space for the pages on the sheet"
+        );
+    }
+}
@@ -5,8 +5,11 @@ use icu_provider_adapters::fork::ForkByKeyProvider;
 use icu_provider_blob::BlobDataProvider;
 use icu_segmenter::LineSegmenter;
 use once_cell::sync::Lazy;
+use unicode_segmentation::UnicodeSegmentation;
 
-use super::Preparation;
+use super::{line, Preparation};
+use crate::engine::Engine;
+use crate::layout::Abs;
 use crate::syntax::link_prefix;
 use crate::text::{Lang, TextElem};
 
@@ -158,6 +161,31 @@ pub(super) fn breakpoints<'a>(
     }
 }
 
+/// Calls `f` for all breakpoints like [`breakpoints`], but additionally yields
+/// a normal breakpoint between any two graphemes of a segment that is too wide
+/// to fit into a line of its own.
+///
+/// This is a last resort for text that lacks break opportunities, like a very
+/// long word in a narrow column.
+pub(super) fn emergency_breakpoints<'a>(
+    engine: &Engine,
+    p: &'a Preparation<'a>,
+    width: Abs,
+    mut f: impl FnMut(usize, Breakpoint),
+) {
+    let mut last = 0;
+    breakpoints(p, |end, breakpoint| {
+        if !width.fits(line(engine, p, last..end, breakpoint).width) {
+            let segment = p.bidi.text[last..end].trim_end();
+            for (i, _) in segment.grapheme_indices(true).skip(1) {
+                f(last + i, Breakpoint::Normal);
+            }
+        }
+        f(end, breakpoint);
+        last = end;
+    });
+}
+
 /// Produce linebreak opportunities for a link.
 fn linebreak_link(link: &str, mut f: impl FnMut(usize)) {
     #[derive(PartialEq)]
@@ -5,19 +5,19 @@ use comemo::{Tracked, TrackedMut};
 use unicode_bidi::{BidiInfo, Level as BidiLevel};
 use unicode_script::{Script, UnicodeScript};
 
-use self::linebreak::{breakpoints, Breakpoint};
+use self::linebreak::{breakpoints, emergency_breakpoints, Breakpoint};
 use self::shaping::{
     cjk_punct_style, is_of_cj_script, shape, ShapedGlyph, ShapedText, BEGIN_PUNCT_PAT,
     END_PUNCT_PAT,
 };
-use crate::diag::{bail, SourceResult};
+use crate::diag::{bail, warning, SourceResult};
 use crate::engine::{Engine, Route};
 use crate::eval::Tracer;
 use crate::foundations::{Content, Packed, Resolve, Smart, StyleChain, StyledElem};
 use crate::introspection::{Introspector, Locator, MetaElem};
 use crate::layout::{
-    Abs, AlignElem, Axes, BoxElem, Dir, Em, FixedAlignment, Fr, Fragment, Frame, HElem,
-    Point, Regions, Size, Sizing, Spacing,
+    grid_gap, Abs, AlignElem, Axes, BoxElem, Dir, Em, FixedAlignment, Fr, Fragment,
+    Frame, HElem, Point, Regions, Size, Sizing, Spacing,
 };
 use crate::math::{EquationElem, MathParItem};
 use crate::model::{Linebreaks, ParElem};
@@ -36,6 +36,7 @@ pub(crate) fn layout_inline(
     consecutive: bool,
     region: Size,
     expand: bool,
+    grid: Option<Abs>,
 ) -> SourceResult<Fragment> {
     #[comemo::memoize]
     #[allow(clippy::too_many_arguments)]
@@ -50,6 +51,7 @@ pub(crate) fn layout_inline(
         consecutive: bool,
         region: Size,
         expand: bool,
+        grid: Option<Abs>,
     ) -> SourceResult<Fragment> {
         let mut locator = Locator::chained(locator);
         let mut engine = Engine {
@@ -70,10 +72,10 @@ pub(crate) fn layout_inline(
         let p = prepare(&mut engine, children, &text, segments, spans, styles, region)?;
 
         // Break the paragraph into lines.
-        let lines = linebreak(&engine, &p, region.x - p.hang);
+        let lines = linebreak(&mut engine, &p, region.x - p.hang, expand);
 
         // Stack the lines into one frame per region.
-        finalize(&mut engine, &p, &lines, region, expand)
+        finalize(&mut engine, &p, &lines, region, expand, grid)
     }
 
     let fragment = cached(
@@ -87,12 +89,66 @@ pub(crate) fn layout_inline(
         consecutive,
         region,
         expand,
+        grid,
     )?;
 
     engine.locator.visit_frames(&fragment);
     Ok(fragment)
 }
 
+/// Counts the lines into which inline content breaks at the given width.
+///
+/// This runs the same preparation and line breaking as [`layout_inline`], but
+/// skips building the line frames.
+pub(crate) fn count_lines(
+    children: &[Content],
+    engine: &mut Engine,
+    styles: StyleChain,
+    consecutive: bool,
+    width: Abs,
+) -> SourceResult<usize> {
+    #[comemo::memoize]
+    #[allow(clippy::too_many_arguments)]
+    fn cached(
+        children: &[Content],
+        world: Tracked<dyn World + '_>,
+        introspector: Tracked<Introspector>,
+        route: Tracked<Route>,
+        locator: Tracked<Locator>,
+        tracer: TrackedMut<Tracer>,
+        styles: StyleChain,
+        consecutive: bool,
+        width: Abs,
+    ) -> SourceResult<usize> {
+        let mut locator = Locator::chained(locator);
+        let mut engine = Engine {
+            world,
+            introspector,
+            route: Route::extend(route),
+            locator: &mut locator,
+            tracer,
+        };
+
+        let region = Size::new(width, Abs::inf());
+        let (text, segments, spans) =
+            collect(children, &mut engine, &styles, region, consecutive)?;
+        let p = prepare(&mut engine, children, &text, segments, spans, styles, region)?;
+        Ok(linebreak(&mut engine, &p, width - p.hang, true).len())
+    }
+
+    cached(
+        children,
+        engine.world,
+        engine.introspector,
+        engine.route.track(),
+        engine.locator.track(),
+        TrackedMut::reborrow_mut(&mut engine.tracer),
+        styles,
+        consecutive,
+        width,
+    )
+}
+
 /// Range of a substring of text.
 type Range = std::ops::Range<usize>;
 
@@ -132,6 +188,8 @@ struct Preparation<'a> {
     leading: Abs,
     /// How to determine line breaks.
     linebreaks: Smart<Linebreaks>,
+    /// Whether overlong words may be broken at arbitrary positions.
+    break_words: bool,
     /// The text size.
     size: Abs,
 }
@@ -642,6 +700,7 @@ fn prepare<'a>(
         fallback: TextElem::fallback_in(styles),
         leading: ParElem::leading_in(styles),
         linebreaks: ParElem::linebreaks_in(styles),
+        break_words: ParElem::break_words_in(styles),
         size: TextElem::size_in(styles),
     })
 }
@@ -781,7 +840,18 @@ fn shared_get<T: PartialEq>(
 }
 
 /// Find suitable linebreaks.
-fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Line<'a>> {
+///
+/// If `fixed` is true, the width is the final width of the paragraph and, if
+/// enabled, words that don't fit into it are broken at arbitrary graphemes as
+/// a last resort. Otherwise, the paragraph is merely being measured and
+/// overlong words are kept intact so that they determine the measured width.
+/// A warning points at the first word that had to be broken.
+fn linebreak<'a>(
+    engine: &mut Engine,
+    p: &'a Preparation<'a>,
+    width: Abs,
+    fixed: bool,
+) -> Vec<Line<'a>> {
     let linebreaks = p.linebreaks.unwrap_or_else(|| {
         if p.justify {
             Linebreaks::Optimized
@@ -790,10 +860,36 @@ fn linebreak<'a>(engine: &Engine, p: &'a Preparation<'a>, width: Abs) -> Vec<Lin
         }
     });
 
-    match linebreaks {
-        Linebreaks::Simple => linebreak_simple(engine, p, width),
-        Linebreaks::Optimized => linebreak_optimized(engine, p, width),
+    let run = |emergency| match linebreaks {
+        Linebreaks::Simple => linebreak_simple(engine, p, width, emergency),
+        Linebreaks::Optimized => linebreak_optimized(engine, p, width, emergency),
+    };
+
+    let lines = run(false);
+    if !(p.break_words && fixed && width.is_finite())
+        || lines.iter().all(|line| width.fits(line.width))
+    {
+        return lines;
+    }
+
+    // Some lines are too wide. As a last resort, break the segments that
+    // don't fit at arbitrary graphemes.
+    let lines = run(true);
+    let mut regular = vec![];
+    breakpoints(p, |end, _| regular.push(end));
+    if let Some(forced) =
+        lines.iter().find(|line| regular.binary_search(&line.end).is_err())
+    {
+        let (span, _) = p.spans.span_at(forced.end);
+        if !span.is_detached() {
+            engine.tracer.warn(warning!(
+                span, "word is too long to fit into a line";
+                hint: "it was broken at an arbitrary position"
+            ));
+        }
     }
+
+    lines
 }
 
 /// Perform line breaking in simple first-fit style. This means that we build
@@ -803,12 +899,13 @@ fn linebreak_simple<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
     width: Abs,
+    emergency: bool,
 ) -> Vec<Line<'a>> {
     let mut lines = Vec::with_capacity(16);
     let mut start = 0;
     let mut last = None;
 
-    breakpoints(p, |end, breakpoint| {
+    each_breakpoint(engine, p, width, emergency, |end, breakpoint| {
         // Compute the line and its size.
         let mut attempt = line(engine, p, start..end, breakpoint);
 
@@ -863,6 +960,7 @@ fn linebreak_optimized<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
     width: Abs,
+    emergency: bool,
 ) -> Vec<Line<'a>> {
     /// The cost of a line or paragraph layout.
     type Cost = f64;
@@ -891,7 +989,7 @@ fn linebreak_optimized<'a>(
 
     let em = p.size;
     let mut lines = Vec::with_capacity(16);
-    breakpoints(p, |end, breakpoint| {
+    each_breakpoint(engine, p, width, emergency, |end, breakpoint| {
         let k = table.len();
         let eof = end == p.bidi.text.len();
         let mut best: Option<Entry> = None;
@@ -1008,6 +1106,21 @@ fn linebreak_optimized<'a>(
     lines
 }
 
+/// Calls `f` for all breakpoints, including emergency ones if requested.
+fn each_breakpoint<'a>(
+    engine: &Engine,
+    p: &'a Preparation<'a>,
+    width: Abs,
+    emergency: bool,
+    f: impl FnMut(usize, Breakpoint),
+) {
+    if emergency {
+        emergency_breakpoints(engine, p, width, f);
+    } else {
+        breakpoints(p, f);
+    }
+}
+
 /// Create a line which spans the given range.
 fn line<'a>(
     engine: &Engine,
@@ -1191,6 +1304,7 @@ fn finalize(
     lines: &[Line],
     region: Size,
     expand: bool,
+    grid: Option<Abs>,
 ) -> SourceResult<Fragment> {
     // Determine the paragraph's width: Full width of the region if we
     // should expand or there's fractional spacing, fit-to-width otherwise.
@@ -1210,11 +1324,27 @@ fn finalize(
         .map(|line| commit(engine, p, line, width, region.y))
         .collect::<SourceResult<_>>()?;
 
+    // Determine the gap between two lines that are kept together. With a
+    // baseline grid, it is enlarged to keep both baselines on the grid.
+    let gap = |first: &Frame, second: &Frame| match grid {
+        Some(grid) => {
+            let distance = first.height() - first.baseline() + second.baseline();
+            p.leading + grid_gap(distance + p.leading, grid)
+        }
+        None => p.leading,
+    };
+
+    // Determine the gap before the last line before merging the first lines.
+    let len = frames.len();
+    let last_gap =
+        if len >= 2 { gap(&frames[len - 2], &frames[len - 1]) } else { p.leading };
+
     // Prevent orphans.
     if frames.len() >= 2 && !frames[1].is_empty() {
+        let gap = gap(&frames[0], &frames[1]);
         let second = frames.remove(1);
         let first = &mut frames[0];
-        merge(first, second, p.leading);
+        merge(first, second, gap);
     }
 
     // Prevent widows.
@@ -1222,15 +1352,15 @@ fn finalize(
     if len >= 2 && !frames[len - 2].is_empty() {
         let second = frames.pop().unwrap();
         let first = frames.last_mut().unwrap();
-        merge(first, second, p.leading);
+        merge(first, second, last_gap);
     }
 
     Ok(Fragment::frames(frames))
 }
 
 /// Merge two line frames
-fn merge(first: &mut Frame, second: Frame, leading: Abs) {
-    let offset = first.height() + leading;
+fn merge(first: &mut Frame, second: Frame, gap: Abs) {
+    let offset = first.height() + gap;
     let total = offset + second.height();
     first.push_frame(Point::with_y(offset), second);
     first.size_mut().y = total;
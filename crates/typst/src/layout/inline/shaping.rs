@@ -12,15 +12,23 @@ use unicode_script::{Script, UnicodeScript};
 use super::SpanMapper;
 use crate::engine::Engine;
 use crate::foundations::StyleChain;
-use crate::layout::{Abs, Dir, Em, Frame, FrameItem, Point, Size};
+use crate::layout::{Abs, Dir, Em, Frame, FrameItem, Point, Ratio, Size, Transform};
 use crate::syntax::Span;
 use crate::text::{
-    decorate, families, features, variant, Font, FontVariant, Glyph, Lang, Region,
-    TextElem, TextItem,
+    decorate, families, features, variant, Font, FontStyle, FontVariant, FontWeight,
+    Glyph, Lang, Region, TextElem, TextItem,
 };
 use crate::util::SliceExt;
+use crate::visualize::{FixedStroke, LineJoin};
 use crate::World;
 
+/// The stroke thickness with which missing bold faces are synthesized.
+const SYNTHETIC_BOLD_STROKE: Em = Em::new(0.04);
+
+/// The horizontal skew with which missing italic faces are synthesized. This
+/// corresponds to a slant of roughly 12 degrees.
+const SYNTHETIC_ITALIC_SKEW: Ratio = Ratio::new(-0.21);
+
 /// The result of shaping text.
 ///
 /// This type contains owned or borrowed shaped text runs, which can be
@@ -231,6 +239,7 @@ impl<'a> ShapedText<'a> {
         let decos = TextElem::deco_in(self.styles);
         let fill = TextElem::fill_in(self.styles);
         let stroke = TextElem::stroke_in(self.styles);
+        let synthesis = TextElem::synthesis_in(self.styles);
         let span_offset = TextElem::span_offset_in(self.styles);
 
         for ((font, y_offset), group) in
@@ -305,22 +314,46 @@ impl<'a> ShapedText<'a> {
                 })
                 .collect();
 
+            let (bold, italic) = if synthesis {
+                missing_styles(self.variant, font.info().variant)
+            } else {
+                (false, false)
+            };
+
+            let mut stroke = stroke.clone().map(|s| s.unwrap_or_default());
+            if bold && stroke.is_none() {
+                stroke = Some(FixedStroke {
+                    paint: fill.clone(),
+                    thickness: SYNTHETIC_BOLD_STROKE.at(self.size),
+                    join: LineJoin::Round,
+                    ..FixedStroke::default()
+                });
+            }
+
             let item = TextItem {
                 font,
                 size: self.size,
                 lang,
                 fill: fill.clone(),
-                stroke: stroke.clone().map(|s| s.unwrap_or_default()),
+                stroke,
                 text: self.text[range.start - self.base..range.end - self.base].into(),
                 glyphs,
             };
 
             let width = item.width();
-            if decos.is_empty() {
-                frame.push(pos, FrameItem::Text(item));
+            let decorated = (!decos.is_empty()).then(|| item.clone());
+            if italic {
+                // Slant the glyphs around the start of their baseline.
+                let mut slanted = Frame::soft(Size::zero());
+                slanted.push(Point::zero(), FrameItem::Text(item));
+                slanted.transform(Transform::skew(SYNTHETIC_ITALIC_SKEW, Ratio::zero()));
+                frame.push_frame(pos, slanted);
             } else {
-                // Apply line decorations.
-                frame.push(pos, FrameItem::Text(item.clone()));
+                frame.push(pos, FrameItem::Text(item));
+            }
+
+            // Apply line decorations.
+            if let Some(item) = decorated {
                 for deco in &decos {
                     decorate(&mut frame, deco, &item, width, shift, pos);
                 }
@@ -363,7 +396,16 @@ impl<'a> ShapedText<'a> {
         } else {
             for g in self.glyphs.iter() {
                 let bbox = if top_edge.is_bounds() || bottom_edge.is_bounds() {
-                    g.font.ttf().glyph_bounding_box(ttf_parser::GlyphId(g.glyph_id))
+                    g.font.ttf().glyph_bounding_box(ttf_parser::GlyphId(g.glyph_id)).map(
+                        |mut bbox| {
+                            // Marks and other glyphs can be shifted vertically
+                            // by the shaper.
+                            let dy = (g.y_offset.get() * g.font.units_per_em()).round();
+                            bbox.y_min = bbox.y_min.saturating_add(dy as i16);
+                            bbox.y_max = bbox.y_max.saturating_add(dy as i16);
+                            bbox
+                        },
+                    )
                 } else {
                     None
                 };
@@ -591,6 +633,18 @@ struct ShapingContext<'a, 'v> {
     dir: Dir,
 }
 
+/// Determine which of the requested bold and italic styles are missing from
+/// the variant of the font that was selected, returning whether to synthesize
+/// bold and italic, respectively.
+fn missing_styles(requested: FontVariant, selected: FontVariant) -> (bool, bool) {
+    let bold = requested.weight >= FontWeight::SEMIBOLD
+        && selected.weight < FontWeight::SEMIBOLD
+        && selected.weight.to_number() + 200 <= requested.weight.to_number();
+    let italic =
+        requested.style != FontStyle::Normal && selected.style == FontStyle::Normal;
+    (bold, italic)
+}
+
 /// Shape text into [`ShapedText`].
 #[allow(clippy::too_many_arguments)]
 pub(super) fn shape<'a>(
@@ -683,41 +737,25 @@ fn shape_segment<'a>(
 
     ctx.used.push(font.clone());
 
-    // Fill the buffer with our text.
-    let mut buffer = UnicodeBuffer::new();
-    buffer.push_str(text);
-    buffer.set_language(language(ctx.styles));
-    if let Some(script) = TextElem::script_in(ctx.styles).custom().and_then(|script| {
+    // Determine the script override, if any.
+    let script = TextElem::script_in(ctx.styles).custom().and_then(|script| {
         rustybuzz::Script::from_iso15924_tag(Tag::from_bytes(script.as_bytes()))
-    }) {
-        buffer.set_script(script)
-    }
-    buffer.set_direction(match ctx.dir {
+    });
+
+    let direction = match ctx.dir {
         Dir::LTR => rustybuzz::Direction::LeftToRight,
         Dir::RTL => rustybuzz::Direction::RightToLeft,
         _ => unimplemented!("vertical text layout"),
-    });
-    buffer.guess_segment_properties();
-
-    // Prepare the shape plan. This plan depends on direction, script, language,
-    // and features, but is independent from the text and can thus be
-    // memoized.
-    let plan = create_shape_plan(
-        &font,
-        buffer.direction(),
-        buffer.script(),
-        buffer.language().as_ref(),
-        &ctx.features,
-    );
+    };
 
     // Shape!
-    let buffer = rustybuzz::shape_with_plan(font.rusty(), &plan, buffer);
-    let infos = buffer.glyph_infos();
-    let pos = buffer.glyph_positions();
+    let run =
+        shape_run(&font, text, direction, script, language(ctx.styles), &ctx.features);
     let ltr = ctx.dir.is_positive();
 
     // Collect the shaped glyphs, doing fallback and shaping parts again with
     // the next font if necessary.
+    let infos = &run[..];
     let mut i = 0;
     while i < infos.len() {
         let info = &infos[i];
@@ -734,17 +772,17 @@ fn shape_segment<'a>(
 
             let c = text[cluster..].chars().next().unwrap();
             let script = c.script();
-            let x_advance = font.to_em(pos[i].x_advance);
+            let x_advance = font.to_em(info.x_advance);
             ctx.glyphs.push(ShapedGlyph {
                 font: font.clone(),
                 glyph_id: info.glyph_id as u16,
                 // TODO: Don't ignore y_advance.
                 x_advance,
-                x_offset: font.to_em(pos[i].x_offset),
-                y_offset: font.to_em(pos[i].y_offset),
+                x_offset: font.to_em(info.x_offset),
+                y_offset: font.to_em(info.y_offset),
                 adjustability: Adjustability::default(),
                 range: start..end,
-                safe_to_break: !info.unsafe_to_break(),
+                safe_to_break: info.safe_to_break,
                 c,
                 span: ctx.spans.span_at(start),
                 is_justifiable: is_justifiable(
@@ -802,6 +840,76 @@ fn shape_segment<'a>(
     ctx.used.pop();
 }
 
+/// A glyph as produced by shaping a text with a single font.
+#[derive(Debug, Clone)]
+struct RunGlyph {
+    /// The glyph's index in the font. Zero if the font has no glyph.
+    glyph_id: u32,
+    /// The byte offset of the glyph's cluster in the shaped text.
+    cluster: u32,
+    /// The horizontal advance in font units.
+    x_advance: i32,
+    /// The horizontal offset in font units.
+    x_offset: i32,
+    /// The vertical offset in font units.
+    y_offset: i32,
+    /// Whether the text may be split before this glyph without reshaping.
+    safe_to_break: bool,
+}
+
+/// Shape a text with a single font.
+///
+/// This is memoized because the same runs (think of page headers, footers
+/// and repeated labels) are shaped over and over again. The font is part of
+/// the key, so a different fallback choice automatically yields a fresh
+/// result.
+#[typst_macros::time(name = "shape run")]
+#[comemo::memoize]
+fn shape_run(
+    font: &Font,
+    text: &str,
+    direction: rustybuzz::Direction,
+    script: Option<rustybuzz::Script>,
+    language: rustybuzz::Language,
+    features: &[rustybuzz::Feature],
+) -> Arc<[RunGlyph]> {
+    // Fill the buffer with our text.
+    let mut buffer = UnicodeBuffer::new();
+    buffer.push_str(text);
+    buffer.set_language(language);
+    if let Some(script) = script {
+        buffer.set_script(script);
+    }
+    buffer.set_direction(direction);
+    buffer.guess_segment_properties();
+
+    // Prepare the shape plan. This plan depends on direction, script, language,
+    // and features, but is independent from the text and can thus be
+    // memoized.
+    let plan = create_shape_plan(
+        font,
+        buffer.direction(),
+        buffer.script(),
+        buffer.language().as_ref(),
+        features,
+    );
+
+    let buffer = rustybuzz::shape_with_plan(font.rusty(), &plan, buffer);
+    buffer
+        .glyph_infos()
+        .iter()
+        .zip(buffer.glyph_positions())
+        .map(|(info, pos)| RunGlyph {
+            glyph_id: info.glyph_id,
+            cluster: info.cluster,
+            x_advance: pos.x_advance,
+            x_offset: pos.x_offset,
+            y_offset: pos.y_offset,
+            safe_to_break: !info.unsafe_to_break(),
+        })
+        .collect()
+}
+
 /// Create a shape plan.
 #[comemo::memoize]
 fn create_shape_plan(
@@ -1093,3 +1201,82 @@ fn is_justifiable(
         || is_cjk_right_aligned_punctuation(c, x_advance, stretchability)
         || is_cjk_center_aligned_punctuation(c, style)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::foundations::Bytes;
+    use crate::testing::TestWorld;
+
+    /// Lay out the source in New Computer Modern Math, which only has regular
+    /// faces, and list all non-blank text runs in the first page with whether
+    /// they are stroked and whether they are slanted.
+    fn runs(text: &str) -> Vec<(String, bool, bool)> {
+        fn walk(frame: &Frame, slanted: bool, runs: &mut Vec<(String, bool, bool)>) {
+            for (_, item) in frame.items() {
+                match item {
+                    FrameItem::Group(group) => walk(
+                        &group.frame,
+                        slanted || group.transform.kx != Ratio::zero(),
+                        runs,
+                    ),
+                    FrameItem::Text(text) if !text.text.trim().is_empty() => runs.push((
+                        text.text.trim().into(),
+                        text.stroke.is_some(),
+                        slanted,
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        let text = format!("#set text(font: \"New Computer Modern Math\")\n{text}");
+        let world = TestWorld::new(&text);
+        let document = crate::compile(&world, &mut Default::default()).unwrap();
+        let mut runs = vec![];
+        walk(&document.pages[0].frame, false, &mut runs);
+        runs
+    }
+
+    #[test]
+    fn test_shape_run_memoized() {
+        let mut fonts =
+            typst_assets::fonts().flat_map(|data| Font::iter(Bytes::from_static(data)));
+        let (a, b) = (fonts.next().unwrap(), fonts.next().unwrap());
+        let shape = |font: &Font, text: &str| {
+            let language = rustybuzz::Language::from_str("en").unwrap();
+            shape_run(font, text, rustybuzz::Direction::LeftToRight, None, language, &[])
+        };
+        let shaped = || typst_timing::count("shape run");
+        typst_timing::enable();
+
+        // Shaping the same run again reuses the first result.
+        let start = shaped();
+        let first = shape(&a, "A run that is shaped twice");
+        let second = shape(&a, "A run that is shaped twice");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(shaped() - start, 1);
+
+        // A different text or font is shaped anew.
+        shape(&a, "Another run");
+        shape(&b, "A run that is shaped twice");
+        assert_eq!(shaped() - start, 3);
+    }
+
+    #[test]
+    fn test_synthesized_styles() {
+        // The family only has regular faces.
+        assert_eq!(
+            runs("Regular *Bold* _Italic_"),
+            [
+                ("Regular".into(), false, false),
+                ("Bold".into(), true, false),
+                ("Italic".into(), false, true),
+            ]
+        );
+        assert_eq!(
+            runs("#set text(synthesis: false)\n*Bold* _Italic_"),
+            [("Bold".into(), false, false), ("Italic".into(), false, false)]
+        );
+    }
+}
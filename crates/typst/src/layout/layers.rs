@@ -0,0 +1,87 @@
+use crate::diag::{warning, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{elem, Content, Packed, Resolve, StyleChain};
+use crate::layout::{
+    Alignment, Axes, FixedAlignment, Fragment, Frame, LayoutMultiple, Regions, Size,
+};
+
+/// Places content in layers on top of each other.
+///
+/// All layers are laid out into the same space. The result is as large as the
+/// largest layer and the layers are painted in order, so the first layer ends
+/// up at the bottom and the last one at the top. Smaller layers are aligned
+/// within the result according to the `align` property.
+///
+/// The layers are never broken across pages. If they don't fit into the
+/// available space, they overflow and a warning is emitted.
+///
+/// # Example
+/// ```example
+/// #layers(
+///   align: center + horizon,
+///   rect(width: 100%, height: 40pt, fill: aqua),
+///   text(20pt, gray)[DRAFT],
+/// )
+/// ```
+#[elem(LayoutMultiple)]
+pub struct LayersElem {
+    /// How to align the layers relative to each other.
+    ///
+    /// ```example
+    /// #layers(
+    ///   align: bottom + right,
+    ///   square(size: 40pt, fill: aqua),
+    ///   square(size: 15pt, fill: eastern),
+    /// )
+    /// ```
+    #[fold]
+    #[default]
+    pub align: Alignment,
+
+    /// The layers, from bottom to top.
+    #[variadic]
+    pub children: Vec<Content>,
+}
+
+impl LayoutMultiple for Packed<LayersElem> {
+    #[typst_macros::time(name = "layers", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let align = self.align(styles).resolve(styles);
+
+        // Lay out the layers into a single region without expansion so that
+        // they can be aligned.
+        let base = regions.base();
+        let pod = Regions::one(base, Axes::splat(false));
+        let mut layers = vec![];
+        for child in self.children() {
+            layers.push(child.layout(engine, styles, pod)?.into_frame());
+        }
+
+        let size = layers.iter().fold(Size::zero(), |size, frame| size.max(frame.size()));
+        if !base.fits(size) {
+            engine.tracer.warn(warning!(
+                self.span(), "layers do not fit into the available space";
+                hint: "layers are not broken across pages"
+            ));
+        }
+
+        let size = regions.expand.select(base, size);
+        let mut output = Frame::soft(size);
+        for (i, frame) in layers.into_iter().enumerate() {
+            let pos = align
+                .zip_map(size - frame.size(), FixedAlignment::position)
+                .to_point();
+            if i == 0 && frame.has_baseline() {
+                output.set_baseline(pos.y + frame.baseline());
+            }
+            output.push_frame(pos, frame);
+        }
+
+        Ok(Fragment::frame(output))
+    }
+}
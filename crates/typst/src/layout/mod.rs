@@ -7,6 +7,7 @@ mod axes;
 mod columns;
 mod container;
 mod corners;
+mod diff;
 mod dir;
 mod em;
 mod flow;
@@ -15,7 +16,9 @@ mod fragment;
 mod frame;
 mod grid;
 mod hide;
+mod impose;
 mod inline;
+mod layers;
 #[path = "layout.rs"]
 mod layout_;
 mod length;
@@ -42,6 +45,7 @@ pub use self::axes::*;
 pub use self::columns::*;
 pub use self::container::*;
 pub use self::corners::*;
+pub use self::diff::*;
 pub use self::dir::*;
 pub use self::em::*;
 pub use self::flow::*;
@@ -50,6 +54,8 @@ pub use self::fragment::*;
 pub use self::frame::*;
 pub use self::grid::*;
 pub use self::hide::*;
+pub use self::impose::*;
+pub use self::layers::*;
 pub use self::layout_::*;
 pub use self::length::*;
 pub use self::measure_::*;
@@ -114,6 +120,7 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<ScaleElem>();
     global.define_elem::<RotateElem>();
     global.define_elem::<HideElem>();
+    global.define_elem::<LayersElem>();
     global.define_func::<measure>();
     global.define_func::<layout>();
 }
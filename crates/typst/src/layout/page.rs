@@ -13,15 +13,16 @@ use crate::foundations::{
 };
 use crate::introspection::{Counter, CounterDisplayElem, CounterKey, ManualPageCounter};
 use crate::layout::{
-    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, HAlignment, LayoutMultiple,
-    Length, OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size, SpecificAlignment,
-    VAlignment,
+    Abs, AlignElem, Alignment, Axes, ColumnsElem, Dir, Frame, FrameItem, HAlignment,
+    LayoutMultiple, Length, OuterVAlignment, Point, Ratio, Regions, Rel, Sides, Size,
+    SpecificAlignment, VAlignment,
 };
 
 use crate::model::Numbering;
+use crate::syntax::Span;
 use crate::text::TextElem;
 use crate::util::{NonZeroExt, Numeric, Scalar};
-use crate::visualize::Paint;
+use crate::visualize::{Color, FixedStroke, Geometry, Paint};
 
 /// Layouts its child onto one or multiple pages.
 ///
@@ -175,6 +176,21 @@ pub struct PageElem {
     #[default(NonZeroUsize::ONE)]
     pub columns: NonZeroUsize,
 
+    /// How to align the page's content as a whole within the area between the
+    /// margins.
+    ///
+    /// By default, the content fills the page from the top. With an
+    /// alignment, content that is smaller than the available area is placed
+    /// into it as a block, without affecting the alignment of the text within.
+    /// This is useful for title pages. Headers and footers are not affected.
+    ///
+    /// ```example
+    /// #set page(height: 120pt, align: center + horizon)
+    /// *A Title* \
+    /// and a subtitle
+    /// ```
+    pub align: Option<Alignment>,
+
     /// The page's background color.
     ///
     /// This instructs the printer to color the complete page with the given
@@ -190,6 +206,48 @@ pub struct PageElem {
     #[borrowed]
     pub fill: Option<Paint>,
 
+    /// How far the page's fill and background extend beyond its edges.
+    ///
+    /// Printers usually can't print up to the edges of a sheet. Pages whose
+    /// fill or background should reach the edges are thus printed on larger
+    /// sheets and trimmed to size. So that no white edge remains when a cut is
+    /// slightly off, the [fill]($page.fill) and the
+    /// [background]($page.background) extend into the bleed on each side.
+    ///
+    /// The bleed doesn't change the page's size or the area available to its
+    /// content. In PDF export, the page's trim and bleed boxes record where to
+    /// cut. Note that [positions]($location.position) are measured from the
+    /// top-left of the exported page and thus include the bleed and the space
+    /// for [crop marks]($page.marks).
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 120pt,
+    ///   height: 80pt,
+    ///   bleed: 6pt,
+    ///   fill: aqua,
+    /// )
+    /// ```
+    #[resolve]
+    pub bleed: Length,
+
+    /// Whether to draw crop marks that show where to trim the page.
+    ///
+    /// The marks are drawn outside of the bleed, so the page grows by some
+    /// extra space on each side to make room for them.
+    ///
+    /// ```example
+    /// #set page(
+    ///   width: 120pt,
+    ///   height: 80pt,
+    ///   bleed: 6pt,
+    ///   fill: aqua,
+    ///   marks: true,
+    /// )
+    /// ```
+    #[default(false)]
+    pub marks: bool,
+
     /// How to [number]($numbering) the pages.
     ///
     /// If an explicit `footer` (or `header` for top-aligned numbering) is
@@ -384,12 +442,37 @@ impl Packed<PageElem> {
                 .spanned(self.span());
         }
 
+        // Ensure that the margins leave room for the content.
         let area = size - margin.sum_by_axis();
-        let mut regions = Regions::repeat(area, area.map(Abs::is_finite));
+        if area.x < Abs::zero() || area.y < Abs::zero() {
+            bail!(
+                self.span(), "page margins exceed the page size";
+                hint: "reduce the margins or increase the page size"
+            );
+        }
+
+        // Aligned content is laid out at its natural size and placed into the
+        // area afterwards.
+        let align = self.align(styles);
+        let expand = area.map(|v| v.is_finite() && align.is_none());
+        let mut regions = Regions::repeat(area, expand);
         regions.root = true;
 
+        // Text with an automatic fill contrasts with the page's fill, unless a
+        // container in between has one.
+        let fill = self.fill(styles);
+        let backed = TextElem::backed(fill, styles);
+        let inner = styles.chain(&backed);
+
         // Layout the child.
-        let mut frames = child.layout(engine, styles, regions)?.into_frames();
+        let mut frames = child.layout(engine, inner, regions)?.into_frames();
+        if let Some(align) = align {
+            let align = align.resolve(styles);
+            for frame in &mut frames {
+                let target = area.map(Abs::is_finite).select(area, frame.size());
+                frame.resize(target, align);
+            }
+        }
 
         // Align the child to the pagebreak's parity.
         // Check for page count after adding the pending frames
@@ -401,7 +484,9 @@ impl Packed<PageElem> {
             frames.push(Frame::hard(size));
         }
 
-        let fill = self.fill(styles);
+        let bleed = self.bleed(styles);
+        // The space around the bleed that is reserved for crop marks.
+        let slug = if self.marks(styles) { Abs::pt(18.0) } else { Abs::zero() };
         let foreground = Cow::Borrowed(self.foreground(styles));
         let background = Cow::Borrowed(self.background(styles));
         let header_ascent = self.header_ascent(styles);
@@ -478,8 +563,8 @@ impl Packed<PageElem> {
                     area = Size::new(pw, margin.bottom - descent);
                     align = Alignment::TOP;
                 } else {
-                    pos = Point::zero();
-                    area = size;
+                    pos = Point::splat(-bleed);
+                    area = size + Size::splat(2.0 * bleed);
                     align = HAlignment::Center + VAlignment::Horizon;
                 };
 
@@ -487,7 +572,7 @@ impl Packed<PageElem> {
                 let sub = content
                     .clone()
                     .styled(AlignElem::set_alignment(align))
-                    .layout(engine, styles, pod)?
+                    .layout(engine, inner, pod)?
                     .into_frame();
 
                 if ptr::eq(marginal, &header) || ptr::eq(marginal, &background) {
@@ -497,15 +582,28 @@ impl Packed<PageElem> {
                 }
             }
 
+            // Realize the bleed.
+            frame.set_size(size + Size::splat(2.0 * bleed));
+            frame.translate(Point::splat(bleed));
+
             if let Some(fill) = fill {
                 frame.fill(fill.clone());
             }
 
+            // Realize the crop marks.
+            if !slug.is_zero() {
+                frame.set_size(frame.size() + Size::splat(2.0 * slug));
+                frame.translate(Point::splat(slug));
+                crop_marks(&mut frame, size, bleed, slug);
+            }
+
             page_counter.visit(engine, &frame)?;
             pages.push(Page {
                 frame,
                 numbering: numbering.clone(),
                 number: page_counter.logical(),
+                bleed,
+                slug,
             });
 
             page_counter.step();
@@ -515,16 +613,57 @@ impl Packed<PageElem> {
     }
 }
 
+/// Draw crop marks in line with the edges of the trimmed page.
+///
+/// The frame's origin must be at the top-left corner of the space reserved for
+/// the marks and the trimmed page must have the given size.
+fn crop_marks(frame: &mut Frame, size: Size, bleed: Abs, slug: Abs) {
+    let stroke = FixedStroke::from_pair(Color::BLACK, Abs::pt(0.25));
+    let trim = slug + bleed;
+
+    // The marks keep a small gap to the bleed so that they aren't visible
+    // if the cut is slightly off.
+    let gap = Abs::pt(3.0);
+    let length = slug - gap;
+
+    // Where the marks beyond the right and bottom edges start.
+    let far = Point::splat(trim + bleed + gap) + size.to_point();
+
+    let mut mark = |pos: Point, target: Point| {
+        let shape = Geometry::Line(target).stroked(stroke.clone());
+        frame.push(pos, FrameItem::Shape(shape, Span::detached()));
+    };
+
+    for y in [trim, trim + size.y] {
+        mark(Point::with_y(y), Point::with_x(length));
+        mark(Point::new(far.x, y), Point::with_x(length));
+    }
+
+    for x in [trim, trim + size.x] {
+        mark(Point::with_x(x), Point::with_y(length));
+        mark(Point::new(x, far.y), Point::with_y(length));
+    }
+}
+
 /// A finished page.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Hash)]
 pub struct Page {
     /// The frame that defines the page.
+    ///
+    /// Includes the bleed and the space for crop marks around the trimmed
+    /// page. The trimmed page thus starts at `bleed + slug` on both axes, and
+    /// positions within the frame are offset by that amount.
     pub frame: Frame,
     /// The page's numbering.
     pub numbering: Option<Numbering>,
     /// The logical page number (controlled by `counter(page)` and may thus not
     /// match the physical number).
     pub number: usize,
+    /// How far the frame extends beyond the trimmed page on each side, not
+    /// counting the space for crop marks.
+    pub bleed: Abs,
+    /// The space for crop marks around the bleed on each side.
+    pub slug: Abs,
 }
 
 /// Specification of the page's margins.
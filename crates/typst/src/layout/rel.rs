@@ -4,7 +4,7 @@ use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssi
 
 use ecow::{eco_format, EcoString};
 
-use crate::foundations::{cast, ty, Fold, Repr, Resolve, StyleChain};
+use crate::foundations::{cast, func, scope, ty, Fold, Repr, Resolve, StyleChain};
 use crate::layout::{Abs, Em, Length, Ratio};
 use crate::util::Numeric;
 
@@ -25,7 +25,7 @@ use crate::util::Numeric;
 /// A relative length has the following fields:
 /// - `length`: Its length component.
 /// - `ratio`: Its ratio component.
-#[ty(cast, name = "relative", title = "Relative Length")]
+#[ty(scope, cast, name = "relative", title = "Relative Length")]
 #[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Rel<T: Numeric = Length> {
     /// The relative part.
@@ -75,6 +75,28 @@ impl<T: Numeric> Rel<T> {
     }
 }
 
+#[scope]
+impl Rel {
+    /// Resolves this relative length against a base length, which the ratio
+    /// component is taken of.
+    ///
+    /// This is useful to find out what a relative length would amount to in a
+    /// container of known size.
+    ///
+    /// ```example
+    /// #(100% - 2cm).resolve(20cm) \
+    /// #relative.resolve(50% + 1em, 10pt)
+    /// ```
+    #[func(name = "resolve")]
+    pub fn resolve_against(
+        self,
+        /// The length that the ratio component is relative to.
+        base: Length,
+    ) -> Length {
+        self.relative_to(base)
+    }
+}
+
 impl Rel<Length> {
     /// Try to divide two relative lengths.
     pub fn try_div(self, other: Self) -> Option<f64> {
@@ -7,7 +7,8 @@ use crate::util::Numeric;
 ///
 /// The spacing can be absolute, relative, or fractional. In the last case, the
 /// remaining space on the line is distributed among all fractional spacings
-/// according to their relative fractions.
+/// according to their relative fractions. Negative spacing pulls adjacent
+/// content closer together and can make it overlap.
 ///
 /// # Example
 /// ```example
@@ -90,6 +91,11 @@ impl Behave for Packed<HElem> {
 /// the remaining space on the page is distributed among all fractional spacings
 /// according to their relative fractions.
 ///
+/// Negative spacing moves the following content up, so that it may overlap
+/// with what comes before. It adds to the spacing between blocks instead of
+/// collapsing with it. Spacing that would move content out of the page or
+/// region entirely is clamped with a warning.
+///
 /// # Example
 /// ```example
 /// #grid(
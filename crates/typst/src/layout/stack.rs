@@ -1,12 +1,13 @@
 use std::fmt::{self, Debug, Formatter};
 
-use crate::diag::SourceResult;
+use crate::diag::{warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{cast, elem, Content, Packed, Resolve, StyleChain, StyledElem};
 use crate::layout::{
     Abs, AlignElem, Axes, Axis, Dir, FixedAlignment, Fr, Fragment, Frame, HElem,
     LayoutMultiple, Point, Regions, Size, Spacing, VElem,
 };
+use crate::syntax::Span;
 use crate::util::{Get, Numeric};
 
 /// Arranges content and spacing horizontally or vertically.
@@ -44,6 +45,16 @@ pub struct StackElem {
     pub dir: Dir,
 
     /// Spacing to insert between items where no explicit spacing was provided.
+    ///
+    /// ```example
+    /// #stack(
+    ///   dir: ltr,
+    ///   spacing: 4pt,
+    ///   square(size: 20pt),
+    ///   square(size: 10pt),
+    ///   square(size: 30pt),
+    /// )
+    /// ```
     pub spacing: Option<Spacing>,
 
     /// The children to stack along the axis.
@@ -97,7 +108,7 @@ impl LayoutMultiple for Packed<StackElem> {
             }
         }
 
-        Ok(layouter.finish())
+        Ok(layouter.finish(engine, self.span()))
     }
 }
 
@@ -152,6 +163,8 @@ struct StackLayouter<'a> {
     items: Vec<StackItem>,
     /// Finished frames for previous regions.
     finished: Vec<Frame>,
+    /// Whether a horizontal stack exceeded the width of a region.
+    overflowed: bool,
 }
 
 /// A prepared item in a stack layout.
@@ -184,6 +197,7 @@ impl<'a> StackLayouter<'a> {
             fr: Fr::zero(),
             items: vec![],
             finished: vec![],
+            overflowed: false,
         }
     }
 
@@ -191,12 +205,14 @@ impl<'a> StackLayouter<'a> {
     fn layout_spacing(&mut self, spacing: Spacing) {
         match spacing {
             Spacing::Rel(v) => {
-                // Resolve the spacing and limit it to the remaining space.
+                // Resolve the spacing and limit it to the remaining space. A
+                // region that is already full or overfull leaves no space, but
+                // doesn't turn positive spacing negative.
                 let resolved = v
                     .resolve(self.styles)
                     .relative_to(self.regions.base().get(self.axis));
                 let remaining = self.regions.size.get_mut(self.axis);
-                let limited = resolved.min(*remaining);
+                let limited = resolved.min((*remaining).max(Abs::zero()));
                 if self.dir.axis() == Axis::Y {
                     *remaining -= limited;
                 }
@@ -217,6 +233,11 @@ impl<'a> StackLayouter<'a> {
         block: &Content,
         styles: StyleChain,
     ) -> SourceResult<()> {
+        // A region without any space left, like a zero-height one, can't hold
+        // the block, so we advance to the next region. This happens at most
+        // once per block: if the next region is full as well, the block is
+        // laid out there regardless. Otherwise, a series of zero-height
+        // regions would make us advance without ever placing anything.
         if self.regions.is_full() {
             self.finish_region();
         }
@@ -269,7 +290,10 @@ impl<'a> StackLayouter<'a> {
 
         // Expand fully if there are fr spacings.
         let full = self.initial.get(self.axis);
-        let remaining = full - self.used.main;
+        let remaining = (full - self.used.main).max(Abs::zero());
+        if self.axis == Axis::X && !full.fits(self.used.main) {
+            self.overflowed = true;
+        }
         if self.fr.get() > 0.0 && full.is_finite() {
             self.used.main = full;
             size.set(self.axis, full);
@@ -323,8 +347,14 @@ impl<'a> StackLayouter<'a> {
     }
 
     /// Finish layouting and return the resulting frames.
-    fn finish(mut self) -> Fragment {
+    fn finish(mut self, engine: &mut Engine, span: Span) -> Fragment {
         self.finish_region();
+        if self.overflowed {
+            engine.tracer.warn(warning!(
+                span, "stack is wider than the available space";
+                hint: "horizontal stacks do not wrap their children"
+            ));
+        }
         Fragment::frames(self.finished)
     }
 }
@@ -364,3 +394,54 @@ impl Gen<Abs> {
         self.into_axes(main).to_point()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::Tracer;
+    use crate::testing::TestWorld;
+
+    #[test]
+    fn test_stack_pathological_regions() {
+        // A small xorshift generator, so that the cases are reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut pick = move |options: &[&'static str]| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            options[(state % options.len() as u64) as usize]
+        };
+
+        // Pages without any space, overfull regions, negative spacing, and
+        // blocks that are taller than the page must neither panic nor loop.
+        for _ in 0..200 {
+            let height = pick(&["0pt", "0.5pt", "3pt", "20pt", "auto"]);
+            let margin = pick(&["0pt", "1pt", "2pt"]);
+            let dir = pick(&["ttb", "btt", "ltr"]);
+            let children: Vec<_> = (0..5)
+                .map(|_| {
+                    pick(&[
+                        "rect(height: 5pt)",
+                        "rect(width: 30pt, height: 0pt)",
+                        "v(1fr)",
+                        "v(-4pt)",
+                        "v(10pt)",
+                        "h(1fr)",
+                        "box(height: 4pt - 6pt)",
+                        "block(height: 12pt, breakable: true)",
+                        "block(breakable: true, stack(v(4pt), rect(height: 4pt)))",
+                        "[A #parbreak() B]",
+                    ])
+                })
+                .collect();
+
+            let source = format!(
+                "#set page(height: {height}, margin: {margin})\n#stack(dir: {dir}, {})",
+                children.join(", "),
+            );
+            let world = TestWorld::new(&source);
+            if let Ok(document) = crate::compile(&world, &mut Tracer::new()) {
+                assert!(document.pages.len() < 1000, "too many pages for {source}");
+            }
+        }
+    }
+}
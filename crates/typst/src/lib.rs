@@ -53,6 +53,10 @@ pub mod symbols;
 pub mod text;
 pub mod visualize;
 
+#[cfg(any(test, feature = "testing"))]
+#[doc(hidden)]
+pub mod testing;
+
 #[doc(inline)]
 pub use typst_syntax as syntax;
 
@@ -103,6 +107,23 @@ pub fn compile(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Document>
     typeset(world, tracer, &module.content()).map_err(deduplicate)
 }
 
+/// Evaluate a source file into its content, without laying it out.
+///
+/// This is all that exports of a document's structure need. Errors and
+/// warnings are reported in the same way as for [`compile`].
+#[typst_macros::time(name = "evaluate")]
+pub fn evaluate(world: &dyn World, tracer: &mut Tracer) -> SourceResult<Content> {
+    let world = world.track();
+    let module = crate::eval::eval(
+        world,
+        Route::default().track(),
+        tracer.track_mut(),
+        &world.main(),
+    )
+    .map_err(deduplicate)?;
+    Ok(module.content())
+}
+
 /// Relayout until introspection converges.
 fn typeset(
     world: Tracked<dyn World + '_>,
@@ -255,6 +276,9 @@ pub struct Library {
     /// The default style properties (for page size, font selection, and
     /// everything else configurable via set and show rules).
     pub styles: Styles,
+    /// The maximum number of elements that evaluation may produce by
+    /// repeating content or by joining it, e.g. in a loop.
+    pub max_elements: usize,
 }
 
 impl Library {
@@ -277,6 +301,8 @@ impl Default for Library {
 #[derive(Debug, Clone, Default)]
 pub struct LibraryBuilder {
     inputs: Option<Dict>,
+    variant: Option<EcoString>,
+    max_elements: Option<usize>,
 }
 
 impl LibraryBuilder {
@@ -286,19 +312,36 @@ impl LibraryBuilder {
         self
     }
 
+    /// Configure the variant of the document visible through `sys.variant`.
+    ///
+    /// Since the variant is part of the library, modules evaluated for one
+    /// variant are never reused for another one.
+    pub fn with_variant(mut self, variant: impl Into<EcoString>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Configure how many elements evaluation may produce by repeating or
+    /// joining content. Defaults to ten million.
+    pub fn with_max_elements(mut self, max_elements: usize) -> Self {
+        self.max_elements = Some(max_elements);
+        self
+    }
+
     /// Consumes the builder and returns a `Library`.
     pub fn build(self) -> Library {
         let math = math::module();
         let inputs = self.inputs.unwrap_or_default();
-        let global = global(math.clone(), inputs);
-        Library { global, math, styles: Styles::new() }
+        let global = global(math.clone(), inputs, self.variant);
+        let max_elements = self.max_elements.unwrap_or(10_000_000);
+        Library { global, math, styles: Styles::new(), max_elements }
     }
 }
 
 /// Construct the module with global definitions.
-fn global(math: Module, inputs: Dict) -> Module {
+fn global(math: Module, inputs: Dict, variant: Option<EcoString>) -> Module {
     let mut global = Scope::deduplicating();
-    self::foundations::define(&mut global, inputs);
+    self::foundations::define(&mut global, inputs, variant);
     self::model::define(&mut global);
     self::text::define(&mut global);
     global.reset_category();
@@ -0,0 +1,56 @@
+//! Base64 encoding.
+
+use ::base64::engine::general_purpose::STANDARD;
+use ::base64::Engine;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{At, SourceResult};
+use crate::foundations::{func, Bytes, Module, Scope};
+use crate::loading::Readable;
+use crate::syntax::Spanned;
+
+/// A module for converting binary data to and from Base64.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<encode>();
+    scope.define_func::<decode>();
+    Module::new("base64", scope)
+}
+
+/// Encodes a string or bytes as Base64.
+///
+/// Strings are encoded in UTF-8 first. The result uses the standard alphabet
+/// with padding, so it can directly be used in a data URI.
+///
+/// ```example
+/// #encoding.base64.encode("Hello") \
+/// #encoding.base64.encode(bytes((0, 255)))
+/// ```
+#[func(title = "Encode Base64")]
+pub fn encode(
+    /// The data to encode.
+    data: Readable,
+) -> EcoString {
+    STANDARD.encode(data.as_slice()).into()
+}
+
+/// Decodes Base64 into bytes.
+///
+/// Use the [`str`] constructor to turn the result into a string if it holds
+/// UTF-8 text.
+///
+/// ```example
+/// #str(encoding.base64.decode("SGVsbG8="))
+/// ```
+#[func(title = "Decode Base64")]
+pub fn decode(
+    /// The Base64 data to decode.
+    data: Spanned<Readable>,
+) -> SourceResult<Bytes> {
+    let Spanned { v: data, span } = data;
+    STANDARD
+        .decode(data.as_slice())
+        .map(Bytes::from)
+        .map_err(|err| eco_format!("failed to decode Base64 ({err})"))
+        .at(span)
+}
@@ -0,0 +1,32 @@
+//! Cryptographic hashes.
+
+use ecow::EcoString;
+use sha2::{Digest, Sha256};
+
+use crate::foundations::{func, Module, Scope};
+use crate::loading::{encode_hex, Readable};
+
+/// A module with cryptographic hash functions.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<sha256>();
+    Module::new("hash", scope)
+}
+
+/// Computes the SHA-256 hash of a string or bytes.
+///
+/// Strings are hashed in their UTF-8 encoding. The hash is returned as 64
+/// lowercase hexadecimal digits. Since it only changes when the data changes,
+/// it is well-suited for building identifiers for assets or verifying that a
+/// file has the expected contents.
+///
+/// ```example
+/// #encoding.hash.sha256("Typst")
+/// ```
+#[func(title = "SHA-256")]
+pub fn sha256(
+    /// The data to hash.
+    data: Readable,
+) -> EcoString {
+    encode_hex(&Sha256::digest(data.as_slice()))
+}
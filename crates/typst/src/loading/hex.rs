@@ -0,0 +1,77 @@
+//! Hexadecimal encoding.
+
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::foundations::{func, Bytes, Module, Scope};
+use crate::loading::Readable;
+use crate::syntax::Spanned;
+
+/// A module for converting binary data to and from hexadecimal.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<encode>();
+    scope.define_func::<decode>();
+    Module::new("hex", scope)
+}
+
+/// Encodes a string or bytes as lowercase hexadecimal digits, two per byte.
+///
+/// Strings are encoded in UTF-8 first.
+///
+/// ```example
+/// #encoding.hex.encode("Hi") \
+/// #encoding.hex.encode(bytes((0, 15, 255)))
+/// ```
+#[func(title = "Encode Hexadecimal")]
+pub fn encode(
+    /// The data to encode.
+    data: Readable,
+) -> EcoString {
+    encode_hex(data.as_slice())
+}
+
+/// Decodes hexadecimal digits into bytes.
+///
+/// Both uppercase and lowercase digits are accepted.
+///
+/// ```example
+/// #array(encoding.hex.decode("00ff7F"))
+/// ```
+#[func(title = "Decode Hexadecimal")]
+pub fn decode(
+    /// The hexadecimal digits to decode.
+    data: Spanned<Readable>,
+) -> SourceResult<Bytes> {
+    let Spanned { v: data, span } = data;
+    decode_hex(data.as_slice()).at(span)
+}
+
+/// Encode bytes as lowercase hexadecimal digits.
+pub(crate) fn encode_hex(data: &[u8]) -> EcoString {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = EcoString::with_capacity(2 * data.len());
+    for &byte in data {
+        out.push(DIGITS[usize::from(byte >> 4)] as char);
+        out.push(DIGITS[usize::from(byte & 0xf)] as char);
+    }
+    out
+}
+
+/// Decode pairs of hexadecimal digits into bytes.
+fn decode_hex(data: &[u8]) -> StrResult<Bytes> {
+    if data.len() % 2 != 0 {
+        bail!("failed to decode hexadecimal (odd number of digits)");
+    }
+
+    let digit = |c: u8| {
+        (c as char).to_digit(16).map(|d| d as u8).ok_or_else(|| {
+            eco_format!("failed to decode hexadecimal (invalid digit {:?})", c as char)
+        })
+    };
+
+    data.chunks_exact(2)
+        .map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?))
+        .collect::<StrResult<Vec<u8>>>()
+        .map(Bytes::from)
+}
@@ -1,5 +1,9 @@
 //! Data loading.
 
+mod base64;
+mod hash;
+mod hex;
+
 #[path = "cbor.rs"]
 mod cbor_;
 #[path = "csv.rs"]
@@ -23,7 +27,9 @@ pub use self::toml_::*;
 pub use self::xml_::*;
 pub use self::yaml_::*;
 
-use crate::foundations::{cast, category, Bytes, Category, Scope, Str};
+pub(crate) use self::hex::encode_hex;
+
+use crate::foundations::{cast, category, Bytes, Category, Module, Scope, Str};
 
 /// Data loading from external files.
 ///
@@ -42,6 +48,16 @@ pub(super) fn define(global: &mut Scope) {
     global.define_func::<yaml>();
     global.define_func::<cbor>();
     global.define_func::<xml>();
+    global.define_module(encoding());
+}
+
+/// A module for encoding and hashing binary data.
+fn encoding() -> Module {
+    let mut scope = Scope::new();
+    scope.define_module(base64::module());
+    scope.define_module(hex::module());
+    scope.define_module(hash::module());
+    Module::new("encoding", scope)
 }
 
 /// A value that can be read from a file.
@@ -54,7 +70,7 @@ pub enum Readable {
 }
 
 impl Readable {
-    fn as_slice(&self) -> &[u8] {
+    pub(crate) fn as_slice(&self) -> &[u8] {
         match self {
             Readable::Bytes(v) => v,
             Readable::Str(v) => v.as_bytes(),
@@ -289,7 +289,7 @@ impl<'a, 'b, 'v> MathContext<'a, 'b, 'v> {
         let par = ParElem::new(vec![text]);
         let frame = Packed::new(par)
             .spanned(span)
-            .layout(self.engine, styles, false, Size::splat(Abs::inf()), false)?
+            .layout(self.engine, styles, false, Size::splat(Abs::inf()), false, None)?
             .into_frame();
 
         Ok(FrameFragment::new(self, styles, frame)
@@ -180,6 +180,9 @@ impl ShowSet for Packed<EquationElem> {
             out.set(EquationElem::set_size(MathSize::Text));
         }
         out.set(TextElem::set_weight(FontWeight::from_number(450)));
+        // Math styles itself through the math alphanumerics instead of
+        // through font variants.
+        out.set(TextElem::set_synthesis(false));
         out.set(TextElem::set_font(FontList(vec![FontFamily::new(
             "New Computer Modern Math",
         )])));
@@ -1,3 +1,4 @@
+use comemo::Prehashed;
 use ecow::EcoString;
 
 use crate::diag::{bail, SourceResult, StrResult};
@@ -99,7 +100,13 @@ impl LayoutRoot for Packed<DocumentElem> {
                         .clear_to()?
                 });
                 let run = page.layout(engine, styles, &mut page_counter, extend_to)?;
-                pages.extend(run);
+                let observe = engine.tracer.observes_pages();
+                for page in run {
+                    if observe {
+                        engine.tracer.page(pages.len(), Prehashed::new(page.clone()));
+                    }
+                    pages.push(page);
+                }
             } else {
                 bail!(child.span(), "unexpected document child");
             }
@@ -7,15 +7,16 @@ use ecow::EcoString;
 use crate::diag::{bail, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    cast, elem, scope, select_where, Content, Element, NativeElement, Packed, Selector,
-    Show, ShowSet, Smart, StyleChain, Styles, Synthesize,
+    cast, elem, scope, select_where, Content, Element, NativeElement, Packed, Resolve,
+    Selector, Show, ShowSet, Smart, StyleChain, Styles, Synthesize,
 };
 use crate::introspection::{
     Count, Counter, CounterKey, CounterUpdate, Locatable, Location,
 };
 use crate::layout::{
-    Alignment, BlockElem, Em, HAlignment, Length, OuterVAlignment, PlaceElem, VAlignment,
-    VElem,
+    Abs, Alignment, Axes, BlockElem, Em, Fragment, Frame, FrameItem, HAlignment,
+    LayoutMultiple, Length, OuterVAlignment, PlaceElem, Point, Regions, Rel, Size,
+    VAlignment, VElem,
 };
 use crate::model::{Numbering, NumberingPattern, Outlinable, Refable, Supplement};
 use crate::text::{Lang, Region, TextElem};
@@ -199,6 +200,32 @@ pub struct FigureElem {
     #[default(Em::new(0.65).into())]
     pub gap: Length,
 
+    /// The width of the caption.
+    ///
+    /// - `{auto}`: The caption is as wide as the figure's body, so that a long
+    ///   caption wraps below the body instead of extending past it. If the
+    ///   body is so narrow that a word of the caption wouldn't fit, the caption
+    ///   uses the full width instead.
+    /// - A length or ratio: The caption has this width. Ratios are relative to
+    ///   the width of the figure's container, so `{100%}` lets the caption use
+    ///   all available space.
+    ///
+    /// Either way, the caption is centered relative to the body.
+    ///
+    /// ```example
+    /// #figure(
+    ///   rect(width: 100pt, height: 40pt),
+    ///   caption: [A caption that is too long for a single line.],
+    /// )
+    ///
+    /// #figure(
+    ///   rect(width: 100pt, height: 40pt),
+    ///   caption: [A caption that is too long for a single line.],
+    ///   caption-width: 100%,
+    /// )
+    /// ```
+    pub caption_width: Smart<Rel<Length>>,
+
     /// Whether the figure should appear in an [`outline`] of figures.
     #[default(true)]
     pub outlined: bool,
@@ -310,9 +337,17 @@ impl Show for Packed<FigureElem> {
         // Build the caption, if any.
         if let Some(caption) = self.caption(styles) {
             let v = VElem::weak(self.gap(styles).into()).pack();
-            realized = match caption.position(styles) {
-                OuterVAlignment::Top => caption.pack() + v + realized,
-                OuterVAlignment::Bottom => realized + v + caption.pack(),
+            let position = caption.position(styles);
+            let caption = FigureCaptionLayout::new(
+                caption.pack(),
+                self.body().clone(),
+                self.caption_width(styles),
+            )
+            .pack()
+            .spanned(self.span());
+            realized = match position {
+                OuterVAlignment::Top => caption + v + realized,
+                OuterVAlignment::Bottom => realized + v + caption,
             };
         }
 
@@ -586,6 +621,105 @@ cast! {
     v: Content => v.unpack::<Self>().unwrap_or_else(Self::new),
 }
 
+/// Lays out a figure's caption with a width that may depend on the figure's
+/// body.
+#[elem(LayoutMultiple)]
+struct FigureCaptionLayout {
+    /// The caption.
+    #[required]
+    caption: Content,
+
+    /// The figure's body.
+    #[required]
+    body: Content,
+
+    /// The width of the caption, or `{auto}` for the width of the body.
+    #[required]
+    width: Smart<Rel<Length>>,
+}
+
+impl LayoutMultiple for Packed<FigureCaptionLayout> {
+    #[typst_macros::time(name = "figure caption layout", span = self.span())]
+    fn layout(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Fragment> {
+        let base = regions.base();
+        let width = match self.width() {
+            Smart::Auto => {
+                let pod = Regions::one(base, Axes::splat(false));
+                let width =
+                    self.body().measure(engine, styles, pod)?.into_frame().width();
+
+                // A body that is narrower than some word of the caption would
+                // force that word to be broken. In that case, the caption is
+                // better off using the full width.
+                let pod = Regions::one(Size::new(Abs::inf(), base.y), Axes::splat(false));
+                let line = self.caption().measure(engine, styles, pod)?.into_frame();
+                if widest_word(&line) <= width {
+                    width
+                } else {
+                    Abs::zero()
+                }
+            }
+            Smart::Custom(width) => width.resolve(styles).relative_to(base.x),
+        };
+
+        // A body without any width, e.g. because it is empty, leaves the
+        // caption alone rather than squeezing it into a single column.
+        let mut pod = regions;
+        if width > Abs::zero() {
+            pod.size.x = width;
+        }
+
+        self.caption().layout(engine, styles, pod)
+    }
+}
+
+/// The width of the widest word in a frame that holds a single line of text.
+/// This is the narrowest width the text can be broken into without splitting
+/// any of its words.
+fn widest_word(frame: &Frame) -> Abs {
+    let mut glyphs = vec![];
+    collect_glyphs(frame, Point::zero(), &mut glyphs);
+    glyphs.sort_by_key(|&(x, ..)| x);
+
+    let mut widest = Abs::zero();
+    let mut start = None;
+    for (x, advance, space) in glyphs {
+        if space {
+            start = None;
+        } else {
+            let start = *start.get_or_insert(x);
+            widest.set_max(x + advance - start);
+        }
+    }
+    widest
+}
+
+/// Collect the horizontal position and advance of each glyph in a frame and
+/// whether it is whitespace.
+fn collect_glyphs(frame: &Frame, offset: Point, glyphs: &mut Vec<(Abs, Abs, bool)>) {
+    for (pos, item) in frame.items() {
+        let pos = offset + *pos;
+        match item {
+            FrameItem::Group(group) => collect_glyphs(&group.frame, pos, glyphs),
+            FrameItem::Text(text) => {
+                let mut x = pos.x;
+                for glyph in &text.glyphs {
+                    let advance = glyph.x_advance.at(text.size);
+                    let space = text.text[glyph.range()].chars().all(char::is_whitespace);
+                    glyphs.push((x, advance, space));
+                    x += advance;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// The `kind` parameter of a [`FigureElem`].
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum FigureKind {
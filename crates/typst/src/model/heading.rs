@@ -1,16 +1,18 @@
 use std::num::NonZeroUsize;
 
-use crate::diag::SourceResult;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::{warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, Content, NativeElement, Packed, Show, ShowSet, Smart, StyleChain, Styles,
-    Synthesize,
+    elem, Content, NativeElement, Packed, Resolve, Show, ShowSet, Smart, StyleChain,
+    Styles, Synthesize,
 };
 use crate::introspection::{Count, Counter, CounterUpdate, Locatable};
-use crate::layout::{BlockElem, Em, HElem, VElem};
+use crate::layout::{Abs, BlockElem, Em, HElem, Length, VElem};
 use crate::model::{Numbering, Outlinable, Refable, Supplement};
 use crate::text::{FontWeight, Lang, LocalName, Region, SpaceElem, TextElem, TextSize};
-use crate::util::{option_eq, NonZeroExt};
+use crate::util::{option_eq, NonZeroExt, Numeric};
 
 /// A section heading.
 ///
@@ -163,12 +165,86 @@ pub struct HeadingElem {
     #[default(Smart::Auto)]
     pub bookmarked: Smart<bool>,
 
+    /// The text sizes of the headings, starting with level one.
+    ///
+    /// The default of `{auto}` uses `{1.4em}` for the first level, `{1.2em}`
+    /// for the second level and `{1em}` for all other levels. Sizes in `em`
+    /// are relative to the surrounding text. When the array has no entry for
+    /// a heading's level or the entry is not positive, the default size for
+    /// that level is used and a warning is emitted. The same happens for a
+    /// size that mixes absolute and relative parts, like `{1em - 20pt}`,
+    /// since whether it is positive depends on the surrounding text.
+    ///
+    /// ```example
+    /// #set heading(sizes: (1.6em, 1.3em, 1.1em))
+    ///
+    /// = Chapter
+    /// == Section
+    /// === Subsection
+    /// ```
+    pub sizes: Smart<Vec<Length>>,
+
+    /// The spacing above the heading.
+    ///
+    /// The default of `{auto}` amounts to `{1.8em}` of the surrounding text
+    /// for first-level headings and `{1.44em}` for all other headings.
+    ///
+    /// ```example
+    /// #set heading(above: 2em, below: 0.5em)
+    ///
+    /// Some text.
+    /// = Heading
+    /// More text.
+    /// ```
+    pub above: Smart<Length>,
+
+    /// The spacing below the heading.
+    ///
+    /// The default of `{auto}` amounts to `{0.75em}` of the surrounding text.
+    pub below: Smart<Length>,
+
+    /// Whether the heading's first baseline snaps to the paragraph
+    /// [baseline grid]($par.baseline-grid), if one is set.
+    ///
+    /// ```example
+    /// #set par(baseline-grid: 14pt)
+    /// #show heading.where(level: 2): set heading(grid-snap: false)
+    ///
+    /// = On the grid
+    /// Text on the grid.
+    /// == Off the grid
+    /// More text on the grid.
+    /// ```
+    #[default(true)]
+    pub grid_snap: bool,
+
     /// The heading's title.
     #[required]
     pub body: Content,
 }
 
 impl HeadingElem {
+    /// The configured text size for the heading's level.
+    ///
+    /// Returns `Ok(None)` if no sizes are configured and an error message if
+    /// the configuration is invalid for this level.
+    fn configured_size(&self, styles: StyleChain) -> Result<Option<Length>, EcoString> {
+        let Smart::Custom(sizes) = self.sizes(styles) else { return Ok(None) };
+        let level = self.resolve_level(styles).get();
+        let Some(&size) = sizes.get(level - 1) else {
+            return Err(eco_format!("heading sizes have no entry for level {level}"));
+        };
+        if !size.abs.is_zero() && !size.em.is_zero() {
+            return Err(eco_format!(
+                "heading size for level {level} cannot mix absolute and relative lengths"
+            ));
+        }
+        if size.abs < Abs::zero() || size.em < Em::zero() || size.is_zero() {
+            return Err(eco_format!("heading size for level {level} must be positive"));
+        }
+        Ok(Some(size))
+    }
+
     pub fn resolve_level(&self, styles: StyleChain) -> NonZeroUsize {
         self.level(styles).unwrap_or_else(|| {
             NonZeroUsize::new(self.offset(styles) + self.depth(styles).get())
@@ -202,6 +278,13 @@ impl Show for Packed<HeadingElem> {
     #[typst_macros::time(name = "heading", span = self.span())]
     fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
         let span = self.span();
+        if let Err(message) = (**self).configured_size(styles) {
+            engine.tracer.warn(warning!(
+                span, "{}", message;
+                hint: "using the default size for this level"
+            ));
+        }
+
         let mut realized = self.body().clone();
         if let Some(numbering) = (**self).numbering(styles).as_ref() {
             realized = Counter::of(HeadingElem::elem())
@@ -223,16 +306,32 @@ impl ShowSet for Packed<HeadingElem> {
             _ => 1.0,
         };
 
-        let size = Em::new(scale);
-        let above = Em::new(if level == 1 { 1.8 } else { 1.44 }) / scale;
-        let below = Em::new(0.75) / scale;
+        let above = Em::new(if level == 1 { 1.8 } else { 1.44 });
+        let below = Em::new(0.75);
+
+        // The default spacing is relative to the surrounding text. With the
+        // default sizes, it can be expressed relative to the heading's own
+        // size. With custom sizes, it is resolved against the surrounding
+        // text.
+        let (size, above, below): (Length, Length, Length) = match (**self)
+            .configured_size(styles)
+        {
+            Ok(Some(size)) => {
+                (size, above.resolve(styles).into(), below.resolve(styles).into())
+            }
+            _ => (Em::new(scale).into(), (above / scale).into(), (below / scale).into()),
+        };
+
+        let above = (**self).above(styles).unwrap_or(above);
+        let below = (**self).below(styles).unwrap_or(below);
 
         let mut out = Styles::new();
-        out.set(TextElem::set_size(TextSize(size.into())));
+        out.set(TextElem::set_size(TextSize(size)));
         out.set(TextElem::set_weight(FontWeight::BOLD));
         out.set(BlockElem::set_above(VElem::block_around(above.into())));
         out.set(BlockElem::set_below(VElem::block_around(below.into())));
         out.set(BlockElem::set_sticky(true));
+        out.set(BlockElem::set_grid_snap((**self).grid_snap(styles)));
         out
     }
 }
@@ -0,0 +1,193 @@
+//! Back-of-book index generation.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use comemo::Tracked;
+use ecow::EcoString;
+
+use crate::diag::{At, SourceResult};
+use crate::engine::Engine;
+use crate::foundations::{
+    elem, func, Content, Context, Module, NativeElement, Packed, Scope, Show, StyleChain,
+};
+use crate::introspection::{Counter, CounterKey, Locatable, Location};
+use crate::model::{ListElem, ListItem, NumberingPattern};
+use crate::syntax::Span;
+use crate::text::TextElem;
+
+/// A module for generating a back-of-book index.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_elem::<IndexEntryElem>();
+    scope.define_func::<build>();
+    Module::new("index", scope)
+}
+
+/// Marks the current position as an occurrence of a term in the index.
+///
+/// The entry itself is invisible. Its page number is listed next to the term
+/// once the index is [built]($meta.index.build).
+///
+/// ```example
+/// #meta.index.entry("fonts", "fallback")
+/// If a font lacks a glyph, the next
+/// font in the list is tried.
+///
+/// #meta.index.build()
+/// ```
+#[elem(name = "entry", title = "Index Entry", Locatable, Show)]
+pub struct IndexEntryElem {
+    /// The term under which the entry is listed.
+    #[required]
+    pub term: EcoString,
+
+    /// An optional sub-term, listed below the term.
+    #[positional]
+    pub sub: Option<EcoString>,
+}
+
+impl Show for Packed<IndexEntryElem> {
+    fn show(&self, _: &mut Engine, _: StyleChain) -> SourceResult<Content> {
+        Ok(Content::empty())
+    }
+}
+
+/// Builds the index from all entries in the document.
+///
+/// The index is a two-level [list]($list) with one item per term and nested
+/// items for its sub-terms. Each term is followed by the pages it occurs on.
+/// Consecutive pages are collapsed into ranges and multiple entries on one
+/// page are listed once.
+///
+/// Terms are sorted alphabetically without regard to case. Terms that only
+/// differ in case are ordered by their code points, which puts uppercase
+/// before lowercase.
+///
+/// ```example
+/// #set page(height: 80pt)
+/// #meta.index.entry("Typst")
+/// #meta.index.entry("typesetting")
+/// Typst is a typesetting system.
+/// #pagebreak()
+/// #meta.index.entry("typesetting")
+/// #meta.index.entry("fonts", "fallback")
+/// It supports font fallback.
+///
+/// #meta.index.build()
+/// ```
+#[func(contextual)]
+pub fn build(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite context.
+    context: Tracked<Context>,
+    /// The callsite span.
+    span: Span,
+) -> SourceResult<Content> {
+    context.introspect().at(span)?;
+    let styles = context.styles().at(span)?;
+
+    let mut terms = BTreeMap::<SortKey, Term>::new();
+    for elem in engine.introspector.query(&IndexEntryElem::elem().select()) {
+        let entry = elem.to_packed::<IndexEntryElem>().unwrap();
+        let location = elem.location().unwrap();
+        let page = Counter::new(CounterKey::Page).at_loc(engine, location)?.first();
+        let term = terms.entry(SortKey::new(entry.term())).or_default();
+        let pages = match entry.sub(styles) {
+            Some(sub) => term.subs.entry(SortKey::new(&sub)).or_default(),
+            None => &mut term.pages,
+        };
+        pages.entry(page).or_insert(location);
+    }
+
+    let mut items = vec![];
+    for (key, term) in terms {
+        let mut body = line(engine, styles, key.1, &term.pages)?;
+        if !term.subs.is_empty() {
+            let mut subs = vec![];
+            for (key, pages) in term.subs {
+                let line = line(engine, styles, key.1, &pages)?;
+                subs.push(Packed::new(ListItem::new(line)).spanned(span));
+            }
+            body += ListElem::new(subs).pack().spanned(span);
+        }
+        items.push(Packed::new(ListItem::new(body)).spanned(span));
+    }
+
+    Ok(ListElem::new(items).pack().spanned(span))
+}
+
+/// A term in the index.
+#[derive(Default)]
+struct Term {
+    /// The pages the term occurs on directly, with the first location on each.
+    pages: Pages,
+    /// The term's sub-terms.
+    subs: BTreeMap<SortKey, Pages>,
+}
+
+/// Logical page numbers with the first location on each page.
+type Pages = BTreeMap<usize, Location>;
+
+/// Sorts terms case-insensitively, breaking ties by code points.
+#[derive(Eq, PartialEq, Ord, PartialOrd)]
+struct SortKey(EcoString, EcoString);
+
+impl SortKey {
+    fn new(term: &EcoString) -> Self {
+        Self(term.to_lowercase(), term.clone())
+    }
+}
+
+/// Produce a term followed by its collapsed page ranges, like `fonts, 3–5, 9`.
+fn line(
+    engine: &mut Engine,
+    styles: StyleChain,
+    term: EcoString,
+    pages: &Pages,
+) -> SourceResult<Content> {
+    let mut seq = vec![TextElem::packed(term)];
+    for (start, end) in ranges(pages) {
+        seq.push(TextElem::packed(", "));
+        seq.push(page(engine, styles, start)?);
+        if let Some(end) = end {
+            seq.push(TextElem::packed("–"));
+            seq.push(page(engine, styles, end)?);
+        }
+    }
+    Ok(Content::sequence(seq))
+}
+
+/// Collapse runs of consecutive pages into ranges.
+///
+/// Returns the location on the first page of each range and, if the range
+/// spans multiple pages, on its last page.
+fn ranges(pages: &Pages) -> Vec<(Location, Option<Location>)> {
+    let mut ranges: Vec<(Location, Option<Location>)> = vec![];
+    let mut last = None;
+    for (&page, &location) in pages {
+        match ranges.last_mut() {
+            Some((_, end)) if last.is_some_and(|last| last + 1 == page) => {
+                *end = Some(location);
+            }
+            _ => ranges.push((location, None)),
+        }
+        last = Some(page);
+    }
+    ranges
+}
+
+/// Display the page number at a location with the page's numbering.
+fn page(
+    engine: &mut Engine,
+    styles: StyleChain,
+    location: Location,
+) -> SourceResult<Content> {
+    let numbering = engine
+        .introspector
+        .page_numbering(location)
+        .cloned()
+        .unwrap_or_else(|| NumberingPattern::from_str("1").unwrap().into());
+    Counter::new(CounterKey::Page).display_at_loc(engine, location, styles, &numbering)
+}
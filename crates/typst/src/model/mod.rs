@@ -9,14 +9,17 @@ mod enum_;
 mod figure;
 mod footnote;
 mod heading;
+mod index;
 mod link;
 mod list;
 #[path = "numbering.rs"]
 mod numbering_;
 mod outline;
 mod par;
+mod pdf;
 mod quote;
 mod reference;
+mod resources;
 mod strong;
 mod table;
 mod terms;
@@ -29,18 +32,21 @@ pub use self::enum_::*;
 pub use self::figure::*;
 pub use self::footnote::*;
 pub use self::heading::*;
+pub use self::index::IndexEntryElem;
 pub use self::link::*;
 pub use self::list::*;
 pub use self::numbering_::*;
 pub use self::outline::*;
 pub use self::par::*;
+pub use self::pdf::{PdfMarker, PdfNote};
 pub use self::quote::*;
 pub use self::reference::*;
+pub use self::resources::*;
 pub use self::strong::*;
 pub use self::table::*;
 pub use self::terms::*;
 
-use crate::foundations::{category, Category, Scope};
+use crate::foundations::{category, Category, Module, Scope};
 
 /// Document structuring.
 ///
@@ -72,4 +78,14 @@ pub fn define(global: &mut Scope) {
     global.define_elem::<EmphElem>();
     global.define_elem::<StrongElem>();
     global.define_func::<numbering>();
+    global.define_module(meta());
+}
+
+/// A module for document metadata that isn't part of the visible content:
+/// index entries and PDF-specific markers.
+fn meta() -> Module {
+    let mut scope = Scope::new();
+    scope.define_module(index::module());
+    scope.define_module(pdf::module());
+    Module::new("meta", scope)
 }
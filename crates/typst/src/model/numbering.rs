@@ -4,10 +4,10 @@ use chinese_number::{ChineseCase, ChineseCountMethod, ChineseVariant, NumberToCh
 use comemo::Tracked;
 use ecow::{eco_format, EcoString, EcoVec};
 
-use crate::diag::SourceResult;
+use crate::diag::{bail, SourceResult, StrResult};
 use crate::engine::Engine;
-use crate::foundations::{cast, func, Context, Func, Str, Value};
-use crate::text::Case;
+use crate::foundations::{cast, func, scope, Context, Func, Repr, Str, Value};
+use crate::text::{Case, Lang};
 
 /// Applies a numbering to a sequence of numbers.
 ///
@@ -32,7 +32,7 @@ use crate::text::Case;
 ///   1, 2, 3,
 /// )
 /// ```
-#[func]
+#[func(scope)]
 pub fn numbering(
     /// The engine.
     engine: &mut Engine,
@@ -72,6 +72,97 @@ pub fn numbering(
     numbering.apply(engine, context, &numbers)
 }
 
+#[scope]
+impl numbering {
+    /// Converts a number into a roman numeral.
+    ///
+    /// Numbers from 4000 on are written with overlines that multiply a numeral
+    /// by one thousand, just like with the `{"I"}` numbering pattern. This
+    /// works up to 3 999 999. Larger numbers and numbers below one have no
+    /// roman numeral and result in an error.
+    ///
+    /// ```example
+    /// #numbering.roman(1998) \
+    /// #numbering.roman(1998, lower: true) \
+    /// #numbering.roman(12000)
+    /// ```
+    #[func]
+    pub fn roman(
+        /// The number to convert.
+        number: i64,
+        /// Whether to use lowercase letters.
+        #[named]
+        #[default(false)]
+        lower: bool,
+    ) -> StrResult<Str> {
+        let max = 3999999;
+        if number < 1 {
+            bail!("roman numerals only exist for positive numbers");
+        } else if number > max {
+            bail!("number is too large for a roman numeral (maximum is {max})");
+        }
+
+        let case = if lower { Case::Lower } else { Case::Upper };
+        Ok(roman(number as usize, case).into())
+    }
+
+    /// Converts a roman numeral back into a number.
+    ///
+    /// The numeral may be uppercase or lowercase, but must be written in
+    /// standard form, e.g. `{"IV"}` instead of `{"IIII"}`.
+    ///
+    /// ```example
+    /// #numbering.from-roman("MCMXCVIII") \
+    /// #numbering.from-roman("xiv")
+    /// ```
+    #[func]
+    pub fn from_roman(
+        /// The roman numeral to convert.
+        numeral: Str,
+    ) -> StrResult<i64> {
+        let upper = numeral.to_uppercase();
+        let mut rest = upper.as_str();
+        let mut number = 0;
+        for &(name, value) in ROMAN {
+            while let Some(tail) = rest.strip_prefix(name) {
+                rest = tail;
+                number += value;
+            }
+        }
+
+        if !rest.is_empty() || number == 0 || roman(number, Case::Upper) != upper {
+            bail!("{} is not a valid roman numeral", numeral.repr());
+        }
+
+        Ok(number as i64)
+    }
+
+    /// Spells out a number in words.
+    ///
+    /// Currently, only English is supported. Numbers are spelled without
+    /// "and" between hundreds and tens, as is common in American English.
+    ///
+    /// ```example
+    /// #numbering.spell(42) \
+    /// #numbering.spell(1998) \
+    /// #numbering.spell(-7)
+    /// ```
+    #[func]
+    pub fn spell(
+        /// The number to spell out.
+        number: i64,
+        /// The language to spell the number in.
+        #[named]
+        #[default(Lang::ENGLISH)]
+        lang: Lang,
+    ) -> StrResult<Str> {
+        match lang {
+            Lang::ENGLISH => Ok(spell_english(number).into()),
+            _ => bail!("cannot spell out numbers in language {}", lang.as_str()),
+        }
+    }
+}
+
 /// How to number a sequence of things.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum Numbering {
@@ -369,43 +460,7 @@ impl NumberingKind {
                     return 'N'.into();
                 }
 
-                // Adapted from Yann Villessuzanne's roman.rs under the
-                // Unlicense, at https://github.com/linfir/roman.rs/
-                let mut fmt = EcoString::new();
-                for &(name, value) in &[
-                    ("M̅", 1000000),
-                    ("D̅", 500000),
-                    ("C̅", 100000),
-                    ("L̅", 50000),
-                    ("X̅", 10000),
-                    ("V̅", 5000),
-                    ("I̅V̅", 4000),
-                    ("M", 1000),
-                    ("CM", 900),
-                    ("D", 500),
-                    ("CD", 400),
-                    ("C", 100),
-                    ("XC", 90),
-                    ("L", 50),
-                    ("XL", 40),
-                    ("X", 10),
-                    ("IX", 9),
-                    ("V", 5),
-                    ("IV", 4),
-                    ("I", 1),
-                ] {
-                    while n >= value {
-                        n -= value;
-                        for c in name.chars() {
-                            match case {
-                                Case::Lower => fmt.extend(c.to_lowercase()),
-                                Case::Upper => fmt.push(c),
-                            }
-                        }
-                    }
-                }
-
-                fmt
+                roman(n, case)
             }
             Self::Symbol => {
                 if n == 0 {
@@ -550,3 +605,121 @@ fn zeroless<const N_DIGITS: usize>(
     }
     cs.into_iter().rev().collect()
 }
+
+/// Roman numerals and their values, from largest to smallest.
+///
+/// Numerals with an overline (vinculum) are multiplied by one thousand.
+const ROMAN: &[(&str, usize)] = &[
+    ("M̅", 1000000),
+    ("C̅M̅", 900000),
+    ("D̅", 500000),
+    ("C̅D̅", 400000),
+    ("C̅", 100000),
+    ("X̅C̅", 90000),
+    ("L̅", 50000),
+    ("X̅L̅", 40000),
+    ("X̅", 10000),
+    ("I̅X̅", 9000),
+    ("V̅", 5000),
+    ("I̅V̅", 4000),
+    ("M", 1000),
+    ("CM", 900),
+    ("D", 500),
+    ("CD", 400),
+    ("C", 100),
+    ("XC", 90),
+    ("L", 50),
+    ("XL", 40),
+    ("X", 10),
+    ("IX", 9),
+    ("V", 5),
+    ("IV", 4),
+    ("I", 1),
+];
+
+/// Write a positive number as a roman numeral.
+fn roman(mut n: usize, case: Case) -> EcoString {
+    // Adapted from Yann Villessuzanne's roman.rs under the
+    // Unlicense, at https://github.com/linfir/roman.rs/
+    let mut fmt = EcoString::new();
+    for &(name, value) in ROMAN {
+        while n >= value {
+            n -= value;
+            for c in name.chars() {
+                match case {
+                    Case::Lower => fmt.extend(c.to_lowercase()),
+                    Case::Upper => fmt.push(c),
+                }
+            }
+        }
+    }
+    fmt
+}
+
+/// Spell out a number in English words.
+fn spell_english(n: i64) -> EcoString {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty",
+        "ninety",
+    ];
+    const SCALES: [&str; 7] =
+        ["", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion"];
+
+    if n == 0 {
+        return ONES[0].into();
+    }
+
+    let mut groups = vec![];
+    let mut rest = n.unsigned_abs();
+    for scale in SCALES {
+        let group = (rest % 1000) as usize;
+        rest /= 1000;
+        if group == 0 {
+            continue;
+        }
+
+        let mut words = vec![];
+        if group >= 100 {
+            words.push(eco_format!("{} hundred", ONES[group / 100]));
+        }
+        match group % 100 {
+            0 => {}
+            tail @ 1..=19 => words.push(ONES[tail].into()),
+            tail if tail % 10 == 0 => words.push(TENS[tail / 10].into()),
+            tail => words.push(eco_format!("{}-{}", TENS[tail / 10], ONES[tail % 10])),
+        }
+        if !scale.is_empty() {
+            words.push(scale.into());
+        }
+        groups.push(words.join(" "));
+    }
+
+    if n < 0 {
+        groups.push("minus".into());
+    }
+
+    groups.reverse();
+    groups.join(" ").into()
+}
@@ -54,6 +54,9 @@ use crate::util::{option_eq, NonZeroExt};
 /// )
 /// ```
 ///
+/// When no element matches the target, the outline is left out entirely,
+/// including its title.
+///
 /// # Styling the outline
 /// The outline element has several options for customization, such as its
 /// `title` and `indent` parameters. If desired, however, it is possible to have
@@ -210,6 +213,7 @@ impl Show for Packed<OutlineElem> {
 
         let mut ancestors: Vec<&Content> = vec![];
         let elems = engine.introspector.query(&self.target(styles).0);
+        let mut empty = true;
 
         for elem in &elems {
             let Some(entry) = OutlineEntry::from_outlinable(
@@ -252,6 +256,12 @@ impl Show for Packed<OutlineElem> {
             seq.push(LinebreakElem::new().pack());
 
             ancestors.push(elem);
+            empty = false;
+        }
+
+        // Without any entries, there is nothing to title.
+        if empty {
+            return Ok(Content::empty());
         }
 
         seq.push(ParbreakElem::new().pack());
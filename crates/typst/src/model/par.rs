@@ -1,12 +1,19 @@
 use std::fmt::{self, Debug, Formatter};
 
-use crate::diag::SourceResult;
+use comemo::{Tracked, TrackedMut};
+
+use crate::diag::{At, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
-    elem, Args, Cast, Construct, Content, NativeElement, Packed, Set, Smart, StyleChain,
-    Unlabellable,
+    elem, func, scope, Args, Cast, Construct, Content, Context, NativeElement, Packed,
+    Resolve, Set, Smart, StyleChain, StyledElem, Unlabellable,
+};
+use crate::introspection::{Locator, MetaElem};
+use crate::layout::{
+    Abs, Axes, Em, FlowElem, Fragment, LayoutMultiple, Length, Regions, Size, VElem,
 };
-use crate::layout::{Em, Fragment, Length, Size};
+use crate::realize::{realize_block, Arenas};
+use crate::syntax::Span;
 
 /// Arranges text, spacing and inline-level elements into a paragraph.
 ///
@@ -33,7 +40,25 @@ use crate::layout::{Em, Fragment, Length, Size};
 /// let $a$ be the smallest of the
 /// three integers. Then, we ...
 /// ```
-#[elem(title = "Paragraph", Debug, Construct)]
+///
+/// # Fitting content
+/// The [`par.line-count`]($par.line-count) and [`par.fits`]($par.fits)
+/// functions lay out content in the current [context] without placing it into
+/// the document. This lets a template adapt content to the available space,
+/// for instance by shrinking the font until a text fits into a box:
+///
+/// ```example
+/// #let fitted(body, width, height) = context {
+///   let size = text.size
+///   while size > 4pt and not par.fits(text(size, body), width, height) {
+///     size -= 0.5pt
+///   }
+///   box(width: width, height: height, stroke: 0.5pt, text(size, body))
+/// }
+///
+/// #fitted(lorem(20), 120pt, 40pt)
+/// ```
+#[elem(scope, title = "Paragraph", Debug, Construct)]
 pub struct ParElem {
     /// The spacing between lines.
     #[resolve]
@@ -78,6 +103,26 @@ pub struct ParElem {
     #[ghost]
     pub linebreaks: Smart<Linebreaks>,
 
+    /// Whether to break words that are too long to fit into a line.
+    ///
+    /// When enabled, a word that doesn't fit into the width of the paragraph
+    /// even on a line of its own is broken between two arbitrary characters
+    /// as a last resort and a warning points at the first word broken this
+    /// way. This is useful for long identifiers, URLs without break
+    /// opportunities, or DNA sequences in narrow columns. Words are never
+    /// broken while the paragraph is only measured, for example to determine
+    /// the width of an auto-sized grid column.
+    ///
+    /// ```example
+    /// #set page(width: 100pt)
+    /// #set par(break-words: true)
+    /// #let dna = range(12).map(_ => "ACGT").join()
+    /// The sequence #dna ends here.
+    /// ```
+    #[ghost]
+    #[default(false)]
+    pub break_words: bool,
+
     /// The indent the first line of a paragraph should have.
     ///
     /// Only the first line of a consecutive paragraph will be indented (not
@@ -96,6 +141,37 @@ pub struct ParElem {
     #[resolve]
     pub hanging_indent: Length,
 
+    /// A grid that the baselines of lines snap to.
+    ///
+    /// When set, each line of a paragraph in the page's flow is moved down so
+    /// that its baseline lands on the next multiple of this distance, counted
+    /// from the top of the page's content area or column. This keeps lines
+    /// in neighbouring columns and on facing pages aligned even if images,
+    /// equations, or headings of other sizes interrupt the text. Headings
+    /// snap their first baseline to the grid, too, unless their
+    /// [`grid-snap`]($heading.grid-snap) property is disabled.
+    ///
+    /// Only paragraphs directly in the page's flow snap. Paragraphs nested in
+    /// other block-level elements, like lists, quotes, or table cells, keep
+    /// their regular line spacing because their position relative to the grid
+    /// is only known once the surrounding element is laid out. Paragraphs
+    /// after such an element snap back onto the grid.
+    ///
+    /// ```example
+    /// #set page(columns: 2, height: 100pt)
+    /// #set par(baseline-grid: 14pt)
+    ///
+    /// = Heading
+    /// #lorem(5)
+    ///
+    /// #colbreak()
+    /// #text(1.4em, lorem(2))
+    /// #lorem(5)
+    /// ```
+    #[ghost]
+    #[resolve]
+    pub baseline_grid: Option<Length>,
+
     /// The contents of the paragraph.
     #[external]
     #[required]
@@ -107,6 +183,114 @@ pub struct ParElem {
     pub children: Vec<Content>,
 }
 
+#[scope]
+impl ParElem {
+    /// Counts the lines into which the paragraphs in the content break at the
+    /// given width.
+    ///
+    /// The content is laid out with the styles of the current context, so
+    /// this function can only be used within [context]. Lines of paragraphs
+    /// nested in other block-level elements are not counted.
+    ///
+    /// ```example
+    /// #let body = lorem(12)
+    /// #context par.line-count(body, 80pt) \
+    /// #context par.line-count(body, 200pt)
+    /// ```
+    #[func(contextual)]
+    pub fn line_count(
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The callsite span.
+        span: Span,
+        /// The content whose lines to count.
+        body: Content,
+        /// The width available to the paragraphs.
+        width: Length,
+    ) -> SourceResult<usize> {
+        let styles = context.styles().at(span)?;
+        let width = width.resolve(styles);
+
+        // Realize the content into a flow without affecting the document's
+        // introspection state, just like measuring does.
+        let mut locator = Locator::chained(engine.locator.track());
+        let mut engine = Engine {
+            world: engine.world,
+            route: engine.route.clone(),
+            introspector: engine.introspector,
+            locator: &mut locator,
+            tracer: TrackedMut::reborrow_mut(&mut engine.tracer),
+        };
+
+        let arenas = Arenas::default();
+        let (realized, outer) = realize_block(&mut engine, &arenas, &body, styles)?;
+        let Some(flow) = realized.to_packed::<FlowElem>() else {
+            return Ok(0);
+        };
+
+        let mut count = 0;
+        let mut consecutive = false;
+        for mut child in flow.children().iter() {
+            let mut styles = outer;
+            if let Some(styled) = child.to_packed::<StyledElem>() {
+                child = &styled.child;
+                styles = outer.chain(&styled.styles);
+            }
+
+            if let Some(par) = child.to_packed::<ParElem>() {
+                count += crate::layout::count_lines(
+                    par.children(),
+                    &mut engine,
+                    styles,
+                    consecutive,
+                    width,
+                )?;
+                consecutive = true;
+            } else if !child.is::<MetaElem>() && !child.is::<VElem>() {
+                consecutive = false;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Checks whether the content fits into a box of the given size.
+    ///
+    /// The content is laid out with the styles of the current context at the
+    /// given width. It fits if the result is neither wider nor taller than the
+    /// given size. Like [`measure`], this function can only be used within
+    /// [context].
+    ///
+    /// ```example
+    /// #let body = lorem(20)
+    /// #context par.fits(body, 150pt, 40pt) \
+    /// #context par.fits(body, 150pt, 200pt)
+    /// ```
+    #[func(contextual)]
+    pub fn fits(
+        /// The engine.
+        engine: &mut Engine,
+        /// The callsite context.
+        context: Tracked<Context>,
+        /// The callsite span.
+        span: Span,
+        /// The content to fit.
+        body: Content,
+        /// The width of the box.
+        width: Length,
+        /// The height of the box.
+        height: Length,
+    ) -> SourceResult<bool> {
+        let styles = context.styles().at(span)?;
+        let size = Size::new(width.resolve(styles), height.resolve(styles));
+        let regions = Regions::one(Size::new(size.x, Abs::inf()), Axes::splat(false));
+        let frame = body.measure(engine, styles, regions)?.into_frame();
+        Ok(size.x.fits(frame.width()) && size.y.fits(frame.height()))
+    }
+}
+
 impl Construct for ParElem {
     fn construct(engine: &mut Engine, args: &mut Args) -> SourceResult<Content> {
         // The paragraph constructor is special: It doesn't create a paragraph
@@ -124,6 +308,9 @@ impl Construct for ParElem {
 
 impl Packed<ParElem> {
     /// Layout the paragraph into a collection of lines.
+    ///
+    /// If a baseline `grid` is given, lines that are kept together in one
+    /// frame are spaced such that their baselines are a multiple of it apart.
     #[typst_macros::time(name = "par", span = self.span())]
     pub fn layout(
         &self,
@@ -132,6 +319,7 @@ impl Packed<ParElem> {
         consecutive: bool,
         region: Size,
         expand: bool,
+        grid: Option<Abs>,
     ) -> SourceResult<Fragment> {
         crate::layout::layout_inline(
             self.children(),
@@ -140,6 +328,7 @@ impl Packed<ParElem> {
             consecutive,
             region,
             expand,
+            grid,
         )
     }
 }
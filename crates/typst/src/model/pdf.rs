@@ -0,0 +1,122 @@
+//! PDF-specific functionality.
+
+use ecow::EcoString;
+use smallvec::smallvec;
+
+use crate::foundations::{func, Content, Datetime, Module, NativeElement, Scope};
+use crate::introspection::{Meta, MetaElem};
+use crate::syntax::Span;
+
+/// A module with PDF-specific definitions.
+pub fn module() -> Module {
+    let mut scope = Scope::new();
+    scope.define_func::<dest>();
+    scope.define_func::<comment>();
+    scope.define_func::<note>();
+    Module::new("pdf", scope)
+}
+
+/// Data that is only meaningful to the PDF exporter. Other exporters ignore
+/// it.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum PdfMarker {
+    /// A named destination at the marker's position.
+    Dest(EcoString),
+    /// A comment in the page's content stream.
+    Comment(EcoString),
+    /// A note for the reader, shown by viewers as a sticky note at the
+    /// marker's position.
+    Note(PdfNote),
+}
+
+/// A note that is exported as a PDF text annotation.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct PdfNote {
+    /// The text of the note.
+    pub text: EcoString,
+    /// Who wrote the note.
+    pub author: Option<EcoString>,
+    /// When the note was written.
+    pub date: Option<Datetime>,
+}
+
+/// Creates a named destination at the current position.
+///
+/// Named destinations are written to the exported PDF's name dictionary and
+/// allow other documents to link to a specific place in this one, for
+/// instance with a URL ending in `#name`. Labelled headings automatically
+/// become named destinations, so this is only needed for other places.
+///
+/// When multiple destinations share a name, the first one is used. Other
+/// exporters ignore named destinations.
+///
+/// ```example
+/// #meta.pdf.dest("results")
+/// The results are in.
+/// ```
+#[func]
+pub fn dest(
+    /// The callsite span.
+    span: Span,
+    /// The name of the destination.
+    name: EcoString,
+) -> Content {
+    marker(PdfMarker::Dest(name), span)
+}
+
+/// Writes a comment into the content stream of the exported PDF.
+///
+/// This is useful for finding a particular place when debugging the PDF's
+/// content streams. The comment is written as a marked-content point with the
+/// tag `/Comment` and the text in its `/Text` property. It is ignored by
+/// layout and by other exporters.
+///
+/// ```example
+/// #meta.pdf.comment("start of table")
+/// #table(columns: 2)[A][B]
+/// ```
+#[func]
+pub fn comment(
+    /// The callsite span.
+    span: Span,
+    /// The text of the comment.
+    text: EcoString,
+) -> Content {
+    marker(PdfMarker::Comment(text), span)
+}
+
+/// Attaches a note to the current position in the exported PDF.
+///
+/// PDF viewers show the note as a sticky note icon that reveals its text when
+/// clicked, which is handy for leaving remarks for reviewers. The note takes
+/// up no space and doesn't change the layout, but its icon is printed along
+/// with the page. Other exporters ignore notes, and the CLI's `--strip-notes`
+/// flag removes them from the final PDF.
+///
+/// ```example
+/// #meta.pdf.note("Check this figure", author: "AB")
+/// The results are in.
+/// ```
+#[func]
+pub fn note(
+    /// The callsite span.
+    span: Span,
+    /// The text of the note.
+    text: EcoString,
+    /// Who wrote the note.
+    #[named]
+    author: Option<EcoString>,
+    /// When the note was written.
+    #[named]
+    date: Option<Datetime>,
+) -> Content {
+    marker(PdfMarker::Note(PdfNote { text, author, date }), span)
+}
+
+/// Create invisible content that places a PDF marker at its position.
+fn marker(marker: PdfMarker, span: Span) -> Content {
+    MetaElem::new()
+        .pack()
+        .spanned(span)
+        .styled(MetaElem::set_data(smallvec![Meta::Pdf(marker)]))
+}
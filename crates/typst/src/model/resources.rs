@@ -0,0 +1,205 @@
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroUsize;
+
+use ecow::EcoVec;
+
+use crate::diag::{warning, SourceDiagnostic};
+use crate::layout::{Abs, Frame, FrameItem, Size, Transform};
+use crate::model::Document;
+use crate::syntax::Span;
+use crate::text::Font;
+use crate::visualize::{Image, ImageFormat, Paint};
+
+/// The fonts and images that a laid out document uses.
+///
+/// This is what an exporter has to embed. It is collected from the finished
+/// frames, so fonts that were loaded but never used for a glyph don't show up.
+#[derive(Debug, Default, Clone)]
+pub struct Resources {
+    /// The fonts in order of their first use.
+    pub fonts: Vec<FontUsage>,
+    /// Each placement of an image in document order.
+    pub images: Vec<ImagePlacement>,
+}
+
+/// A font that is used in a document.
+#[derive(Debug, Clone)]
+pub struct FontUsage {
+    /// The font.
+    pub font: Font,
+    /// The number of distinct glyphs used from the font.
+    pub glyphs: usize,
+}
+
+/// An image that is placed somewhere in a document.
+#[derive(Debug, Clone)]
+pub struct ImagePlacement {
+    /// The image.
+    pub image: Image,
+    /// The physical page number the image is placed on.
+    pub page: NonZeroUsize,
+    /// The size the image is placed at, including any scaling applied to it.
+    pub size: Size,
+    /// The span of the element that placed the image.
+    pub span: Span,
+}
+
+impl ImagePlacement {
+    /// The effective resolution of a raster image at its placed size in dots
+    /// per inch.
+    ///
+    /// If the image is stretched unevenly, this is the lower of the horizontal
+    /// and vertical resolutions. Returns `None` for vector images and images
+    /// that aren't visible.
+    pub fn dpi(&self) -> Option<f64> {
+        if matches!(self.image.format(), ImageFormat::Vector(_))
+            || self.size.x <= Abs::zero()
+            || self.size.y <= Abs::zero()
+        {
+            return None;
+        }
+
+        let x = self.image.width() / self.size.x.to_inches();
+        let y = self.image.height() / self.size.y.to_inches();
+        Some(x.min(y))
+    }
+}
+
+impl Resources {
+    /// Collect the resources used by a document.
+    #[typst_macros::time(name = "resources")]
+    pub fn new(document: &Document) -> Self {
+        let mut collector = Collector::default();
+        for (i, page) in document.pages.iter().enumerate() {
+            let number = NonZeroUsize::new(1 + i).unwrap();
+            collector.frame(&page.frame, number, Transform::identity());
+        }
+        collector.finish()
+    }
+
+    /// Create warnings for raster images that are placed at a lower resolution
+    /// than `min_dpi`.
+    pub fn warnings(&self, min_dpi: f64) -> EcoVec<SourceDiagnostic> {
+        self.images
+            .iter()
+            .filter_map(|placed| {
+                let dpi = placed.dpi().filter(|&dpi| dpi < min_dpi)?;
+                Some(warning!(
+                    placed.span,
+                    "image is placed at only {dpi:.0} DPI";
+                    hint: "at least {min_dpi:.0} DPI are needed to print it sharply";
+                    hint: "use an image with more pixels or place it at a smaller size"
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Walks frames and gathers their resources.
+#[derive(Default)]
+struct Collector {
+    fonts: Vec<(Font, HashSet<u16>)>,
+    indices: HashMap<Font, usize>,
+    images: Vec<ImagePlacement>,
+}
+
+impl Collector {
+    fn frame(&mut self, frame: &Frame, page: NonZeroUsize, ts: Transform) {
+        for (pos, item) in frame.items() {
+            match item {
+                FrameItem::Group(group) => {
+                    let ts = ts
+                        .pre_concat(Transform::translate(pos.x, pos.y))
+                        .pre_concat(group.transform);
+                    self.frame(&group.frame, page, ts);
+                }
+                FrameItem::Text(text) => {
+                    let Self { fonts, indices, .. } = self;
+                    let index = *indices.entry(text.font.clone()).or_insert_with(|| {
+                        fonts.push((text.font.clone(), HashSet::new()));
+                        fonts.len() - 1
+                    });
+                    fonts[index].1.extend(text.glyphs.iter().map(|glyph| glyph.id));
+                    if let Paint::Pattern(pattern) = &text.fill {
+                        self.frame(pattern.frame(), page, ts);
+                    }
+                }
+                FrameItem::Shape(shape, _) => {
+                    if let Some(Paint::Pattern(pattern)) = &shape.fill {
+                        self.frame(pattern.frame(), page, ts);
+                    }
+                }
+                FrameItem::Image(image, size, span) => {
+                    // The lengths of the transformed edge vectors.
+                    let sx = ts.sx.get().hypot(ts.ky.get());
+                    let sy = ts.kx.get().hypot(ts.sy.get());
+                    self.images.push(ImagePlacement {
+                        image: image.clone(),
+                        page,
+                        size: Size::new(size.x * sx, size.y * sy),
+                        span: *span,
+                    });
+                }
+                FrameItem::Meta(..) => {}
+            }
+        }
+    }
+
+    fn finish(self) -> Resources {
+        Resources {
+            fonts: self
+                .fonts
+                .into_iter()
+                .map(|(font, glyphs)| FontUsage { font, glyphs: glyphs.len() })
+                .collect(),
+            images: self.images,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestWorld;
+
+    #[test]
+    fn test_resources() {
+        // A 20x10 pixel image.
+        let mut image = vec![];
+        ::image::RgbImage::new(20, 10)
+            .write_to(&mut std::io::Cursor::new(&mut image), ::image::ImageFormat::Png)
+            .unwrap();
+
+        let world = TestWorld::new(
+            "#set page(width: 600pt, height: auto, margin: 0pt)\n\
+             Hello\n\
+             #image(\"img.png\", width: 1in)\n\
+             #scale(400%, image(\"img.png\", width: 1in))\n\
+             #pagebreak()\n\
+             Hello there",
+        )
+        .with_file("img.png", image);
+        let document = crate::compile(&world, &mut Default::default()).unwrap();
+        let resources = Resources::new(&document);
+
+        assert_eq!(resources.fonts.len(), 1);
+        let usage = &resources.fonts[0];
+        assert_eq!(usage.font.info().family, "Linux Libertine");
+        assert_eq!(usage.glyphs, "Helo thr".len());
+
+        assert_eq!(resources.images.len(), 2);
+        let [plain, scaled] = [&resources.images[0], &resources.images[1]];
+        assert_eq!(plain.page.get(), 1);
+        assert_eq!(plain.size, Size::new(Abs::inches(1.0), Abs::inches(0.5)));
+        assert_eq!(plain.dpi(), Some(20.0));
+        assert!(scaled.size.x.approx_eq(Abs::inches(4.0)));
+        assert!(scaled.size.y.approx_eq(Abs::inches(2.0)));
+        assert!((scaled.dpi().unwrap() - 5.0).abs() < 1e-6);
+
+        let warnings = resources.warnings(10.0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].span, scaled.span);
+        assert_eq!(warnings[0].message, "image is placed at only 5 DPI");
+        assert_eq!(resources.warnings(300.0).len(), 2);
+    }
+}
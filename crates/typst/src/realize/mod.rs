@@ -109,6 +109,11 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
                 .store(EquationElem::new(content.clone()).pack().spanned(content.span()));
         }
 
+        #[cfg(feature = "debug")]
+        if let Some(traced) = self.traced(content, styles) {
+            return self.accept(traced, styles);
+        }
+
         if let Some(realized) = process(self.engine, content, styles)? {
             self.engine.route.increase();
             if !self.engine.route.within(Route::MAX_SHOW_RULE_DEPTH) {
@@ -178,6 +183,26 @@ impl<'a, 'v, 't> Builder<'a, 'v, 't> {
         }
     }
 
+    /// Attach the calls of user-defined functions that returned the content
+    /// as metadata, unless that already happened further up.
+    #[cfg(feature = "debug")]
+    fn traced(
+        &self,
+        content: &'a Content,
+        styles: StyleChain<'a>,
+    ) -> Option<&'a Content> {
+        use crate::introspection::Meta;
+
+        let calls = content.calls();
+        let innermost = *calls.first()?;
+        if MetaElem::data_in(styles).contains(&Meta::Call(innermost)) {
+            return None;
+        }
+
+        let data = calls.iter().rev().map(|&span| Meta::Call(span)).collect();
+        Some(self.arenas.store(content.clone().styled(MetaElem::set_data(data))))
+    }
+
     fn styled(
         &mut self,
         styled: &'a StyledElem,
@@ -3,7 +3,7 @@ use std::cell::OnceCell;
 use comemo::{Track, Tracked};
 use smallvec::smallvec;
 
-use crate::diag::SourceResult;
+use crate::diag::{warning, SourceResult};
 use crate::engine::Engine;
 use crate::foundations::{
     Content, Context, Packed, Recipe, RecipeIndex, Regex, Selector, Show, ShowSet, Style,
@@ -270,7 +270,14 @@ fn show(
     }
 }
 
+/// The maximum number of matches a regex show rule transforms in a single run
+/// of text. This keeps pathological patterns from blowing up the document.
+const MAX_MATCHES: usize = 10_000;
+
 /// Apply a regex show rule recipe to a target.
+///
+/// Only the first [`MAX_MATCHES`] matches are transformed; the rest of the text
+/// is kept as is and a warning is emitted.
 fn show_regex(
     engine: &mut Engine,
     target: &Packed<TextElem>,
@@ -290,7 +297,15 @@ fn show_regex(
 
     let text = target.text();
 
-    for m in regex.find_iter(target.text()) {
+    for (i, m) in regex.find_iter(target.text()).enumerate() {
+        if i == MAX_MATCHES {
+            engine.tracer.warn(warning!(
+                target.span(), "text show rule matched too often";
+                hint: "only the first {MAX_MATCHES} matches were transformed"
+            ));
+            break;
+        }
+
         let start = m.start();
         if cursor < start {
             result.push(make(&text[cursor..start]));
@@ -0,0 +1,103 @@
+//! Helpers for the unit tests of Typst's crates.
+//!
+//! Other crates get access to this module through the `testing` feature,
+//! which they should only enable for their dev-dependency on `typst`.
+
+use comemo::Prehashed;
+use once_cell::sync::Lazy;
+
+use crate::diag::{FileError, FileResult};
+use crate::foundations::{Bytes, Datetime};
+use crate::syntax::{FileId, Source, VirtualPath};
+use crate::text::{Font, FontBook};
+use crate::{Library, World};
+
+/// The fonts bundled with Typst, loaded once for all tests.
+static FONTS: Lazy<(Prehashed<FontBook>, Vec<Font>)> = Lazy::new(|| {
+    let fonts: Vec<_> = typst_assets::fonts()
+        .flat_map(|data| Font::iter(Bytes::from_static(data)))
+        .collect();
+    (Prehashed::new(FontBook::from_fonts(&fonts)), fonts)
+});
+
+/// A world with a main source file `main.typ`, the fonts bundled with Typst,
+/// and optionally a few more files in memory.
+pub struct TestWorld {
+    library: Prehashed<Library>,
+    main: Source,
+    sources: Vec<Source>,
+    files: Vec<(FileId, Bytes)>,
+}
+
+impl TestWorld {
+    /// Create a world whose main file has the given text.
+    pub fn new(text: &str) -> Self {
+        Self {
+            library: Prehashed::new(Library::default()),
+            main: Source::new(
+                FileId::new(None, VirtualPath::new("main.typ")),
+                text.into(),
+            ),
+            sources: vec![],
+            files: vec![],
+        }
+    }
+
+    /// Use a different standard library, for example one with inputs.
+    pub fn with_library(mut self, library: Library) -> Self {
+        self.library = Prehashed::new(library);
+        self
+    }
+
+    /// Add another source file at the given path.
+    pub fn with_source(mut self, path: &str, text: &str) -> Self {
+        let id = FileId::new(None, VirtualPath::new(path));
+        self.sources.push(Source::new(id, text.into()));
+        self
+    }
+
+    /// Add a binary file at the given path.
+    pub fn with_file(mut self, path: &str, data: impl Into<Bytes>) -> Self {
+        let id = FileId::new(None, VirtualPath::new(path));
+        self.files.push((id, data.into()));
+        self
+    }
+}
+
+impl World for TestWorld {
+    fn library(&self) -> &Prehashed<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &Prehashed<FontBook> {
+        &FONTS.0
+    }
+
+    fn main(&self) -> Source {
+        self.main.clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        std::iter::once(&self.main)
+            .chain(&self.sources)
+            .find(|source| source.id() == id)
+            .cloned()
+            .ok_or_else(|| FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        self.files
+            .iter()
+            .find(|(file, _)| *file == id)
+            .map(|(_, data)| data.clone())
+            .ok_or_else(|| FileError::NotFound(id.vpath().as_rootless_path().into()))
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        FONTS.1.get(index).cloned()
+    }
+
+    fn today(&self, _: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
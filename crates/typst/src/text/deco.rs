@@ -63,6 +63,8 @@ pub struct UnderlineElem {
     /// Whether the line skips sections in which it would collide with the
     /// glyphs.
     ///
+    /// The gaps around descenders grow with the thickness of the line.
+    ///
     /// ```example
     /// This #underline(evade: true)[is great].
     /// This #underline(evade: false)[is less great].
@@ -438,7 +440,9 @@ pub(crate) fn decorate(
         metrics.thickness.at(text.size),
     ));
 
-    let gap_padding = 0.08 * text.size;
+    // Thicker lines need larger gaps to keep the same visual distance to
+    // the glyphs they evade.
+    let gap_padding = (0.08 * text.size).max(stroke.thickness);
     let min_width = 0.162 * text.size;
 
     let start = pos.x - deco.extent;
@@ -127,7 +127,8 @@ impl FontBook {
     ///   but it doesn't exist, the other one of the two is still better than
     ///   normal.
     /// - The absolute distance to the target stretch.
-    /// - The absolute distance to the target weight.
+    /// - The absolute distance to the target weight. If two weights are
+    ///   equally close, the bolder one is preferred.
     fn find_best_variant(
         &self,
         like: Option<&FontInfo>,
@@ -153,6 +154,7 @@ impl FontBook {
                 current.variant.style.distance(variant.style),
                 current.variant.stretch.distance(variant.stretch),
                 current.variant.weight.distance(variant.weight),
+                Reverse(current.variant.weight),
             );
 
             if best_key.map_or(true, |b| key < b) {
@@ -501,6 +503,39 @@ mod tests {
         assert_eq!(typographic_family("Font Ultra Bold"), "Font");
     }
 
+    #[test]
+    fn test_select_nearest_weight() {
+        let book = FontBook::from_infos([400, 500, 700].map(|weight| FontInfo {
+            family: "Test".into(),
+            variant: FontVariant::new(
+                FontStyle::Normal,
+                FontWeight::from_number(weight),
+                FontStretch::NORMAL,
+            ),
+            flags: FontFlags::empty(),
+            coverage: Coverage::from_vec(vec![]),
+        }));
+
+        #[track_caller]
+        fn test(book: &FontBook, weight: FontWeight, delta: i16, expected: u16) {
+            let variant = FontVariant::new(
+                FontStyle::Normal,
+                weight.thicken(delta),
+                FontStretch::NORMAL,
+            );
+            let id = book.select("test", variant).unwrap();
+            assert_eq!(book.info(id).unwrap().variant.weight.to_number(), expected);
+        }
+
+        test(&book, FontWeight::REGULAR, 0, 400);
+        test(&book, FontWeight::REGULAR, 100, 500);
+        test(&book, FontWeight::REGULAR, 300, 700);
+        test(&book, FontWeight::MEDIUM, 300, 700);
+        test(&book, FontWeight::REGULAR, 200, 700);
+        test(&book, FontWeight::REGULAR, -300, 400);
+        test(&book, FontWeight::BOLD, 300, 700);
+    }
+
     #[test]
     fn test_coverage() {
         #[track_caller]
@@ -0,0 +1,234 @@
+use comemo::Tracked;
+use ecow::{eco_format, EcoString};
+
+use crate::diag::warning;
+use crate::engine::Engine;
+use crate::foundations::calc::Num;
+use crate::foundations::{func, repr, Context, Smart};
+use crate::syntax::Span;
+use crate::text::{Lang, TextElem};
+
+/// Displays a number following the conventions of a language.
+///
+/// The digits of the integer part are grouped in threes and the decimal
+/// separator customary in the language is used. The result only depends on
+/// the language and not on the system the document is compiled on.
+///
+/// ```example
+/// #display-number(1234.5) \
+/// #display-number(1234.5, lang: "de") \
+/// #display-number(-1234567, lang: "fr")
+/// ```
+#[func]
+pub fn display_number(
+    /// The engine.
+    engine: &mut Engine,
+    /// The callsite context.
+    context: Tracked<Context>,
+    /// The callsite span.
+    span: Span,
+    /// The number to display.
+    number: Num,
+    /// The language whose conventions to follow.
+    ///
+    /// Defaults to the [text language]($text.lang) if the context is known
+    /// and to English otherwise. English, German, and French are supported.
+    /// Other languages fall back to English with a warning.
+    #[named]
+    #[default]
+    lang: Smart<Lang>,
+) -> EcoString {
+    Locale::resolve(engine, context, span, lang).format_number(number)
+}
+
+/// Data for formatting numbers and dates in a language.
+pub(crate) struct Locale {
+    /// The separator between the integer and the fractional part.
+    decimal: &'static str,
+    /// The separator between groups of three digits.
+    group: &'static str,
+    /// The names of the months, starting with January.
+    months: [&'static str; 12],
+    /// The abbreviated names of the months.
+    months_short: [&'static str; 12],
+    /// The names of the weekdays, starting with Monday.
+    weekdays: [&'static str; 7],
+    /// The abbreviated names of the weekdays.
+    weekdays_short: [&'static str; 7],
+}
+
+impl Locale {
+    pub(crate) const ENGLISH: Self = Self {
+        decimal: ".",
+        group: ",",
+        months: [
+            "January",
+            "February",
+            "March",
+            "April",
+            "May",
+            "June",
+            "July",
+            "August",
+            "September",
+            "October",
+            "November",
+            "December",
+        ],
+        months_short: [
+            "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov",
+            "Dec",
+        ],
+        weekdays: [
+            "Monday",
+            "Tuesday",
+            "Wednesday",
+            "Thursday",
+            "Friday",
+            "Saturday",
+            "Sunday",
+        ],
+        weekdays_short: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+    };
+
+    const GERMAN: Self = Self {
+        decimal: ",",
+        group: ".",
+        months: [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ],
+        months_short: [
+            "Jan.", "Feb.", "März", "Apr.", "Mai", "Juni", "Juli", "Aug.", "Sept.",
+            "Okt.", "Nov.", "Dez.",
+        ],
+        weekdays: [
+            "Montag",
+            "Dienstag",
+            "Mittwoch",
+            "Donnerstag",
+            "Freitag",
+            "Samstag",
+            "Sonntag",
+        ],
+        weekdays_short: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+    };
+
+    const FRENCH: Self = Self {
+        decimal: ",",
+        group: "\u{202F}",
+        months: [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ],
+        months_short: [
+            "janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.",
+            "oct.", "nov.", "déc.",
+        ],
+        weekdays: [
+            "lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche",
+        ],
+        weekdays_short: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+    };
+
+    /// The data for a language, if there is any.
+    fn get(lang: Lang) -> Option<&'static Self> {
+        match lang {
+            Lang::ENGLISH => Some(&Self::ENGLISH),
+            Lang::GERMAN => Some(&Self::GERMAN),
+            Lang::FRENCH => Some(&Self::FRENCH),
+            _ => None,
+        }
+    }
+
+    /// The data for an explicitly given language or the text language from
+    /// the context.
+    ///
+    /// Falls back to English with a warning if there is no data for the
+    /// language.
+    pub(crate) fn resolve(
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+        lang: Smart<Lang>,
+    ) -> &'static Self {
+        let lang = lang
+            .unwrap_or_else(|| context.styles().map_or(Lang::ENGLISH, TextElem::lang_in));
+
+        Self::get(lang).unwrap_or_else(|| {
+            engine.tracer.warn(warning!(
+                span,
+                "localized formatting is not available for language {}",
+                lang.as_str();
+                hint: "falling back to English"
+            ));
+            &Self::ENGLISH
+        })
+    }
+
+    /// Format a number with the separators of this locale.
+    pub(crate) fn format_number(&self, number: Num) -> EcoString {
+        let (negative, digits) = match number {
+            Num::Int(v) => (v < 0, eco_format!("{}", v.unsigned_abs())),
+            Num::Float(v) if !v.is_finite() => {
+                return repr::format_float(v, None, false, "");
+            }
+            Num::Float(v) => (v < 0.0, eco_format!("{}", v.abs())),
+        };
+
+        let (int, frac) = digits.split_once('.').unwrap_or((&digits, ""));
+        let mut out = EcoString::new();
+        if negative {
+            out.push_str(repr::MINUS_SIGN);
+        }
+
+        for (i, c) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                out.push_str(self.group);
+            }
+            out.push(c);
+        }
+
+        if !frac.is_empty() {
+            out.push_str(self.decimal);
+            out.push_str(frac);
+        }
+
+        out
+    }
+
+    /// Translate the English name of a month or weekday into this locale.
+    pub(crate) fn translate(&self, name: &str) -> Option<&'static str> {
+        let en = &Self::ENGLISH;
+        let tables: [(&[&str], &[&'static str]); 4] = [
+            (&en.months, &self.months),
+            (&en.months_short, &self.months_short),
+            (&en.weekdays, &self.weekdays),
+            (&en.weekdays_short, &self.weekdays_short),
+        ];
+
+        tables.into_iter().find_map(|(english, names)| {
+            english.iter().position(|&n| n == name).map(|i| names[i])
+        })
+    }
+}
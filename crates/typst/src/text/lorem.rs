@@ -1,3 +1,6 @@
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
 use crate::foundations::{func, Str};
 
 /// Creates blind text.
@@ -19,6 +22,25 @@ use crate::foundations::{func, Str};
 pub fn lorem(
     /// The length of the blind text in words.
     words: usize,
+    /// A seed for choosing a different, but equally deterministic sequence of
+    /// words.
+    ///
+    /// The same seed always yields the same text, independently of the
+    /// platform. Without a seed, the text starts with the classic
+    /// "Lorem ipsum" and always follows the same sequence.
+    ///
+    /// ```example
+    /// #lorem(10, seed: 1) \
+    /// #lorem(10, seed: 2)
+    /// ```
+    #[named]
+    seed: Option<u64>,
 ) -> Str {
-    lipsum::lipsum(words).replace("--", "–").into()
+    let text = match seed {
+        Some(seed) => {
+            lipsum::lipsum_words_with_rng(ChaCha8Rng::seed_from_u64(seed), words)
+        }
+        None => lipsum::lipsum(words),
+    };
+    text.replace("--", "–").into()
 }
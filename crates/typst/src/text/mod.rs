@@ -6,6 +6,7 @@ mod font;
 mod item;
 mod lang;
 mod linebreak;
+mod locale;
 #[path = "lorem.rs"]
 mod lorem_;
 mod raw;
@@ -21,6 +22,7 @@ pub use self::font::*;
 pub use self::item::*;
 pub use self::lang::*;
 pub use self::linebreak::*;
+pub use self::locale::*;
 pub use self::lorem_::*;
 pub use self::raw::*;
 pub use self::shift::*;
@@ -41,12 +43,13 @@ use crate::foundations::Packed;
 use crate::foundations::{
     cast, category, elem, Args, Array, Cast, Category, Construct, Content, Dict, Fold,
     NativeElement, Never, PlainText, Repr, Resolve, Scope, Set, Smart, StyleChain,
+    Styles,
 };
 use crate::layout::Em;
 use crate::layout::{Abs, Axis, Dir, Length, Rel};
 use crate::model::ParElem;
 use crate::syntax::Spanned;
-use crate::visualize::{Color, Paint, RelativeTo, Stroke};
+use crate::visualize::{Color, Paint, RelativeTo, Rgb, Stroke};
 
 /// Text styling.
 ///
@@ -71,6 +74,7 @@ pub(super) fn define(global: &mut Scope) {
     global.define_func::<upper>();
     global.define_func::<smallcaps>();
     global.define_func::<lorem>();
+    global.define_func::<display_number>();
 }
 
 /// Customizes the look and layout of text in a variety of ways.
@@ -203,6 +207,25 @@ pub struct TextElem {
     #[ghost]
     pub stretch: FontStretch,
 
+    /// Whether to synthesize bold and italic styles that are missing from the
+    /// font family.
+    ///
+    /// When bold text is requested, but the family has no bold face, Typst
+    /// emboldens the regular glyphs by stroking their outlines. When italic or
+    /// oblique text is requested, but the family has neither an italic nor an
+    /// oblique face, Typst slants the regular glyphs. Set this to `{false}` to
+    /// use the closest available face as-is instead.
+    ///
+    /// ```example
+    /// #set text(font: "New Computer Modern Math")
+    /// *Bold* and _italic_ \
+    /// #set text(synthesis: false)
+    /// *Bold* and _italic_
+    /// ```
+    #[ghost]
+    #[default(true)]
+    pub synthesis: bool,
+
     /// The size of the glyphs. This value forms the basis of the `em` unit:
     /// `{1em}` is equivalent to the font size.
     ///
@@ -226,12 +249,29 @@ pub struct TextElem {
     /// #set text(fill: red)
     /// This text is red.
     /// ```
+    ///
+    /// When set to `{auto}`, the text is black or white, whichever contrasts
+    /// more with the fill of the nearest surrounding box, block, shape, or
+    /// table cell, or otherwise with the page's fill. This is decided by the
+    /// background's relative luminance as defined by WCAG: Below a luminance
+    /// of 0.179, the text is white. A gradient counts with its average color
+    /// and a pattern counts as white. A translucent fill is blended with the
+    /// fill behind it, and a page without a fill counts as white paper.
+    ///
+    /// ```example
+    /// #set text(fill: auto)
+    /// #box(fill: navy, inset: 4pt)[Dark]
+    /// #box(fill: yellow.lighten(60%), inset: 4pt)[Light]
+    /// ```
     #[parse({
-        let paint: Option<Spanned<Paint>> = args.named_or_find("fill")?;
-        if let Some(paint) = &paint {
-            if paint.v.relative() == Smart::Custom(RelativeTo::Self_) {
+        let paint = match args.named::<Spanned<TextFill>>("fill")? {
+            Some(fill) => Some(fill),
+            None => args.find::<Spanned<Paint>>()?.map(|paint| paint.map(TextFill::from)),
+        };
+        if let Some(Spanned { v: TextFill(Smart::Custom(paint)), span }) = &paint {
+            if paint.relative() == Smart::Custom(RelativeTo::Self_) {
                 bail!(
-                    paint.span,
+                    *span,
                     "gradients and patterns on text must be relative to the parent";
                     hint: "make sure to set `relative: auto` on your text fill"
                 );
@@ -239,14 +279,25 @@ pub struct TextElem {
         }
         paint.map(|paint| paint.v)
     })]
+    #[resolve]
     #[default(Color::BLACK.into())]
     #[ghost]
-    pub fill: Paint,
+    pub fill: TextFill,
 
     /// How to stroke the text.
     ///
+    /// The stroke is drawn centered on the outlines of the glyphs, so half of
+    /// it covers the fill. At small sizes, a stroke that is wider than the
+    /// stems of the glyphs fills their counters and makes the text hard to
+    /// read. Decorations like [underlines]($underline) are not affected by
+    /// the text's stroke; they have their own `stroke` property and use the
+    /// text's fill by default.
+    ///
     /// ```example
     /// #text(stroke: 0.5pt + red)[Stroked]
+    ///
+    /// #set text(size: 24pt, weight: "bold")
+    /// #text(stroke: 0.5pt + black, fill: white)[TITLE]
     /// ```
     #[resolve]
     #[ghost]
@@ -324,6 +375,10 @@ pub struct TextElem {
     /// The top end of the conceptual frame around the text used for layout and
     /// positioning. This affects the size of containers that hold text.
     ///
+    /// With `{"bounds"}` for both edges, the frame tightly fits the ink of the
+    /// glyphs, so that a box around the text has the same padding on all
+    /// sides. The baseline stays where it is.
+    ///
     /// ```example
     /// #set rect(inset: 0pt)
     /// #set text(size: 20pt)
@@ -333,6 +388,9 @@ pub struct TextElem {
     ///
     /// #set text(top-edge: "cap-height")
     /// #rect(fill: aqua)[Typst]
+    ///
+    /// #set text(top-edge: "bounds", bottom-edge: "bounds")
+    /// #rect(fill: aqua)[Typst]
     /// ```
     #[default(TopEdge::Metric(TopEdgeMetric::CapHeight))]
     #[ghost]
@@ -656,6 +714,14 @@ pub struct TextElem {
     #[default(false)]
     #[ghost]
     pub smallcaps: bool,
+
+    /// The opaque color behind the text, blended from the fills of the page
+    /// and all surrounding containers. An automatic text fill contrasts with
+    /// it.
+    #[internal]
+    #[default(Color::WHITE)]
+    #[ghost]
+    pub background: Color,
 }
 
 impl TextElem {
@@ -663,6 +729,23 @@ impl TextElem {
     pub fn packed(text: impl Into<EcoString>) -> Content {
         Self::new(text.into()).pack()
     }
+
+    /// The styles for content shown over a background fill, which text with
+    /// an automatic fill contrasts with. The fill is painted over the
+    /// background that is already in effect in `styles`.
+    pub fn backed(fill: &Option<Paint>, styles: StyleChain) -> Styles {
+        let mut out = Styles::new();
+        if let Some(fill) = fill {
+            let color = match fill {
+                Paint::Solid(color) => *color,
+                Paint::Gradient(gradient) => gradient.average(),
+                Paint::Pattern(_) => Color::WHITE,
+            };
+            let behind = Self::background_in(styles);
+            out.set(Self::set_background(blend(color, behind)));
+        }
+        out
+    }
 }
 
 impl Debug for TextElem {
@@ -975,6 +1058,55 @@ impl Resolve for TextDir {
     }
 }
 
+/// The fill of text, which may be chosen automatically for contrast.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct TextFill(pub Smart<Paint>);
+
+impl TextFill {
+    /// The relative luminance of a background below which white text
+    /// contrasts more than black text. At this luminance, both have a WCAG
+    /// contrast ratio of about 4.58.
+    pub const THRESHOLD: f32 = 0.179;
+}
+
+impl<T: Into<Paint>> From<T> for TextFill {
+    fn from(paint: T) -> Self {
+        Self(Smart::Custom(paint.into()))
+    }
+}
+
+cast! {
+    TextFill,
+    self => self.0.into_value(),
+    v: Smart<Paint> => Self(v),
+}
+
+impl Resolve for TextFill {
+    type Output = Paint;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        let color = match self.0 {
+            Smart::Custom(paint) => return paint,
+            Smart::Auto => TextElem::background_in(styles),
+        };
+
+        if color.relative_luminance() < Self::THRESHOLD {
+            Color::WHITE.into()
+        } else {
+            Color::BLACK.into()
+        }
+    }
+}
+
+/// Paint a possibly translucent color over an opaque one, as a viewer would
+/// composite them in sRGB.
+fn blend(color: Color, behind: Color) -> Color {
+    let [r, g, b, a] = color.to_rgb().to_vec4();
+    let [br, bg, bb, _] = behind.to_rgb().to_vec4();
+    let mix = |front: f32, back: f32| a * front + (1.0 - a) * back;
+    Color::Rgb(Rgb::new(mix(r, br), mix(g, bg), mix(b, bb), 1.0))
+}
+
 /// Whether to hyphenate text.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Hyphenate(pub Smart<bool>);
@@ -1165,3 +1297,86 @@ impl Fold for WeightDelta {
         Self(outer.0 + self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::layout::{Angle, Ratio};
+    use crate::visualize::{ColorSpace, Gradient, LinearGradient};
+
+    #[test]
+    fn test_text_fill_auto() {
+        #[track_caller]
+        fn test(fills: &[Paint], expected: Color) {
+            let layers: Vec<_> = fills.iter().map(|fill| Some(fill.clone())).collect();
+            let base = Styles::new();
+            auto(StyleChain::new(&base), &layers, expected);
+        }
+
+        #[track_caller]
+        fn auto(styles: StyleChain, layers: &[Option<Paint>], expected: Color) {
+            match layers {
+                [] => assert_eq!(TextFill(Smart::Auto).resolve(styles), expected.into()),
+                [fill, rest @ ..] => {
+                    let backed = TextElem::backed(fill, styles);
+                    auto(styles.chain(&backed), rest, expected)
+                }
+            }
+        }
+
+        let navy = Color::from_u8(0x00, 0x1f, 0x3f, 0xff);
+        let light_yellow = Color::from_u8(0xff, 0xf9, 0xc4, 0xff);
+        test(&[navy.into()], Color::WHITE);
+        test(&[light_yellow.into()], Color::BLACK);
+        test(&[], Color::BLACK);
+
+        // Translucent fills are blended with what is behind them, which is
+        // white paper if nothing else is.
+        let glass = Color::WHITE.with_alpha(0.1);
+        test(&[glass.into()], Color::BLACK);
+        test(&[navy.into(), glass.into()], Color::WHITE);
+        test(&[navy.into(), Color::WHITE.with_alpha(0.9).into()], Color::BLACK);
+        test(&[navy.with_alpha(0.1).into()], Color::BLACK);
+
+        // Averaged in linear RGB, a gradient from black to white is light.
+        let gradient = |from, to| {
+            Gradient::Linear(Arc::new(LinearGradient {
+                stops: vec![(from, Ratio::zero()), (to, Ratio::one())],
+                angle: Angle::zero(),
+                space: ColorSpace::Oklab,
+                relative: Smart::Auto,
+                anti_alias: true,
+            }))
+        };
+        test(&[gradient(Color::BLACK, Color::WHITE).into()], Color::BLACK);
+        test(&[gradient(Color::BLACK, navy).into()], Color::WHITE);
+    }
+
+    #[test]
+    fn test_text_fill_auto_on_page_fill() {
+        use crate::layout::FrameItem;
+        use crate::testing::TestWorld;
+
+        let world = TestWorld::new(
+            "#set page(fill: navy)\n\
+             #set text(fill: auto)\n\
+             A #box(fill: white.transparentize(20%))[B]",
+        );
+        let document = crate::compile(&world, &mut Default::default()).unwrap();
+        let mut fills = vec![];
+        let mut frames = vec![&document.pages[0].frame];
+        while let Some(frame) = frames.pop() {
+            for (_, item) in frame.items() {
+                match item {
+                    FrameItem::Group(group) => frames.push(&group.frame),
+                    FrameItem::Text(text) => fills.push(text.fill.clone()),
+                    _ => {}
+                }
+            }
+        }
+        fills.sort_by_key(|fill| fill == &Paint::from(Color::WHITE));
+        assert_eq!(fills, [Color::BLACK.into(), Color::WHITE.into()]);
+    }
+}
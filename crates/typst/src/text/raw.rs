@@ -5,6 +5,7 @@ use std::sync::Arc;
 use ecow::{eco_format, EcoString, EcoVec};
 use once_cell::sync::Lazy;
 use once_cell::unsync::Lazy as UnsyncLazy;
+use smallvec::smallvec;
 use syntect::highlighting as synt;
 use syntect::parsing::{SyntaxDefinition, SyntaxSet, SyntaxSetBuilder};
 use unicode_segmentation::UnicodeSegmentation;
@@ -13,17 +14,22 @@ use crate::diag::{At, FileError, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, scope, Args, Array, Bytes, Content, Fold, NativeElement, Packed,
-    PlainText, Show, ShowSet, Smart, StyleChain, Styles, Synthesize, Value,
+    PlainText, RecipeIndex, Selector, Show, ShowSet, Smart, Style, StyleChain, Styles,
+    Synthesize, Value,
 };
-use crate::layout::{BlockElem, Em, HAlignment};
-use crate::model::Figurable;
+use crate::introspection::{Meta, MetaElem};
+use crate::layout::{
+    Abs, Axes, BlockElem, BoxElem, Corners, Em, GridCell, GridChild, GridElem, GridItem,
+    HAlignment, LayoutMultiple, Length, Regions, Rel, Sides, Sizing, TrackSizings,
+};
+use crate::model::{Figurable, ParElem};
 use crate::syntax::{split_newlines, LinkedNode, Span, Spanned};
 use crate::text::{
     FontFamily, FontList, Hyphenate, Lang, LinebreakElem, LocalName, Region,
     SmartQuoteElem, TextElem, TextSize,
 };
 use crate::util::option_eq;
-use crate::visualize::Color;
+use crate::visualize::{Color, Paint};
 use crate::{syntax, World};
 
 // Shorthand for highlighter closures.
@@ -57,8 +63,14 @@ type LineFn<'a> = &'a mut dyn FnMut(usize, Range<usize>, &mut Vec<Content>);
 /// ````
 ///
 /// # Syntax
-/// This function also has dedicated syntax. You can enclose text in 1 or 3+
-/// backticks (`` ` ``) to make it raw. Two backticks produce empty raw text.
+/// This function also has dedicated syntax. You can enclose text in one or
+/// more backticks (`` ` ``) to make it raw. The raw text ends at the next run
+/// of exactly as many backticks as it started with, so it can contain runs
+/// of backticks of other lengths. Two backticks that aren't closed by another
+/// two produce empty raw text. With a single backtick, the text can't contain
+/// backticks at all. Raw text with three or more backticks that spans multiple
+/// lines only ends at a fence that starts a line.
+///
 /// When you use three or more backticks, you can additionally specify a
 /// language tag for syntax highlighting directly after the opening backticks.
 /// Within raw blocks, everything (except for the language tag, if applicable)
@@ -70,6 +82,14 @@ type LineFn<'a> = &'a mut dyn FnMut(usize, Range<usize>, &mut Vec<Content>);
 /// needed, start the text with a single space (which will be trimmed) or use
 /// the single backtick syntax. If your text should start or end with a
 /// backtick, put a space before or after it (it will be trimmed).
+///
+/// In a raw block that spans multiple lines, the first and the last line are
+/// dropped if they only contain whitespace. Moreover, the indentation that all
+/// non-blank lines after the first one and the line with the closing backticks
+/// have in common is removed. This way, a block can be indented along with the
+/// surrounding markup while the relative indentation of the code within it is
+/// preserved exactly. For this, every whitespace character, including a tab,
+/// counts as one level of indentation.
 #[elem(
     scope,
     title = "Raw Text / Code",
@@ -106,7 +126,7 @@ pub struct RawElem {
 
     /// Whether the raw text is displayed as a separate block.
     ///
-    /// In markup mode, using one-backtick notation makes this `{false}`.
+    /// In markup mode, using one- or two-backtick notation makes this `{false}`.
     /// Using three-backtick notation makes it `{true}` if the enclosed content
     /// contains at least one line break.
     ///
@@ -177,6 +197,32 @@ pub struct RawElem {
     #[default(HAlignment::Start)]
     pub align: HAlignment,
 
+    /// The background of inline raw text. Raw blocks are not affected, use a
+    /// show rule with [`block`]($block) for those instead.
+    ///
+    /// When a background or an inset is set, inline raw text is kept together
+    /// in a [box]($box) that keeps the baseline of the surrounding text. The
+    /// text is then never broken across lines.
+    ///
+    /// ```example
+    /// #set raw(fill: luma(240), inset: 2pt, radius: 2pt)
+    /// Run `cargo test` to check
+    /// everything `works`.
+    /// ```
+    pub fill: Option<Paint>,
+
+    /// How much to pad inline raw text.
+    ///
+    /// Only the horizontal insets take up space in the line. The vertical ones
+    /// extend the background without affecting the line height, like the
+    /// [outset]($box.outset) of a box.
+    #[fold]
+    pub inset: Sides<Option<Rel<Length>>>,
+
+    /// How much to round the corners of the background of inline raw text.
+    #[fold]
+    pub radius: Corners<Option<Rel<Length>>>,
+
     /// One or multiple additional syntax definitions to load. The syntax
     /// definitions should be in the
     /// [`sublime-syntax` file format](https://www.sublimetext.com/docs/syntax.html).
@@ -257,6 +303,41 @@ pub struct RawElem {
     #[default(2)]
     pub tab_size: usize,
 
+    /// Whether to number the lines of a raw block. This option is ignored if
+    /// this is not a raw block.
+    ///
+    /// The numbers are shown right-aligned in a lighter color in front of the
+    /// lines. They are not part of the code, so text extraction skips them.
+    ///
+    /// ````example
+    /// #set raw(numbers: true)
+    /// ```rust
+    /// fn main() {
+    ///     println!("Hello World!");
+    /// }
+    /// ```
+    /// ````
+    #[default(false)]
+    pub numbers: bool,
+
+    /// Whether string and regex show rules from outside of the raw element
+    /// apply to its text.
+    ///
+    /// By default, they don't, so that replacements that are meant for prose
+    /// stay out of code. Enable this to let them apply like to any other text.
+    /// Text show rules that are defined within a show rule for `raw` always
+    /// apply.
+    ///
+    /// ````example
+    /// #show "Typst": smallcaps
+    ///
+    /// Typst is written in Rust: `Typst` \
+    /// #set raw(text-rules: true)
+    /// Typst is written in Rust: `Typst`
+    /// ````
+    #[default(false)]
+    pub text_rules: bool,
+
     /// The stylized lines of raw text.
     ///
     /// Made accessible for the [`raw.line` element]($raw.line).
@@ -429,8 +510,11 @@ impl Packed<RawElem> {
 
 impl Show for Packed<RawElem> {
     #[typst_macros::time(name = "raw", span = self.span())]
-    fn show(&self, _: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
+    fn show(&self, engine: &mut Engine, styles: StyleChain) -> SourceResult<Content> {
         let lines = self.lines().map(|v| v.as_slice()).unwrap_or_default();
+        if self.block(styles) && self.numbers(styles) {
+            return Ok(self.numbered(lines, styles));
+        }
 
         let mut seq = EcoVec::with_capacity((2 * lines.len()).saturating_sub(1));
         for (i, line) in lines.iter().enumerate() {
@@ -447,20 +531,100 @@ impl Show for Packed<RawElem> {
             realized = realized.aligned(self.align(styles).into());
             realized =
                 BlockElem::new().with_body(Some(realized)).pack().spanned(self.span());
+        } else {
+            realized = self.boxed(engine, realized, styles)?;
         }
 
         Ok(realized)
     }
 }
 
+impl Packed<RawElem> {
+    /// Put inline raw text into a box with its background and inset, if any.
+    ///
+    /// The text is measured up front and laid out at its natural width, so
+    /// that it is never broken across lines.
+    fn boxed(
+        &self,
+        engine: &mut Engine,
+        body: Content,
+        styles: StyleChain,
+    ) -> SourceResult<Content> {
+        let fill = self.fill(styles);
+        let inset = self.inset(styles);
+        if fill.is_none() && inset.iter().all(Option::is_none) {
+            return Ok(body);
+        }
+
+        let pod = Regions::one(Axes::splat(Abs::inf()), Axes::splat(false));
+        let width = body.measure(engine, styles, pod)?.into_frame().width();
+        let body = BoxElem::new()
+            .with_width(Sizing::Rel(Length::from(width).into()))
+            .with_body(Some(body))
+            .pack()
+            .spanned(self.span());
+
+        Ok(BoxElem::new()
+            .with_body(Some(body))
+            .with_fill(fill)
+            .with_radius(self.radius(styles))
+            .with_inset(Sides { top: None, bottom: None, ..inset })
+            .with_outset(Sides { left: None, right: None, ..inset })
+            .pack()
+            .spanned(self.span()))
+    }
+
+    /// Lay out the lines of a raw block next to a column of line numbers.
+    fn numbered(&self, lines: &[Packed<RawLine>], styles: StyleChain) -> Content {
+        let align = self.align(styles);
+        let mut cells = vec![];
+        for line in lines {
+            let number = TextElem::packed(eco_format!("{}", line.number))
+                .styled(TextElem::set_fill(Color::GRAY.into()))
+                .styled(MetaElem::set_data(smallvec![Meta::Artifact]))
+                .aligned(HAlignment::End.into());
+            cells.push(GridChild::Item(GridItem::Cell(
+                Packed::new(GridCell::new(number)).spanned(self.span()),
+            )));
+            cells.push(GridChild::Item(GridItem::Cell(
+                Packed::new(GridCell::new(line.clone().pack().aligned(align.into())))
+                    .spanned(self.span()),
+            )));
+        }
+
+        let grid = GridElem::new(cells)
+            .with_columns(TrackSizings(smallvec![Sizing::Auto; 2]))
+            .with_column_gutter(TrackSizings(smallvec![Em::new(1.0).into()]))
+            .with_row_gutter(TrackSizings(smallvec![ParElem::leading_in(styles).into()]))
+            .pack()
+            .spanned(self.span());
+
+        BlockElem::new().with_body(Some(grid)).pack().spanned(self.span())
+    }
+}
+
 impl ShowSet for Packed<RawElem> {
-    fn show_set(&self, _: StyleChain) -> Styles {
+    fn show_set(&self, styles: StyleChain) -> Styles {
         let mut out = Styles::new();
         out.set(TextElem::set_overhang(false));
         out.set(TextElem::set_hyphenate(Hyphenate(Smart::Custom(false))));
         out.set(TextElem::set_size(TextSize(Em::new(0.8).into())));
         out.set(TextElem::set_font(FontList(vec![FontFamily::new("DejaVu Sans Mono")])));
         out.set(SmartQuoteElem::set_enabled(false));
+
+        // Keep text show rules from outside of the raw element away from its
+        // text unless requested otherwise. Since these styles end up around the result of
+        // any show rule for `raw`, text show rules defined in there still
+        // apply. Recipes are indexed from the top of the chain.
+        if !self.text_rules(styles) {
+            let recipes: Vec<_> = styles.entries().filter_map(Style::recipe).collect();
+            for (i, recipe) in recipes.iter().enumerate() {
+                if matches!(recipe.selector, Some(Selector::Regex(_))) {
+                    out.set(Style::Revocation(RecipeIndex(recipes.len() - i)));
+                }
+            }
+        }
+
         out
     }
 }
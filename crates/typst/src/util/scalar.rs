@@ -6,6 +6,7 @@ use std::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
+use crate::foundations::cast;
 use crate::util::Numeric;
 
 /// A 64-bit float that implements `Eq`, `Ord` and `Hash`.
@@ -37,6 +38,12 @@ impl Scalar {
     }
 }
 
+cast! {
+    Scalar,
+    self => self.0.into_value(),
+    v: f64 => Self::new(v),
+}
+
 // We have to detect NaNs this way since `f64::is_nan` isn’t const
 // on stable yet:
 // ([tracking issue](https://github.com/rust-lang/rust/issues/57241))
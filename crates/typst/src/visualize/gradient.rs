@@ -755,6 +755,17 @@ impl Gradient {
 
         self
     }
+
+    /// The average color of the gradient, mixed in linear RGB.
+    pub fn average(&self) -> Color {
+        const SAMPLES: usize = 32;
+        let colors = (0..SAMPLES).map(|i| {
+            let t = Ratio::new(i as f64 / (SAMPLES - 1) as f64);
+            WeightedColor::new(self.sample(RatioOrAngle::Ratio(t)), 1.0)
+        });
+        Color::mix_iter(colors, ColorSpace::LinearRgb).unwrap()
+    }
+
     /// Returns a reference to the stops of this gradient.
     pub fn stops_ref(&self) -> &[(Color, Ratio)] {
         match self {
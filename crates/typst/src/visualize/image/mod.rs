@@ -13,22 +13,23 @@ use std::sync::Arc;
 use comemo::Tracked;
 use ecow::EcoString;
 
-use crate::diag::{bail, At, SourceResult, StrResult};
+use crate::diag::{bail, At, Severity, SourceResult, StrResult};
 use crate::engine::Engine;
 use crate::foundations::{
     cast, elem, func, scope, Bytes, Cast, Content, NativeElement, Packed, Resolve, Smart,
     StyleChain,
 };
 use crate::layout::{
-    Abs, Axes, FixedAlignment, Frame, FrameItem, LayoutSingle, Length, Point, Regions,
-    Rel, Size,
+    Abs, AlignElem, Alignment, Axes, BoxElem, Em, FixedAlignment, Frame, FrameItem,
+    HAlignment, LayoutSingle, Length, Point, Regions, Rel, Sides, Size, Sizing,
+    VAlignment,
 };
 use crate::loading::Readable;
 use crate::model::Figurable;
 use crate::syntax::{Span, Spanned};
-use crate::text::{families, Lang, LocalName, Region};
+use crate::text::{families, Lang, LocalName, Region, TextElem, TextSize};
 use crate::util::{option_eq, LazyHash};
-use crate::visualize::Path;
+use crate::visualize::{Color, Path, Stroke};
 use crate::World;
 
 /// A raster or vector graphic.
@@ -58,8 +59,10 @@ pub struct ImageElem {
     #[parse(
         let Spanned { v: path, span } =
             args.expect::<Spanned<EcoString>>("path to image file")?;
-        let id = span.resolve_path(&path).at(span)?;
-        let data = engine.world.file(id).at(span)?;
+        let loaded = span
+            .resolve_path(&path)
+            .and_then(|id| engine.world.file(id).map_err(Into::into))
+            .map_err(|message| Spanned::new(message, span));
         path
     )]
     #[borrowed]
@@ -68,9 +71,16 @@ pub struct ImageElem {
     /// The raw file data.
     #[internal]
     #[required]
-    #[parse(Readable::Bytes(data))]
+    #[parse(Readable::Bytes(loaded.clone().unwrap_or_else(|_| Bytes::from_static(&[]))))]
     pub data: Readable,
 
+    /// Why the file couldn't be loaded, if it couldn't. The error is reported
+    /// during layout, where it is known whether to show a fallback instead.
+    #[internal]
+    #[required]
+    #[parse(loaded.err())]
+    pub failure: Option<Spanned<EcoString>>,
+
     /// The image's format. Detected automatically by default.
     pub format: Smart<ImageFormat>,
 
@@ -96,9 +106,29 @@ pub struct ImageElem {
     /// ```
     #[default(ImageFit::Cover)]
     pub fit: ImageFit,
+
+    /// Whether to show a placeholder if the image can't be loaded or decoded.
+    ///
+    /// By default, a missing or broken image is an error. With a fallback,
+    /// the problem is reported as a warning instead and a box with the image's
+    /// file name takes its place, so that the rest of the document can still
+    /// be reviewed. The box has the image's width and height if they are
+    /// given and is a `{5em}` square otherwise.
+    ///
+    /// So that set rules can enable the fallback, a file that can't be loaded
+    /// is only reported once the image is laid out. An image that is never
+    /// shown doesn't produce an error.
+    ///
+    /// ```example
+    /// #set image(fallback: true)
+    /// #image("missing.png", width: 80%)
+    /// ```
+    #[default(false)]
+    pub fallback: bool,
 }
 
 #[scope]
+#[allow(clippy::too_many_arguments)]
 impl ImageElem {
     /// Decode a raster or vector graphic from bytes or a string.
     ///
@@ -133,8 +163,11 @@ impl ImageElem {
         /// How the image should adjust itself to a given area.
         #[named]
         fit: Option<ImageFit>,
+        /// Whether to show a placeholder if the image can't be decoded.
+        #[named]
+        fallback: Option<bool>,
     ) -> StrResult<Content> {
-        let mut elem = ImageElem::new(EcoString::new(), data);
+        let mut elem = ImageElem::new(EcoString::new(), data, None);
         if let Some(format) = format {
             elem.push_format(format);
         }
@@ -150,6 +183,9 @@ impl ImageElem {
         if let Some(fit) = fit {
             elem.push_fit(fit);
         }
+        if let Some(fallback) = fallback {
+            elem.push_fallback(fallback);
+        }
         Ok(elem.pack().spanned(span))
     }
 }
@@ -162,6 +198,32 @@ impl LayoutSingle for Packed<ImageElem> {
         styles: StyleChain,
         regions: Regions,
     ) -> SourceResult<Frame> {
+        match self.layout_image(engine, styles, regions) {
+            Err(errors) if self.fallback(styles) => {
+                for mut error in errors {
+                    error.severity = Severity::Warning;
+                    error.hint("the image was replaced by a placeholder");
+                    engine.tracer.warn(error);
+                }
+                self.layout_placeholder(engine, styles, regions)
+            }
+            result => result,
+        }
+    }
+}
+
+impl Packed<ImageElem> {
+    /// Load and lay out the image.
+    fn layout_image(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Frame> {
+        if let Some(Spanned { v: message, span }) = self.failure() {
+            return Err(message.clone()).at(*span);
+        }
+
         // Take the format that was explicitly defined, or parse the extension,
         // or try to detect the format.
         let data = self.data();
@@ -261,6 +323,42 @@ impl LayoutSingle for Packed<ImageElem> {
 
         Ok(frame)
     }
+
+    /// Lay out a box with the image's path in place of the image.
+    fn layout_placeholder(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        regions: Regions,
+    ) -> SourceResult<Frame> {
+        let default = Em::new(5.0).resolve(styles);
+        let size = Axes::new(self.width(styles), self.height(styles))
+            .zip_map(regions.base(), |s, r| {
+                s.map_or(default, |v| v.resolve(styles).relative_to(r))
+            });
+
+        let body = TextElem::packed(self.path().clone())
+            .styled(TextElem::set_size(TextSize(Em::new(0.6).into())))
+            .styled(AlignElem::set_alignment(Alignment::Both(
+                HAlignment::Center,
+                VAlignment::Horizon,
+            )));
+
+        let stroke = Stroke::from_pair(Color::GRAY, Abs::pt(0.5).into());
+        let placeholder = BoxElem::new()
+            .with_width(Sizing::Rel(size.x.into()))
+            .with_height(Smart::Custom(size.y.into()))
+            .with_stroke(Sides::splat(Some(Some(stroke))))
+            .with_inset(Sides::splat(Some(Abs::pt(2.0).into())))
+            .with_clip(true)
+            .with_body(Some(body));
+
+        Packed::new(placeholder).spanned(self.span()).layout(
+            engine,
+            styles,
+            Regions::one(size, Axes::splat(true)),
+        )
+    }
 }
 
 impl LocalName for Packed<ImageElem> {
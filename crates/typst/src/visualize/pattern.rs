@@ -207,16 +207,23 @@ impl Pattern {
             );
         }
 
-        Ok(Self(Arc::new(Repr {
+        Ok(Self::from_frame(frame, spacing.v.map(|l| l.abs), relative))
+    }
+}
+
+impl Pattern {
+    /// Create a pattern that tiles an already laid-out frame.
+    ///
+    /// The frame's size is the tile size and must be non-zero.
+    pub fn from_frame(frame: Frame, spacing: Size, relative: Smart<RelativeTo>) -> Self {
+        Self(Arc::new(Repr {
             size: frame.size(),
             frame: Prehashed::new(frame),
-            spacing: spacing.v.map(|l| l.abs),
+            spacing,
             relative,
-        })))
+        }))
     }
-}
 
-impl Pattern {
     /// Set the relative placement of the pattern.
     pub fn with_relative(mut self, relative: RelativeTo) -> Self {
         if let Some(this) = Arc::get_mut(&mut self.0) {
@@ -8,6 +8,7 @@ use crate::layout::{
     Point, Ratio, Regions, Rel, Sides, Size,
 };
 use crate::syntax::Span;
+use crate::text::TextElem;
 use crate::util::Get;
 use crate::visualize::{FixedStroke, Paint, Path, Stroke};
 
@@ -474,6 +475,8 @@ fn layout(
 
         // Pad the child.
         let child = child.clone().padded(inset.map(|side| side.map(Length::from)));
+        let backed = TextElem::backed(&fill, styles);
+        let styles = styles.chain(&backed);
         let expand = sizing.as_ref().map(Smart::is_custom);
         let pod = Regions::one(region, expand);
         frame = child.layout(engine, styles, pod)?.into_frame();
@@ -1,8 +1,10 @@
 #![no_main]
 
 use libfuzzer_sys::fuzz_target;
-use typst_syntax::parse;
+use typst_syntax::{parse, parse_code, parse_math};
 
 fuzz_target!(|text: &str| {
-    std::hint::black_box(parse(text));
+    for root in [parse(text), parse_code(text), parse_math(text)] {
+        assert_eq!(std::hint::black_box(root).len(), text.len());
+    }
 });
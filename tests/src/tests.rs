@@ -75,6 +75,10 @@ struct Args {
     /// Exports the tests as PDF into `tests/pdf`.
     #[arg(long)]
     pdf: bool,
+    /// Lays out each subtest a second time without memoized results and fails
+    /// with a structured diff if the pages differ.
+    #[arg(long)]
+    relayout: bool,
     /// Configuration of what to print.
     #[command(flatten)]
     print: PrintConfig,
@@ -486,6 +490,7 @@ fn test(
                 header_configuration.as_ref().unwrap_or(&Default::default()),
                 &mut rng,
                 args.verbose,
+                args.relayout,
             );
 
             ok &= part_ok;
@@ -503,14 +508,15 @@ fn test(
                 &document,
                 Smart::Custom(&format!("typst-test: {}", name.display())),
                 world.today(Some(0)),
-            );
+            )
+            .unwrap();
             fs::create_dir_all(pdf_path.parent().unwrap()).unwrap();
             fs::write(pdf_path, pdf_data).unwrap();
         }
 
         if world.print.frames {
-            for frame in &document.pages {
-                writeln!(output, "{frame:#?}\n").unwrap();
+            for page in &document.pages {
+                writeln!(output, "{}", page.frame.dump()).unwrap();
             }
         }
 
@@ -596,6 +602,7 @@ fn test_part(
     header_configuration: &TestConfig,
     rng: &mut LinearShift,
     verbose: bool,
+    relayout: bool,
 ) -> (bool, bool, Vec<Page>) {
     let source = world.set(src_path, text);
     if world.print.syntax {
@@ -638,6 +645,9 @@ fn test_part(
             }
             ok &= test_spans(output, source.root());
             ok &= test_reparse(output, source.text(), i, rng);
+            if relayout {
+                ok &= test_relayout(output, world, i, &frames);
+            }
 
             // Don't retain frames if we don't want to compare with reference images.
             if !compare_ref {
@@ -714,6 +724,31 @@ fn test_part(
     }
 }
 
+/// Lays out the current source again without memoized results and reports how
+/// the pages differ from the first layout.
+fn test_relayout(
+    output: &mut String,
+    world: &TestWorld,
+    i: usize,
+    pages: &[Page],
+) -> bool {
+    comemo::evict(0);
+    let fresh = typst::compile(world, &mut Tracer::new())
+        .map(|document| document.pages)
+        .unwrap_or_default();
+
+    let diffs = typst::layout::diff_pages(pages, &fresh);
+    if diffs.is_empty() {
+        return true;
+    }
+
+    writeln!(output, "  Subtest {i} lays out differently without memoization:").unwrap();
+    for diff in diffs {
+        writeln!(output, "    {diff}").unwrap();
+    }
+    false
+}
+
 #[allow(clippy::too_many_arguments)]
 fn test_autocomplete<'a>(
     output: &mut String,
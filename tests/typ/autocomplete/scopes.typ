@@ -0,0 +1,32 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Bindings of enclosing blocks and closure parameters are in scope, but later
+// bindings are not.
+// Autocomplete contains: 4:7 "a", "f", "x", "y", "rest", "b"
+// Autocomplete excludes: 4:7 "d"
+#let a = 1
+#let f(x, y: 2, ..rest) = {
+  let b = 2
+  { x }
+  let d = 4
+}
+
+---
+// Closure parameters are not in scope in their own defaults.
+// Autocomplete excludes: 1:16 "x"
+#let g(x, y: x) = x
+
+---
+// Items of an imported file.
+// Autocomplete contains: -1 "name"
+#import "/typ/compiler/modules/chap1.typ": na
+
+---
+// Parameters of a standard library function.
+// Autocomplete contains: 1:7 "width", "fill", "stroke"
+// Autocomplete contains: 2:19 "fill"
+// Autocomplete excludes: 2:19 "width"
+#rect()
+#rect(width: 1pt, )
@@ -27,6 +27,8 @@
 #test({}, none)
 
 // Let evaluates to none.
+// Warning: 13-14 this shadows the function `v`
+// Hint: 13-14 consider using a different name for this variable
 #test({ let v = 0 }, none)
 
 // Evaluates to single expression.
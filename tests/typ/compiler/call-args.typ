@@ -0,0 +1,77 @@
+// Test that library functions consistently reject missing required and
+// unexpected arguments.
+// Ref: false
+
+---
+// Error: 2-5 missing argument: amount
+#h()
+
+---
+// Error: 9-18 unexpected argument: size
+// Hint: 9-18 the only valid named argument is `weak`
+#v(1em, size: 2pt)
+
+---
+// Error: 14-17 unexpected argument
+#text(red)[A][B]
+
+---
+// Error: 2-10 missing argument: body
+#strong()
+
+---
+// Error: 12-21 unexpected argument: fill
+// Hint: 12-21 the only valid named argument is `seed`
+#lorem(10, fill: red)
+
+---
+// Error: 2-9 missing argument: end
+#range()
+
+---
+// Error: 11-19 unexpected argument: start
+// Hint: 11-19 the only valid named argument is `base`
+#str(1.5, start: 0)
+
+---
+// Error: 2-11 missing argument: key
+#counter()
+
+---
+// Error: 12-15 unexpected argument
+#heading[A][B]
+
+---
+// Error: 14-23 unexpected argument: fit
+// Hint: 14-23 valid named arguments are `left`, `top`, `right`, `bottom`, `x`, `y`, and `rest`
+#pad(x: 1pt, fit: true)[]
+
+---
+// Error: 2-12 expected at least one value
+#calc.max()
+
+---
+// Error: 7-18 unexpected argument: sized
+// Hint: 7-18 did you mean `size`?
+#text(sized: 12pt)[A]
+
+---
+// Error: 11-24 unexpected argument: marginss
+// Hint: 11-24 did you mean `margin`?
+#set page(marginss: 1cm)
+
+---
+// Error: 16-25 unexpected argument: lenght
+// Hint: 16-25 valid named arguments are `at` and `repeat`
+#"a".trim("a", lenght: 1)
+
+---
+// Error: 17-23 unexpected argument: foo
+#calc.pow(2, 3, foo: 1)
+
+---
+// Optional arguments fall back to their defaults without a diagnostic.
+#box()
+#rect()
+#text()[]
+#grid()
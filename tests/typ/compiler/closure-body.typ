@@ -0,0 +1,31 @@
+// Test passing trailing content blocks to user-defined functions.
+
+---
+#let mybox(fill: yellow, body) = box(fill: fill, inset: 3pt, emph(body))
+
+#mybox[Body only] \
+#mybox(fill: aqua)[With arguments] \
+#mybox(fill: aqua, [Inside parentheses])
+
+---
+// Multiple trailing blocks are passed as separate positional arguments.
+#let pair(a, b) = {
+  test(type(a), content)
+  test(b, [B])
+  [#a & #b]
+}
+#pair[A][B]
+
+---
+// Trailing blocks come after positional arguments in parentheses.
+#let tagged(tag, ..rest) = {
+  test(tag, "note")
+  test(rest.pos(), ([Hello],))
+  test(rest.named(), (size: 8pt))
+}
+#tagged("note", size: 8pt)[Hello]
+
+---
+#let f(body) = body
+// Error: 6-9 unexpected argument
+#f[A][B]
@@ -71,6 +71,8 @@
 ---
 // For loop bindings.
 #{
+  // Warning: 7-8 this shadows the function `v`
+  // Hint: 7-8 consider using a different name for this variable
   let v = (1, 2, 3)
   let f() = {
     let s = 0
@@ -27,6 +27,8 @@
   let f(elem) = {
     let func = elem.func()
     if func == text {
+      // Warning: 11-15 this shadows the function `text`
+      // Hint: 11-15 consider using a different name for this variable
       let text = elem.text
       if regex("^\d+$") in text {
         int(text)
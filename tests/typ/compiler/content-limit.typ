@@ -0,0 +1,54 @@
+// Test the limit on the number of elements that repetition, loops, and
+// joining produce.
+// Ref: false
+
+---
+// Just under the limit.
+#let a = ([x] * 1000) * 10000
+#test(type(a), content)
+#test([x] * 0, [])
+
+---
+// Error: 10-30 cannot repeat this content 10001 times (too many elements)
+#let a = ([x] * 1000) * 10001
+
+---
+// Error: 10-36 cannot repeat this content 10000001 times (too many elements)
+#let a = 10000001 * [#text(red)[x]]
+
+---
+// Just under the limit in a loop.
+#let a = for i in range(10) { ([x] * 1000) * 1000 }
+#test(a.children.len(), 10000)
+
+---
+// Error: 29-52 loop produces too much content
+// Hint: 29-52 a loop can produce at most 10000000 elements
+#let a = for i in range(11) { ([x] * 1000) * 1000 }
+
+---
+#let i = 0
+// Error: 23-54 loop produces too much content
+// Hint: 23-54 a loop can produce at most 10000000 elements
+#let a = while i < 11 { i += 1; ([x] * 1000) * 1000 }
+
+---
+// Error: 3-16 cannot repeat this content 10000001 times (too many elements)
+#([] * 10000001)
+
+---
+// Just under the limit when joining.
+#let a = range(10).map(_ => ([x] * 1000) * 1000).join()
+#test(a.children.len(), 10000)
+
+---
+// Error: 10-56 cannot join this content (too many elements)
+#let a = range(11).map(_ => ([x] * 1000) * 1000).join()
+
+---
+// Error: 10-71 cannot join this content (too many elements)
+#let a = range(10).map(_ => ([x] * 1000) * 1000).join([#text(red)[,]])
+
+---
+// Error: 10-55 cannot sum this content (too many elements)
+#let a = range(11).map(_ => ([x] * 1000) * 1000).sum()
@@ -94,6 +94,23 @@
 #dict.remove("b")
 #test(dict.keys(), ("a", "c", "d"))
 
+---
+// Test that iteration follows insertion order, but equality does not.
+#let first = (:)
+#let second = (:)
+#for key in ("x", "b", "m") { first.insert(key, key.len()) }
+#for key in ("m", "x", "b") { second.insert(key, key.len()) }
+#let joined(dict) = {
+  let out = ""
+  for (k, v) in dict { out += k + str(v) }
+  out
+}
+#test(joined(first), "x1b1m1")
+#test(joined(second), "m1x1b1")
+#test(repr(first), "(x: 1, b: 1, m: 1)")
+#test(repr(second), "(m: 1, x: 1, b: 1)")
+#test(first, second)
+
 ---
 // Error: 24-29 duplicate key: first
 #(first: 1, second: 2, first: 3)
@@ -200,6 +200,7 @@
 ---
 // Cyclic import of this very file.
 // Error: 9-23 cyclic import
+// Hint: 9-23 import cycle: typ/compiler/import.typ → typ/compiler/import.typ
 #import "./import.typ"
 
 ---
@@ -0,0 +1,35 @@
+// Test that diagnostics from inside markup point at the original source.
+// Ref: false
+
+---
+// Error: 14-17 unknown variable: foo
+= A heading #foo
+
+---
+// Error: 14-18 unknown variable: item
+- First and #item
+
+---
++ One
+  // Error: 13-16 unknown variable: two
+  + Nested #two
+
+---
+// Error: 4-7 unknown variable: key
+/ #key: The description
+
+---
+// Error: 15-18 unknown variable: bar
+Some *strong #bar* text
+
+---
+// Error: 18-29 cannot add string and integer
+#show raw: it => it.text + 1
+```unknown-lang
+code
+```
+
+---
+// Error: 1:30-1:37 cannot add integer and string
+#set heading(numbering: n => n + "x")
+= Numbered
@@ -130,5 +130,7 @@
 ---
 // Works if we define rect beforehand
 // (since then it doesn't resolve to the standard library version anymore).
+// Warning: 6-10 this shadows the function `rect`
+// Hint: 6-10 consider using a different name for this variable
 #let rect = ""
 #(rect = "hi")
@@ -0,0 +1,70 @@
+// Test arithmetic, comparison, and resolution of relative lengths.
+// Ref: false
+
+---
+// Test that every operator keeps both components.
+#let rel = 50% + 2cm
+#for (result, expected) in (
+  (-rel, -50% - 2cm),
+  (rel + 1cm, 50% + 3cm),
+  (rel + 10%, 60% + 2cm),
+  (rel + (10% + 1cm), 60% + 3cm),
+  (rel - 1cm, 50% + 1cm),
+  (rel - 10%, 40% + 2cm),
+  (rel - (10% + 1cm), 40% + 1cm),
+  (rel * 2, 100% + 4cm),
+  (rel * 0.5, 25% + 1cm),
+  (rel * 50%, 25% + 1cm),
+  (2 * rel, 100% + 4cm),
+  (0.5 * rel, 25% + 1cm),
+  (50% * rel, 25% + 1cm),
+  (rel / 2, 25% + 1cm),
+  (rel / 0.5, 100% + 4cm),
+) {
+  test(result, expected)
+  test(result.ratio, expected.ratio)
+  test(result.length, expected.length)
+}
+
+---
+// Test division of relative lengths.
+#test((20% + 0pt) / (10% + 0pt), 2.0)
+#test((3pt + 0%) / (1pt + 0%), 3.0)
+#test(4cm / (2cm + 0%), 2.0)
+#test((40% + 0pt) / 10%, 4.0)
+
+---
+// Error: 3-28 cannot divide these two relative lengths
+#((50% + 1cm) / (10% + 1cm))
+
+---
+// Test printing.
+#test(repr(50% + 2pt), "50% + 2pt")
+#test(repr(50% - 2pt), "50% + -2pt")
+#test(str(repr(100% + 0pt)), "100% + 0pt")
+
+---
+// Test equality and ordering.
+#test(50% + 2cm == 50% + 2cm, true)
+#test(50% + 2cm == 50% + 3cm, false)
+#test(50% + 2cm != 40% + 2cm, true)
+#test(2cm + 0% < 3cm + 0%, true)
+#test(20% + 0pt > 10% + 0pt, true)
+#test(2cm + 0% <= 2cm, true)
+
+---
+// Error: 3-20 cannot compare relative length and length
+#((50% + 2cm) < 3cm)
+
+---
+// Error: 3-28 cannot compare 50% + 2pt with 40% + 1pt
+#((50% + 2pt) > (40% + 1pt))
+
+---
+// Test resolving against a base length.
+#test((100% - 2pt).resolve(20pt), 18pt)
+#test((50% + 1pt).resolve(10pt), 6pt)
+#test(relative.resolve(25% + 1pt, 40pt), 11pt)
+#test((0% + 3pt).resolve(1000cm), 3pt)
+#test((50% + 1em).resolve(10pt), 5pt + 1em)
+#test((100% - 2cm).resolve(20cm) < 19cm, true)
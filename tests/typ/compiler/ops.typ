@@ -128,6 +128,27 @@
 // Error: 2-8 invalid hexadecimal number: 0x123z
 #0x123z
 
+---
+// Error: 2-10 number is too large: 1e999999
+#1e999999
+
+---
+// Error: 3-10 number is too large: 1e400
+#(1e400pt)
+
+---
+// Long flat chains are not limited by the nesting depth, but by a more
+// generous limit of their own.
+#test(eval(range(500).map(str).join(" + ")), 124750)
+#let nested = none
+#for _ in range(500) { nested = (inner: nested) }
+#test(eval("nested" + ".inner" * 500, scope: (nested: nested)), none)
+#test(eval("(" * 250 + range(500).map(str).join(" + ") + ")" * 250), 124750)
+
+---
+// Error: 7-38 maximum nesting depth exceeded
+#eval(range(600).map(str).join(" + "))
+
 ---
 // Test that multiplying infinite numbers by certain units does not crash.
 #(float("inf") * 1pt)
@@ -165,6 +165,68 @@ test
 
 #for c in cases {
   assert.eq(c.text, c.input.text, message: "in point " + c.name + ", expect " + repr(c.text) + ", got " + repr(c.input.text) + "")
+  // Warning: 7-12 this shadows the function `block`
+  // Hint: 7-12 consider using a different name for this variable
   let block = c.at("block", default: false)
   assert.eq(block, c.input.block, message: "in point " + c.name + ", expect " + repr(block) + ", got " + repr(c.input.block) + "")
 }
+
+---
+// Fences with one, two, and four backticks.
+#let cases = (
+  (input: ` code `, text: " code "),
+  (input: ``a`b``, text: "a`b"),
+  // A single space next to a backtick is trimmed.
+  (input: `` `a` ``, text: "`a`"),
+  (input: `` `a ``, text: "`a "),
+  (input: `` a`b ``, text: " a`b "),
+  // Two backticks have no language tag.
+  (input: ``js a``, text: "js a"),
+  // Longer runs of backticks don't close the fence.
+  (input: ``a```b``, text: "a```b"),
+  (input: ````typ ```a``` ````, text: "```a```", lang: "typ"),
+)
+
+#for c in cases {
+  test(c.input.text, c.text)
+  test(c.input.at("lang", default: none), c.at("lang", default: none))
+  test(c.input.block, false)
+}
+
+---
+// Indented raw blocks keep the relative indentation of their lines.
+#show raw.where(block: true): it => {
+  test(it.lang, "py")
+  test(it.text, "def fib(n):\n    if n < 2:\n        return n\n\n    return fib(n - 1) + fib(n - 2)")
+  test(it.lines.len(), 5)
+  it
+}
+
+- Some Python:
+  #block[
+    ```py
+    def fib(n):
+        if n < 2:
+            return n
+
+        return fib(n - 1) + fib(n - 2)
+    ```
+  ]
+
+---
+// Once a raw block spans multiple lines, only a fence at the start of a line
+// closes it.
+#let fenced = ```
+let fence = "```"
+  ```
+#test(fenced.text, "let fence = \"```\"")
+#test(```js x```.text, "x")
+
+---
+// An unclosed fence is an error on its own, and the text after it is parsed
+// as usual.
+// Error: 1-4 unclosed raw text
+```js
+// Error: 6 expected expression
+#(1 +
+)
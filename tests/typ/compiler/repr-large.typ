@@ -0,0 +1,33 @@
+// Test the representation of large and nested values.
+// Ref: false
+
+---
+// Long arrays and dictionaries are truncated.
+#let r = repr(range(100))
+#test(r.starts-with("(\n  0,\n  1,"), true)
+#test(r.ends-with("39,\n  .. (60 items omitted),\n)"), true)
+
+#let d = (:)
+#for i in range(50) { d.insert(str(i), i) }
+#test(repr(d).ends-with("39\": 39,\n  .. (10 pairs omitted),\n)"), true)
+
+---
+// Short values stay on one line.
+#test(repr(range(5)), "(0, 1, 2, 3, 4)")
+#test(repr((a: (1, 2), b: (c: "d"))), "(a: (1, 2), b: (c: \"d\"))")
+
+---
+// Dictionaries keep their insertion order, so the output is deterministic.
+#let d = (zeta: 1, alpha: 2, mu: 3)
+#test(repr(d), "(zeta: 1, alpha: 2, mu: 3)")
+#test(repr(d), repr((zeta: 1, alpha: 2, mu: 3)))
+#d.insert("beta", 4)
+#test(repr(d), "(zeta: 1, alpha: 2, mu: 3, beta: 4)")
+
+---
+// Floats use the shortest representation that round-trips.
+#test(repr(0.1), "0.1")
+#test(repr(0.1 + 0.2), "0.30000000000000004")
+#test(repr(1 / 3), "0.3333333333333333")
+#test(repr(2.0), "2.0")
+#test(float(repr(1 / 3)), 1 / 3)
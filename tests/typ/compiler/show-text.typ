@@ -91,3 +91,45 @@ World
 #show "GRAPH": image("/assets/images/graph.png")
 
 The GRAPH has nodes.
+
+---
+// Test that every match is transformed and the text around it is kept.
+// Ref: false
+#let found = state("found", ())
+#show regex("\d+"): it => found.update(v => v + (it.text,)) + strong(it)
+#let body = [In 2024, 3 of 10 users wrote x2y3.]
+#body
+#context test(found.final(), ("2024", "3", "10", "2", "3"))
+#context test(measure(body).width > measure[In , of users wrote xy.].width, true)
+
+---
+// Text show rules from outside don't apply to raw text by default, but those
+// defined in a show rule for raw do.
+// Ref: false
+#let count = state("count", 0)
+#show "Typst": it => count.update(n => n + 1) + it
+Typst `Typst`
+#show raw: it => {
+  show "Typst": it => count.update(n => n + 10) + it
+  it
+}
+`Typst`
+#context test(count.final(), 11)
+
+---
+// With `text-rules` enabled, text show rules apply to raw text.
+// Ref: false
+#let count = state("count", 0)
+#show "Typst": it => count.update(n => n + 1) + it
+#set raw(text-rules: true)
+Typst `Typst`
+#context test(count.final(), 2)
+
+---
+// The number of matches transformed in a run of text is limited.
+// Ref: false
+#let body = "a" * 10005
+#show "a": "b"
+// Warning: 2-6 text show rule matched too often
+// Hint: 2-6 only the first 10000 matches were transformed
+#body
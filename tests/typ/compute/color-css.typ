@@ -0,0 +1,35 @@
+// Test the named CSS colors.
+// Ref: false
+
+---
+#test(color.css.red, rgb("#ff0000"))
+#test(color.css.cornflowerblue, rgb("#6495ed"))
+#test(color.css.rebeccapurple, rgb(102, 51, 153))
+#test(color.css.darkslategray, rgb("#2f4f4f"))
+#test(color.css.papayawhip, rgb("#ffefd5"))
+#test(color.css.black, rgb("#000000"))
+#test(color.css.white, rgb("#ffffff"))
+
+---
+// Spelling variants and aliases.
+#test(color.css.gray, color.css.grey)
+#test(color.css.lightgray, color.css.lightgrey)
+#test(color.css.darkslategray, color.css.darkslategrey)
+#test(color.css.aqua, color.css.cyan)
+#test(color.css.fuchsia, color.css.magenta)
+
+---
+// The predefined colors are unaffected.
+#test(red, rgb("#ff4136"))
+#test(color.css.red == red, false)
+
+---
+// Named colors can be shadowed freely.
+#let tan = 1
+#let css = color.css
+#test(css.tan, rgb("#d2b48c"))
+#text(fill: css.tomato)[Tomato]
+
+---
+// Error: 12-19 module `css` does not contain `reddish`
+#color.css.reddish
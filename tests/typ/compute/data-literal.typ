@@ -0,0 +1,16 @@
+// Test that strings from data files are shown literally.
+
+---
+// Markup characters in data strings are never parsed.
+#let data = csv.decode("name,note\n#let x = 1,*not* _bold_ `raw` $x$")
+#let (name, note) = data.at(1)
+#test(name, "#let x = 1")
+#test([#note].text, "*not* _bold_ `raw` $x$")
+#name \
+#note
+
+---
+// Re-interpretation must be explicit.
+#let note = "*bold* and $x^2$"
+#test(eval("1 + 2"), 3)
+#eval(note, mode: "markup")
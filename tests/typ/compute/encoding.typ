@@ -0,0 +1,44 @@
+// Test binary encodings and hashes.
+// Ref: false
+
+---
+// Test Base64.
+#test(encoding.base64.encode("Hello"), "SGVsbG8=")
+#test(encoding.base64.encode(bytes(())), "")
+#test(str(encoding.base64.decode("SGVsbG8=")), "Hello")
+#test(encoding.base64.decode(bytes("AP8=")), bytes((0, 255)))
+
+#let data = bytes(range(256))
+#test(encoding.base64.decode(encoding.base64.encode(data)), data)
+#test(encoding.base64.decode(encoding.base64.encode(data.slice(7, 42))), data.slice(7, 42))
+
+---
+// Error: 25-31 failed to decode Base64 (Invalid symbol 33, offset 2.)
+#encoding.base64.decode("ab!d")
+
+---
+// Test hexadecimal.
+#test(encoding.hex.encode("Hi"), "4869")
+#test(encoding.hex.encode(bytes((0, 15, 16, 255))), "000f10ff")
+#test(encoding.hex.decode("000F10ff"), bytes((0, 15, 16, 255)))
+#test(encoding.hex.decode(""), bytes(()))
+#test(encoding.hex.decode(encoding.hex.encode(bytes(range(256)))), bytes(range(256)))
+
+---
+// Error: 22-27 failed to decode hexadecimal (odd number of digits)
+#encoding.hex.decode("abc")
+
+---
+// Error: 22-28 failed to decode hexadecimal (invalid digit 'g')
+#encoding.hex.decode("0g00")
+
+---
+// Test SHA-256 with the vectors from FIPS 180-4.
+#test(encoding.hash.sha256(""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+#test(encoding.hash.sha256("abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+#test(
+  encoding.hash.sha256("abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+  "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+)
+#test(encoding.hash.sha256(bytes("abc")), encoding.hash.sha256("abc"))
+#test(encoding.hash.sha256("a" * 1000).len(), 64)
@@ -0,0 +1,45 @@
+// Test seeded random number generation.
+// Ref: false
+
+---
+// Test that a seed yields a fixed sequence.
+#let r = rng(42)
+#let rolls = ()
+#for _ in range(5) {
+  let (next, roll) = r.int(1, 7)
+  rolls.push(roll)
+  r = next
+}
+#test(rolls, (1, 1, 6, 6, 5))
+
+---
+// Test that the same seed yields the same values.
+#test(rng(3).int(0, 1000).at(1), rng(3).int(0, 1000).at(1))
+#test(type(rng(3)), rng)
+#test(repr(rng(3)), "rng(..)")
+
+---
+// Test floats.
+#let (_, x) = rng(5).float()
+#test(x, 0.2884112281702357)
+
+---
+// Test picking and shuffling.
+#let r = rng(3)
+#let picks = ()
+#for _ in range(4) {
+  let (next, pick) = r.pick(("a", "b", "c"))
+  picks.push(pick)
+  r = next
+}
+#test(picks, ("c", "b", "c", "b"))
+#test(rng(1).shuffle((1, 2, 3, 4, 5)).at(1), (1, 2, 4, 5, 3))
+#test(rng(1).shuffle(()).at(1), ())
+
+---
+// Error: 2-18 lower bound must be smaller than upper bound
+#rng(1).int(5, 5)
+
+---
+// Error: 2-17 cannot pick from an empty array
+#rng(1).pick(())
@@ -110,3 +110,38 @@ This is a normal page. Very normal.
 #place[OOF]
 #colbreak()
 In flow.
+
+---
+// Test that content after a column break starts at the top of the next
+// column and that weak column breaks collapse at the start of a column.
+// Ref: false
+#set page(height: 2cm, width: 6cm, columns: 2)
+#let pos(label) = locate(label).position()
+
+#metadata(none) <a>
+A
+#colbreak()
+#colbreak(weak: true)
+#metadata(none) <b>
+B
+#v(1fr)
+#colbreak(weak: true)
+#metadata(none) <c>
+C
+
+#context {
+  let (a, b, c) = (pos(<a>), pos(<b>), pos(<c>))
+  test((a.page, b.page, c.page), (1, 1, 2))
+  test(b.y, a.y)
+  assert(b.x > a.x)
+  test((c.x, c.y), (a.x, a.y))
+}
+
+---
+// Test that a weak column break at the start of the page is skipped.
+// Ref: false
+#set page(height: 2cm, width: 6cm, columns: 2)
+#colbreak(weak: true)
+#metadata(none) <first>
+A
+#context test(locate(<first>).position().x, 10pt)
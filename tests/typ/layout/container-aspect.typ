@@ -0,0 +1,58 @@
+// Test boxes with an aspect ratio.
+// Ref: false
+
+---
+// The height is derived from the width.
+#context {
+  test(measure(box(aspect: 2, width: 40pt)), (width: 40pt, height: 20pt))
+  test(
+    measure(block(width: 64pt, box(aspect: 16/9, width: 100%))),
+    (width: 64pt, height: 36pt),
+  )
+}
+
+---
+// The width is derived from the height.
+#context {
+  test(measure(box(aspect: 16/9, height: 9pt)), (width: 16pt, height: 9pt))
+  test(measure(box(aspect: 0.5, height: 30pt)[Hi]).width, 15pt)
+}
+
+---
+// Without a width or height, the available width is used.
+#set page(width: 120pt, margin: 10pt)
+#box(aspect: 4)[#metadata(none)<top>]
+#metadata(none)<below>
+#context test(
+  locate(<below>).position().y - locate(<top>).position().y >= 25pt,
+  true,
+)
+
+---
+// Nothing is derived from an unbounded width.
+#context {
+  test(measure(box(aspect: 2)[Hi]), measure(box[Hi]))
+  test(measure(box(aspect: 2, height: 10pt)).width, 20pt)
+}
+
+---
+// Boxes that are too large are scaled down.
+#context {
+  test(
+    measure(block(width: 50pt, box(aspect: 1, width: 100pt))),
+    (width: 50pt, height: 50pt),
+  )
+}
+
+---
+// Warning: 2-43 aspect ratio is ignored
+// Hint: 2-43 the box already has a width and a height
+#box(aspect: 1, width: 10pt, height: 20pt)
+
+---
+// Error: 14-16 aspect ratio must be positive
+#box(aspect: -1, width: 1cm)
+
+---
+// Error: 18-19 aspect ratio must be positive
+#set box(aspect: 0)
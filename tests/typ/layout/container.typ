@@ -48,3 +48,13 @@ First!
   But, soft! what light through yonder window breaks? It is the east, and Juliet
   is the sun.
 ]
+
+---
+// Test that negative sizes are clamped to zero.
+// Ref: false
+// Warning: 2-39 box has a negative size
+// Hint: 2-39 negative widths and heights are treated as zero
+#box(width: 1cm - 3cm, height: 1cm)[A]
+// Warning: 2-28 block has a negative size
+// Hint: 2-28 negative widths and heights are treated as zero
+#block(height: -5pt)[Hello]
@@ -15,6 +15,27 @@
 + First
   + Nested
 
+---
+// Test full numbering three levels deep. Nested counters restart for
+// each parent item.
+#set enum(numbering: "1.a.i.", full: true)
++ One
+  + A
++ Two
+  + A
+  + B
+    + I
+    + II
+    + III
+
+---
+// Test a custom start with an explicit number further down.
+#set enum(start: 5)
++ Five
++ Six
+12. Twelve
++ Thirteen
+
 ---
 // Test numbering with closure.
 #enum(
@@ -0,0 +1,42 @@
+// Test grid gutters and per-cell alignment.
+
+---
+// Array gutters and a closure that right-aligns the last column.
+#set page(width: 140pt)
+#grid(
+  columns: (auto, 1fr, auto),
+  column-gutter: (4pt, 12pt),
+  row-gutter: (2pt, 8pt),
+  align: (x, y) => if x == 2 { right } else { left },
+  fill: (x, y) => if calc.even(y) { luma(230) },
+  [Item], [Description], [Price],
+  [Tea], [Green], [2.50],
+  [Cake], [Chocolate], [12.00],
+)
+
+---
+// Fractional columns share the width left after gutters.
+#set page(width: 100pt)
+#grid(
+  columns: (1fr, 1fr),
+  column-gutter: 20pt,
+  ..range(2).map(i => layout(size => {
+    test(size.width, 30pt)
+    box(width: 100%, height: 6pt, fill: aqua)
+  })),
+)
+
+---
+// No gutter is left dangling at the end of a region.
+#set page(height: 60pt)
+#grid(
+  columns: 1,
+  row-gutter: 10pt,
+  ..range(6).map(i => rect(width: 100%, height: 12pt, fill: teal)[#i]),
+)
+
+---
+// A single alignment and an array of alignments.
+#set page(width: 120pt)
+#grid(columns: (1fr,) * 3, align: center, [a], [b], [c])
+#grid(columns: (1fr,) * 3, align: (left, center, right), [a], [b], [c])
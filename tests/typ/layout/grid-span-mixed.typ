@@ -0,0 +1,30 @@
+// Test a header spanning all columns next to a row-spanning cell.
+
+---
+#set page(width: 160pt)
+#table(
+  columns: 3,
+  table.cell(colspan: 3, align: center)[*Quarterly results*],
+  table.cell(rowspan: 2, align: horizon)[2024],
+  [Q1], [10],
+  [Q2], [12],
+  [2025], [Q1], [14],
+)
+
+---
+// Cells after a row span skip the covered position.
+#table(
+  columns: 2,
+  table.cell(rowspan: 2)[A], [B],
+  table.cell(x: 1, y: 1)[C],
+  [D], [E],
+)
+
+---
+// A spanning cell's width is distributed over the auto columns it spans.
+#set page(width: auto)
+#table(
+  columns: (auto, auto),
+  table.cell(colspan: 2, box(width: 80pt, height: 8pt, fill: aqua)),
+  [a], [b],
+)
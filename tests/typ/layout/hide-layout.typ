@@ -0,0 +1,31 @@
+// Test that hidden content takes up exactly the space of visible content.
+// Ref: false
+
+---
+#set page(width: 120pt, height: auto)
+#let body(wrap) = [
+  #metadata(none)<pos> Some #wrap[hidden] text
+  #metadata(none)<pos> that #wrap[#box(width: 1cm, height: 1cm)] breaks
+  #metadata(none)<pos> over lines.
+
+  #wrap[= A heading]
+  #metadata(none)<pos> #wrap(lorem(8)) After.
+  #wrap(table(columns: 2)[A][B])
+  #metadata(none)<pos>
+]
+
+#body(it => it)
+#pagebreak()
+#body(hide)
+
+#context {
+  let positions = query(<pos>).map(it => it.location().position())
+  let n = calc.quo(positions.len(), 2)
+  test(n, 5)
+  for i in range(n) {
+    let (visible, hidden) = (positions.at(i), positions.at(n + i))
+    test(hidden.page, visible.page + 1)
+    test(hidden.x, visible.x)
+    test(hidden.y, visible.y)
+  }
+}
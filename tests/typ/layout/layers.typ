@@ -0,0 +1,58 @@
+// Test layered content.
+// Ref: false
+
+---
+// The composite is as large as the largest layer.
+#context {
+  let size = measure(layers(
+    box(width: 30pt, height: 10pt),
+    box(width: 10pt, height: 40pt),
+  ))
+  test(size, (width: 30pt, height: 40pt))
+  test(measure(layers()), (width: 0pt, height: 0pt))
+}
+
+---
+// Text over a filled rectangle stays within the rectangle's bounds.
+#set page(width: 100pt, height: auto, margin: 0pt)
+#layers(
+  align: center + horizon,
+  rect(width: 100%, height: 40pt, fill: aqua),
+  [#box[DRAFT]<draft>],
+)
+
+#context {
+  let pos = locate(<draft>).position()
+  test(measure(layers(rect(width: 100pt, height: 40pt), [DRAFT])).height, 40pt)
+  test(pos.x > 0pt and pos.x < 50pt, true)
+  test(pos.y > 0pt and pos.y < 20pt, true)
+}
+
+---
+// Smaller layers are aligned within the largest one.
+#set page(width: auto, height: auto, margin: 0pt)
+#layers(
+  align: bottom + right,
+  square(size: 20pt, fill: red),
+  box(width: 5pt, height: 5pt, fill: green)[#metadata(none)<small>],
+)
+#context test(locate(<small>).position(), (page: 1, x: 15pt, y: 15pt))
+
+---
+// Layers move to the next page as a whole.
+#set page(height: 100pt, margin: 0pt)
+#v(60pt)
+#layers(block(height: 50pt, width: 100%, fill: aqua), [Top<top>])
+#context test(locate(<top>).page(), 2)
+
+---
+// Layers that don't fit into a page overflow instead of breaking.
+#set page(height: 100pt, margin: 0pt)
+// Warning: 2-70 layers do not fit into the available space
+// Hint: 2-70 layers are not broken across pages
+#layers(align: bottom, block(height: 150pt, width: 100%), [Top<top>])
+#context {
+  let pos = locate(<top>).position()
+  test(pos.page, 1)
+  test(pos.y > 100pt, true)
+}
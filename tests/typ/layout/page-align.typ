@@ -0,0 +1,38 @@
+// Test aligning the page's content as a whole.
+
+---
+#set page(width: 100pt, height: 100pt, margin: 10pt, align: center + horizon)
+#set text(size: 10pt)
+#metadata(none) <start>
+Title
+#context {
+  let pos = locate(<start>).position()
+  let size = measure[Title]
+  test(pos.x, 50pt - size.width / 2)
+  test(calc.abs((pos.y - 50pt + size.height / 2) / 1pt) < 3, true)
+}
+
+---
+// The text within the content keeps its own alignment and headers and
+// footers don't move.
+#set page(
+  width: 120pt,
+  height: 100pt,
+  align: right + bottom,
+  header: [Header],
+  footer: [Footer],
+)
+A short line \
+and a longer line
+
+---
+// Fractional spacing still fills the page.
+#set page(width: 100pt, height: 80pt, align: center + horizon)
+Top
+#v(1fr)
+Bottom
+
+---
+// Content that spans multiple pages is aligned on each of them.
+#set page(width: 100pt, height: 60pt, align: center)
+#lorem(20)
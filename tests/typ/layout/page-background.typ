@@ -0,0 +1,37 @@
+// Test page backgrounds and foregrounds.
+
+---
+// The background is painted behind the body and the foreground on top of it.
+#set page(
+  width: 120pt,
+  height: 80pt,
+  margin: 20pt,
+  background: rect(width: 100%, height: 100%, fill: aqua),
+  foreground: align(center + horizon, rotate(-20deg, text(20pt, red.transparentize(40%))[DRAFT])),
+)
+#rect(width: 100%, height: 100%, fill: white)[Body]
+
+---
+// The background spans the physical page, not the margin box.
+#set page(
+  width: 100pt,
+  height: 60pt,
+  margin: (x: 10pt, y: 20pt),
+  background: layout(size => {
+    test(size.width, 100pt)
+    test(size.height, 60pt)
+  }),
+  foreground: layout(size => test(size, (width: 100pt, height: 60pt))),
+)
+Body
+
+---
+// Backgrounds are laid out once per page and can access the page number.
+#set page(
+  width: 60pt,
+  height: 40pt,
+  background: context place(bottom + right, dx: -4pt, dy: -4pt, text(8pt, gray)[#counter(page).get().first()]),
+)
+A
+#pagebreak()
+B
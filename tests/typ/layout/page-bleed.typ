@@ -0,0 +1,28 @@
+// Test page bleed and crop marks.
+
+---
+// The fill and background extend into the bleed, but the body keeps its
+// area.
+#set page(
+  width: 100pt,
+  height: 60pt,
+  margin: 10pt,
+  bleed: 8pt,
+  fill: aqua,
+  background: layout(size => {
+    test(size, (width: 116pt, height: 76pt))
+    rect(width: 100%, height: 100%, stroke: 2pt + eastern)
+  }),
+)
+#layout(size => test(size, (width: 80pt, height: 40pt)))
+#place(rect(width: 100%, height: 100%, fill: white))
+
+---
+// Crop marks are drawn around the bleed.
+#set page(width: 100pt, height: 60pt, margin: 10pt, bleed: 6pt, fill: aqua, marks: true)
+Trimmed
+
+---
+// Crop marks work without a bleed.
+#set page(width: 100pt, height: 60pt, margin: 10pt, marks: true)
+#rect(width: 100%, height: 100%)
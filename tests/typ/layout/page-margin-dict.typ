@@ -0,0 +1,28 @@
+// Test margin dictionaries.
+// Ref: false
+
+---
+// Test how margin dictionaries resolve to sides.
+#let resolved(margin) = (top: margin.top, right: margin.right, bottom: margin.bottom, left: margin.left)
+#set page(height: 40pt, margin: (x: 4pt, y: 8pt))
+#context test(resolved(page.margin), (top: 8pt, right: 4pt, bottom: 8pt, left: 4pt))
+#set page(margin: (rest: 3pt, top: 5pt, x: 4pt))
+#context test(resolved(page.margin), (top: 5pt, right: 4pt, bottom: 3pt, left: 4pt))
+#set page(margin: (bottom: auto))
+#context test(page.margin.bottom, auto)
+#set page(margin: 6pt)
+#context test(page.margin, 6pt)
+#set page(margin: (inside: 1pt, outside: 2pt))
+#context test(page.margin, (top: 6pt, bottom: 6pt, inside: 1pt, outside: 2pt))
+
+---
+// Error: 19-43 `inside` and `outside` are mutually exclusive with `left` and `right`
+#set page(margin: (inside: 1cm, left: 2cm))
+
+---
+// Error: 19-44 `inside` and `outside` are mutually exclusive with `left` and `right`
+#set page(margin: (right: 1cm, inside: 2cm))
+
+---
+// Error: 19-32 unexpected key "middle", valid keys are "left", "top", "right", "bottom", "outside", "inside", "x", "y", and "rest"
+#set page(margin: (middle: 1cm))
@@ -18,3 +18,8 @@
 
 // Ensure that specific margins override general margins.
 #[#set page(margin: (rest: 0pt, left: 20pt)); Overridden]
+
+---
+// Error: 2-40 page margins exceed the page size
+// Hint: 2-40 reduce the margins or increase the page size
+#page(width: 40pt, margin: 30pt)[Hello]
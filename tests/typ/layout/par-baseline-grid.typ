@@ -0,0 +1,56 @@
+// Test snapping lines to a baseline grid.
+
+---
+// Test that baselines in two columns with different content line up.
+#set page(width: 200pt, height: 150pt, margin: 10pt, columns: 2)
+#set par(baseline-grid: 14pt)
+#let m = box(metadata("baseline"))
+
+= Heading
+A#m line of text that#m breaks into multiple#m lines.
+
+#colbreak()
+#text(1.5em)[Big#m text]
+#rect(height: 13pt)
+Small#m text after a#m rectangle.
+#context {
+  let ys = query(metadata).map(it => it.location().position().y)
+  test(ys.len(), 6)
+  for y in ys {
+    test(calc.rem((y - 10pt).pt(), 14), 0)
+  }
+}
+
+---
+// Test that a heading can opt out of snapping.
+#set page(width: 120pt, height: auto, margin: 10pt)
+#set par(baseline-grid: 14pt)
+#set heading(grid-snap: false)
+#let m = box(metadata("baseline"))
+
+= Heading#m
+Text#m on the grid.
+#context {
+  let ys = query(metadata).map(it => it.location().position().y - 10pt)
+  test(calc.rem(ys.first().pt(), 14) != 0, true)
+  test(calc.rem(ys.last().pt(), 14), 0)
+}
+
+---
+// Test that paragraphs nested in a list don't snap, but the paragraph after
+// the list does.
+#set page(width: 120pt, height: auto, margin: 10pt)
+#set par(baseline-grid: 14pt, leading: 0.4em)
+#let m = box(metadata("baseline"))
+
+Before#m the list.
+- Item#m
+- Item#m
+After#m the list.
+#context {
+  let ys = query(metadata).map(it => it.location().position().y - 10pt)
+  test(ys.len(), 4)
+  test(calc.rem(ys.first().pt(), 14), 0)
+  test(calc.rem(ys.at(2).pt(), 14) != 0, true)
+  test(calc.rem(ys.last().pt(), 14), 0)
+}
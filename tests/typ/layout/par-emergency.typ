@@ -0,0 +1,52 @@
+// Test breaking words that are too long to fit into a line.
+// Ref: false
+
+---
+#set page(width: 3cm, height: auto, margin: 0pt)
+#set par(break-words: true)
+#let dna = range(125).map(_ => "ACGT").join()
+// Warning: 2-5 word is too long to fit into a line
+// Hint: 2-5 it was broken at an arbitrary position
+#dna#box[]<end>
+
+#context {
+  test(par.line-count(dna, 3cm) > 10, true)
+  test(locate(<end>).position().x <= 3cm, true)
+}
+
+---
+// Without `break-words`, long words overflow.
+#set page(width: 3cm, height: auto, margin: 0pt)
+#let dna = range(125).map(_ => "ACGT").join()
+#context test(par.line-count(dna, 3cm), 1)
+
+---
+// Words that fit are not broken.
+#set page(width: 3cm, height: auto, margin: 0pt)
+#set par(break-words: true)
+#context test(par.line-count([Short words stay intact.], 3cm), 2)
+
+---
+// An unbreakable box that is wider than the region overflows.
+#set page(width: 3cm, height: auto, margin: 0pt)
+#set par(break-words: true)
+#context test(par.line-count([A #box(width: 4cm) B], 3cm), 3)
+
+---
+// Justified text without hyphenation.
+#set page(width: 3cm, height: auto, margin: 0pt)
+#set par(justify: true, break-words: true)
+#set text(hyphenate: false)
+#let dna = range(40).map(_ => "ACGT").join()
+// Warning: 8-11 word is too long to fit into a line
+// Hint: 8-11 it was broken at an arbitrary position
+Start #dna end.
+
+---
+// Long words are kept intact while measuring, e.g. in auto-sized boxes.
+#set page(width: 3cm, height: auto, margin: 0pt)
+#set par(break-words: true)
+#let word = range(10).map(_ => "ACGT").join()
+#context test(measure(box(word)).height, measure(word).height)
+#box[#word#metadata(none)<inside>]
+#context test(locate(<inside>).position().x > 3cm, true)
@@ -0,0 +1,63 @@
+// Test counting lines and fitting content into a box.
+// Ref: false
+
+---
+#set page(width: 200pt)
+#context {
+  test(par.line-count([], 100pt), 0)
+  test(par.line-count([Hello], 100pt), 1)
+}
+
+---
+// Narrower widths break into more lines.
+#let body = lorem(20)
+#context {
+  let wide = par.line-count(body, 1000pt)
+  let narrow = par.line-count(body, 60pt)
+  test(wide, 1)
+  test(narrow > 4, true)
+}
+
+---
+// Forced breaks and multiple paragraphs.
+#context {
+  test(par.line-count([A \ B \ C], 100pt), 3)
+  test(par.line-count([A #parbreak() B], 100pt), 2)
+  test(par.line-count(par[A] + par[B], 100pt), 2)
+}
+
+---
+// The current text state is taken into account.
+#let body = lorem(10)
+#context {
+  let small = par.line-count(body, 100pt)
+  set text(20pt)
+  context test(par.line-count(body, 100pt) > small, true)
+}
+
+---
+// Shrink the font until the text fits into a fixed box.
+#let body = lorem(40)
+#context {
+  let size = 20pt
+  while not par.fits(text(size, body), 150pt, 80pt) {
+    size -= 1pt
+  }
+  test(size < 20pt, true)
+  test(par.fits(text(size, body), 150pt, 80pt), true)
+  test(par.fits(text(size + 1pt, body), 150pt, 80pt), false)
+  box(width: 150pt, height: 80pt, text(size, body))
+}
+
+---
+// Content that is too wide doesn't fit.
+#context {
+  test(par.fits(block(width: 50pt), 40pt, 100pt), false)
+  test(par.fits(block(width: 50pt), 60pt, 100pt), true)
+}
+
+---
+// Error: 2-25 can only be used when context is known
+// Hint: 2-25 try wrapping this in a `context` expression
+// Hint: 2-25 the `context` expression should wrap everything that depends on this function
+#par.fits([A], 1pt, 1pt)
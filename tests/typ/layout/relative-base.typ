@@ -0,0 +1,26 @@
+// Test that relative sizes resolve against the current region, not the page.
+// Ref: false
+
+---
+// Test padding.
+#block(width: 200pt, pad(x: 20pt, layout(size => test(size.width, 160pt))))
+
+---
+// Test nested percentages.
+#block(width: 200pt)[
+  #box(width: 50%, box(width: 50%, layout(size => test(size.width, 50pt))))
+]
+
+---
+// Test percentages under padding.
+#block(width: 200pt, pad(left: 40pt, block(width: 50%, layout(size => {
+  test(size.width, 80pt)
+}))))
+
+---
+// Test percentages in columns.
+#set page(width: 220pt, margin: 0pt)
+#columns(2, gutter: 20pt)[
+  #block(width: 50%, layout(size => test(size.width, 50pt)))
+]
+
@@ -0,0 +1,35 @@
+// Test negative spacing.
+
+---
+// Negative vertical spacing adds to the block spacing and can make blocks
+// overlap.
+#set page(height: 40pt, margin: 0pt)
+#set block(spacing: 12pt)
+#let probe(y, fill) = block(width: 20pt, height: 10pt, fill: fill, context {
+  test(here().position().y, y)
+})
+#probe(0pt, aqua)
+#v(-15pt)
+#probe(7pt, teal.transparentize(50%))
+#v(-2pt)
+#probe(27pt, eastern)
+
+---
+// Negative horizontal spacing tightens the gap between words.
+#set page(width: auto)
+#context test(measure[A #h(-2pt) B].width, measure[A B].width - 2pt)
+A B \
+A #h(-2pt) B \
+A#h(-8pt)B
+
+---
+// Negative spacing can pull content above the start of the region, but not
+// out of it entirely.
+#set page(height: 20pt, margin: 0pt)
+#v(-5pt)
+#block(width: 20pt, height: 10pt, fill: aqua, context test(here().position().y, -5pt))
+#pagebreak()
+// Warning: 2-10 negative spacing was clamped
+// Hint: 2-10 it would have moved content out of the region entirely
+#v(-30pt)
+#block(width: 20pt, height: 10pt, context test(here().position().y, -20pt))
@@ -0,0 +1,39 @@
+// Test horizontal stacks.
+// Ref: false
+
+---
+// Children with mixed widths are placed along the direction. Right-to-left
+// stacks mirror the visual order, but keep the logical order of children.
+#set page(width: 200pt, margin: 10pt)
+#let child(width) = [#metadata(width)<child>#box(width: width, height: 10pt)]
+#let children = (10pt, 30pt, 20pt).map(child)
+
+#stack(dir: ltr, spacing: 5pt, ..children)
+#stack(dir: rtl, spacing: 5pt, ..children)
+
+#context {
+  let xs = query(<child>).map(it => it.location().position().x)
+  test(xs.slice(0, 3), (10pt, 25pt, 60pt))
+  test(xs.slice(3), (70pt, 35pt, 10pt))
+}
+
+---
+// Cross-axis alignment of children.
+#set page(width: 200pt, height: auto, margin: 0pt)
+#stack(
+  dir: ltr,
+  [#metadata(none)<a>#box(height: 30pt)],
+  align(horizon)[#metadata(none)<b>#box(width: 10pt, height: 10pt)],
+  align(bottom)[#metadata(none)<c>#box(width: 10pt, height: 10pt)],
+)
+
+#context {
+  test(locate(<b>).position().y, 10pt)
+  test(locate(<c>).position().y, 20pt)
+}
+
+---
+#set page(width: 100pt, margin: 10pt)
+// Warning: 2-55 stack is wider than the available space
+// Hint: 2-55 horizontal stacks do not wrap their children
+#stack(dir: ltr, rect(width: 50pt), rect(width: 50pt))
@@ -0,0 +1,46 @@
+// Test explicit spacing arguments in stacks.
+
+---
+// Explicit spacing replaces the default gap at its position.
+#set page(height: 140pt, margin: 0pt)
+#let probe(y) = block(width: 20pt, height: 10pt, fill: aqua, context {
+  test(here().position().y, y)
+})
+#stack(
+  spacing: 5pt,
+  probe(0pt),
+  probe(15pt),
+  20pt,
+  probe(45pt),
+  probe(60pt),
+)
+
+---
+// Fractional spacing shares the remaining space without default gaps.
+#set page(height: 100pt, margin: 0pt)
+#let probe(y) = block(width: 20pt, height: 10pt, fill: teal, context {
+  test(here().position().y, y)
+})
+#stack(
+  spacing: 5pt,
+  probe(0pt),
+  1fr,
+  probe(27.5pt),
+  3fr,
+  probe(90pt),
+)
+
+---
+// The same holds horizontally.
+#set page(width: 100pt, height: 20pt, margin: 0pt)
+#let probe(x) = box(width: 10pt, height: 10pt, fill: eastern, context {
+  test(here().position().x, x)
+})
+#stack(
+  dir: ltr,
+  spacing: 2pt,
+  probe(0pt),
+  probe(12pt),
+  8pt,
+  probe(30pt),
+)
@@ -0,0 +1,23 @@
+// Test that table headers repeat on every page.
+
+---
+// The header is at the top of each of the three pages.
+#set page(height: 140pt)
+#table(
+  columns: 2,
+  table.header[*Name*][*Value*],
+  ..range(14).map(i => ([Row #i], [#(i * i)])).flatten(),
+)
+#context test(counter(page).final(), (3,))
+
+---
+// The header is not repeated onto a page without body rows, even if the
+// last row ends exactly at the bottom of a page.
+#set page(height: 70pt)
+#table(
+  columns: 1,
+  inset: 5pt,
+  table.header(block(height: 10pt)[*Head*]),
+  ..range(2).map(i => block(height: 20pt)[#i]),
+)
+#context test(counter(page).final(), (2,))
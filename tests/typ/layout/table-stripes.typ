@@ -0,0 +1,40 @@
+// Test zebra striping and line styling in tables.
+
+---
+// A fill closure for zebra striping with outer and inner lines styled
+// separately.
+#set page(width: 160pt)
+#table(
+  columns: (1fr, auto),
+  fill: (x, y) => if calc.odd(y) { luma(235) },
+  stroke: (x, y) => (
+    left: if x == 0 { 1pt },
+    right: if x == 1 { 1pt } else { 0.5pt + gray },
+    top: if y == 0 { 1pt } else { 0.5pt + gray },
+    bottom: 1pt,
+  ),
+  inset: (x: 6pt, y: 3pt),
+  [Apples], [3],
+  [Pears], [5],
+  [Plums], [8],
+  [Cherries], [13],
+)
+
+---
+// Fill as an array per column.
+#table(
+  columns: 3,
+  fill: (red.lighten(60%), none, blue.lighten(60%)),
+  [a], [b], [c],
+  [d], [e], [f],
+)
+
+---
+// The top border is drawn again on each continuation page.
+#set page(height: 70pt)
+#table(
+  columns: 2,
+  fill: (_, y) => if calc.even(y) { aqua.lighten(50%) },
+  stroke: 1pt,
+  ..range(12).map(str),
+)
@@ -32,6 +32,28 @@
 #set terms(hanging-indent: 30pt)
 / Second list: #lorem(5)
 
+---
+// Test that wrapped lines of a description hang below its start and that
+// the hanging indent takes up horizontal space.
+#set text(8pt)
+#let list(indent) = block(
+  width: 100pt,
+  terms(hanging-indent: indent, separator: [: ], ([Term], lorem(12))),
+)
+#context assert(measure(list(40pt)).height > measure(list(0pt)).height)
+#list(2em)
+
+---
+// Test the spacing of tight and loose lists.
+#set text(8pt)
+#let items = (([A], [Alpha]), ([B], [Beta]), ([C], [Gamma]))
+#set par(leading: 4pt)
+#let tight = terms(tight: true, spacing: 10pt, ..items)
+#let loose = terms(tight: false, spacing: 10pt, ..items)
+#context test(measure(loose).height - measure(tight).height, 2 * (10pt - 4pt))
+#tight
+#loose
+
 ---
 // Test RTL.
 #set text(8pt, dir: rtl)
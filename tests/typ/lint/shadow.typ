@@ -0,0 +1,69 @@
+// Test lints for shadowed functions.
+// Ref: false
+
+---
+#let highlight(body) = body
+// Warning: 6-15 this shadows the function `highlight`
+// Hint: 6-15 consider using a different name for this variable
+#let highlight = "yellow"
+
+---
+// Shadowing in a nested block scope.
+#let total(..args) = args.pos().sum()
+#{
+  // Warning: 7-12 this shadows the function `total`
+  // Hint: 7-12 consider using a different name for this variable
+  let total = (1, 2, 3)
+  test(total.len(), 3)
+}
+#test(total(1, 2), 3)
+
+---
+// Shadowing through destructuring.
+#let first(x) = x
+// Warning: 7-12 this shadows the function `first`
+// Hint: 7-12 consider using a different name for this variable
+#let (first, second) = (1, 2)
+
+---
+// Replacing a function with another function is fine.
+#let shout(body) = upper(body)
+#let shout = shout.with()
+#let shout(body) = strong(body)
+
+---
+// Warning: 6-10 this shadows the function `text`
+// Hint: 6-10 consider using a different name for this variable
+#let text = "Arial"
+
+---
+// Shadowing functions of the standard library in a nested block scope.
+#{
+  // Warning: 7-11 this shadows the function `list`
+  // Hint: 7-11 consider using a different name for this variable
+  let list = (1, 2, 3)
+  test(list.len(), 3)
+}
+#test(type(list), function)
+
+---
+// Shadowing functions of the standard library through destructuring.
+// Warning: 7-11 this shadows the function `line`
+// Hint: 7-11 consider using a different name for this variable
+#let (line, column) = (1, 2)
+
+---
+// Wrapping functions of the standard library is fine.
+#let emph = body => strong(body)
+#let strike = strike.with(stroke: 2pt)
+#let current() = {
+  let here = counter(page).get()
+  here
+}
+#context test(current(), (1,))
+
+---
+// Names that aren't functions.
+#let pi = 3
+#let calc = none
+#let my-text = "Arial"
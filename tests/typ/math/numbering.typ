@@ -9,3 +9,15 @@ $ phi.alt := (1 + sqrt(5)) / 2 $ <ratio>
 
 With @ratio, we get
 $ F_n = round(1 / sqrt(5) phi.alt^n) $ <fib>
+
+---
+// A derivation aligned over several lines gets a single number, which a
+// label refers to.
+// Ref: false
+#set math.equation(numbering: "(1)")
+$ (a + b)^2 &= (a + b)(a + b) \
+            &= a^2 + a b + b a + b^2 \
+            &= a^2 + 2 a b + b^2 $ <binomial>
+#context test(counter(math.equation).at(<binomial>), (1,))
+#context test(counter(math.equation).final(), (1,))
+#context test(query(<binomial>).first().numbering, "(1)")
@@ -0,0 +1,24 @@
+// Test starting the page counter at a later number.
+// Ref: false
+
+---
+#set page(
+  height: 60pt,
+  numbering: "1",
+  footer: context {
+    let (number,) = counter(page).get()
+    let (total,) = counter(page).final()
+    test(number, here().page() + 56)
+    test(total, 59)
+    [#number / #total]
+  },
+)
+
+#counter(page).update(57)
+#context test(counter(page).get(), (57,))
+Continued on page #context counter(page).get().first() + 1.
+#pagebreak()
+#context test(counter(page).get(), (58,))
+#pagebreak()
+#context test(counter(page).at(here()), (59,))
+#context test(counter(page).final(), (59,))
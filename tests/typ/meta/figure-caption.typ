@@ -62,3 +62,37 @@
 ---
 // Error: 31-38 expected `top` or `bottom`, found horizon
 #set figure.caption(position: horizon)
+
+---
+// Test that captions are sized to the figure's body by default.
+#set page(width: 200pt)
+#let body = rect(width: 80pt, height: 30pt)
+#let short = figure(body, caption: [Short])
+#let long = figure(body, caption: [A caption that is far too long for one line.])
+#let full = figure(body, caption: [A caption that is far too long.], caption-width: 100%)
+#let fixed = figure(body, caption: [A caption that is far too long.], caption-width: 120pt)
+#let tiny = figure(rect(width: 10pt), caption: [Unbreakable])
+
+#context {
+  let width(it) = measure(it).width
+  test(width(short), 80pt)
+  test(width(long), 80pt)
+  assert(width(full) > 120pt)
+  assert(width(fixed) <= 120pt and width(fixed) > 80pt)
+  assert(measure(long).height > measure(short).height + 10pt)
+  assert(width(tiny) > measure[Unbreakable].width)
+}
+
+#short
+#long
+#full
+#fixed
+#tiny
+
+---
+// Test the gap between body and caption.
+#figure(
+  rect(width: 60pt, height: 20pt),
+  caption: [A caption that has to wrap.],
+  gap: 12pt,
+)
@@ -0,0 +1,58 @@
+// Test configuring heading sizes and spacing.
+// Ref: false
+
+---
+// The default sizes.
+#set text(10pt)
+= #context test(text.size, 14pt)
+== #context test(text.size, 12pt)
+=== #context test(text.size, 10pt)
+
+---
+// Custom sizes per level.
+#set text(10pt)
+#set heading(sizes: (2em, 15pt, 1.1em))
+= #context test(text.size, 20pt)
+== #context test(text.size, 15pt)
+=== #context test(text.size, 11pt)
+
+---
+// Custom spacing.
+#set page(height: auto, margin: 0pt)
+#set heading(above: 30pt, below: 20pt)
+#block(height: 10pt)
+= Heading <heading>
+#block(height: 10pt)[#metadata(none)<after>]
+
+#context {
+  let y = locate(<heading>).position().y
+  test(y, 40pt)
+  test(locate(<after>).position().y - y > 20pt, true)
+}
+
+---
+// Missing entries fall back to the default size.
+#set text(10pt)
+#set heading(sizes: (2em,))
+= #context test(text.size, 20pt)
+// Warning: 1-34 heading sizes have no entry for level 2
+// Hint: 1-34 using the default size for this level
+== #context test(text.size, 12pt)
+
+---
+// Non-positive sizes fall back to the default size.
+#set text(10pt)
+// Warning: 2-55 heading size for level 1 must be positive
+// Hint: 2-55 using the default size for this level
+#heading(sizes: (0pt,), context test(text.size, 14pt))
+
+---
+// Sizes that mix absolute and relative lengths fall back to the default size,
+// since they could be negative.
+#set text(10pt)
+// Warning: 2-62 heading size for level 1 cannot mix absolute and relative lengths
+// Hint: 2-62 using the default size for this level
+#heading(sizes: (1em - 20pt,), context test(text.size, 14pt))
+// Warning: 2-62 heading size for level 1 cannot mix absolute and relative lengths
+// Hint: 2-62 using the default size for this level
+#heading(sizes: (2em + 10pt,), context test(text.size, 14pt))
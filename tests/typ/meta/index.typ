@@ -0,0 +1,44 @@
+// Test the back-of-book index.
+
+---
+#set page(width: 120pt, height: 60pt, numbering: "1")
+#meta.index.entry("typesetting")
+#meta.index.entry("Fonts")
+A
+#pagebreak()
+#meta.index.entry("typesetting")
+#meta.index.entry("typesetting")
+#meta.index.entry("fonts", "fallback")
+B
+#pagebreak()
+#meta.index.entry("typesetting")
+#meta.index.entry("fonts", "fallback")
+#meta.index.entry("fonts")
+C
+#pagebreak()
+#meta.index.entry("Typst")
+D
+#pagebreak()
+#meta.index.entry("typesetting")
+#meta.index.entry("fonts", "embedding")
+E
+#pagebreak()
+#set page(height: auto, numbering: none)
+#context meta.index.build()
+
+---
+// Only sub-terms, and roman page numbers.
+#set page(width: 120pt, height: 60pt, numbering: "i")
+#meta.index.entry("layout", "grid")
+#pagebreak()
+#meta.index.entry("layout", "stack")
+#meta.index.entry("layout", "grid")
+#pagebreak()
+#set page(height: auto, numbering: none)
+#context meta.index.build()
+
+---
+// Error: 2-20 can only be used when context is known
+// Hint: 2-20 try wrapping this in a `context` expression
+// Hint: 2-20 the `context` expression should wrap everything that depends on this function
+#meta.index.build()
@@ -0,0 +1,88 @@
+// Test converting numbers to and from roman numerals and words.
+// Ref: false
+
+---
+#for (n, numeral) in (
+  (1, "I"),
+  (3, "III"),
+  (4, "IV"),
+  (9, "IX"),
+  (14, "XIV"),
+  (40, "XL"),
+  (49, "XLIX"),
+  (90, "XC"),
+  (400, "CD"),
+  (900, "CM"),
+  (1998, "MCMXCVIII"),
+  (2024, "MMXXIV"),
+  (3999, "MMMCMXCIX"),
+  (4000, "I̅V̅"),
+  (12345, "X̅MMCCCXLV"),
+  (9000, "I̅X̅"),
+  (49000, "X̅L̅I̅X̅"),
+  (3999999, "M̅M̅M̅C̅M̅X̅C̅I̅X̅CMXCIX"),
+) {
+  test(numbering.roman(n), numeral)
+  test(numbering.roman(n, lower: true), lower(numeral))
+  test(numbering.from-roman(numeral), n)
+  test(numbering.from-roman(lower(numeral)), n)
+  test(numbering.roman(n), numbering("I", n))
+}
+
+---
+// Error: 2-20 roman numerals only exist for positive numbers
+#numbering.roman(0)
+
+---
+// Error: 2-21 roman numerals only exist for positive numbers
+#numbering.roman(-5)
+
+---
+// Error: 2-26 number is too large for a roman numeral (maximum is 3999999)
+#numbering.roman(4000000)
+
+---
+// Error: 2-30 "IIII" is not a valid roman numeral
+#numbering.from-roman("IIII")
+
+---
+// Error: 2-28 "IC" is not a valid roman numeral
+#numbering.from-roman("IC")
+
+---
+// Error: 2-30 "XIV " is not a valid roman numeral
+#numbering.from-roman("XIV ")
+
+---
+// Error: 2-26 "" is not a valid roman numeral
+#numbering.from-roman("")
+
+---
+#for (n, words) in (
+  (0, "zero"),
+  (7, "seven"),
+  (13, "thirteen"),
+  (20, "twenty"),
+  (42, "forty-two"),
+  (100, "one hundred"),
+  (101, "one hundred one"),
+  (999, "nine hundred ninety-nine"),
+  (1000, "one thousand"),
+  (1998, "one thousand nine hundred ninety-eight"),
+  (1000000, "one million"),
+  (2000017, "two million seventeen"),
+  (-42, "minus forty-two"),
+  (
+    9223372036854775807,
+    "nine quintillion two hundred twenty-three quadrillion three hundred "
+      + "seventy-two trillion thirty-six billion eight hundred fifty-four "
+      + "million seven hundred seventy-five thousand eight hundred seven",
+  ),
+) {
+  test(numbering.spell(n), words)
+  test(numbering.spell(n, lang: "en"), words)
+}
+
+---
+// Error: 2-32 cannot spell out numbers in language de
+#numbering.spell(3, lang: "de")
@@ -43,3 +43,24 @@ Ok ...
 
 = #text(blue)[Sum]mary
 #lorem(10)
+
+---
+// Separate lists of figures and tables.
+#set page(width: 150pt)
+#outline(
+  title: [Figures],
+  target: figure.where(kind: image),
+  fill: box(width: 1fr, repeat[-]),
+)
+#outline(title: [Tables], target: figure.where(kind: table), fill: none)
+
+#figure(rect[A], caption: [First picture.])
+#figure(table[1], caption: [Only table.])
+#pagebreak()
+#figure(rect[B], caption: [Second picture.])
+
+---
+// An outline without entries is omitted, including its title.
+// Ref: false
+#context test(measure(outline(target: figure.where(kind: raw))).height, 0pt)
+#context test(measure(outline(title: [Listings], target: figure)).height, 0pt)
@@ -0,0 +1,26 @@
+// Test PDF-specific markers.
+// Ref: false
+
+---
+// Markers don't take up space.
+#let markers = [#meta.pdf.dest("intro")#meta.pdf.comment("here")]
+#context test(measure(markers).width, 0pt)
+#context test(measure([A#markers;B]).width, measure[AB].width)
+
+---
+// Error: 16-18 expected string, found integer
+#meta.pdf.dest(12)
+
+---
+// Notes don't take up space and don't change the layout around them.
+#let note = meta.pdf.note("Check this figure", author: "AB")
+#context test(measure(note).width, 0pt)
+#let body = [Notes stay out of #note the way of the text.]
+#context test(
+  measure(block(width: 60pt, body)),
+  measure(block(width: 60pt)[Notes stay out of the way of the text.]),
+)
+
+---
+// Error: 33-35 expected string, found integer
+#meta.pdf.note("Check", author: 12)
@@ -0,0 +1,36 @@
+// Test building a custom table of contents with the query function.
+
+---
+#set page(width: 120pt, height: 160pt)
+#set heading(numbering: "1.1")
+
+#let toc = context {
+  let entries = query(heading.where(outlined: true)).map(it => {
+    let loc = it.location()
+    let number = numbering(it.numbering, ..counter(heading).at(loc))
+    // Warning: 9-13 this shadows the function `page`
+    // Hint: 9-13 consider using a different name for this variable
+    let page = counter(page).at(loc).first()
+    (level: it.level, number: number, body: it.body, page: page)
+  })
+
+  test(entries.map(e => e.level), (1, 2, 1))
+  test(entries.map(e => e.number), ("1", "2.1", "3"))
+  test(entries.map(e => e.page), (2, 3, 3))
+
+  for e in entries {
+    h((e.level - 1) * 1em)
+    [#e.number #e.body #box(width: 1fr, repeat[.]) #e.page]
+    linebreak()
+  }
+}
+
+#toc
+#outline(title: none)
+#pagebreak()
+
+= Introduction
+#heading(outlined: false)[Hidden]
+#pagebreak()
+== Details
+= Summary
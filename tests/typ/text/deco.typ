@@ -83,3 +83,16 @@ We can also specify a customized value
 // Test strike background
 #set strike(background: true, stroke: 5pt + red)
 #strike[This is in the background]
+
+---
+// Test that underlines evade descenders. The line under "gap" is split into
+// multiple segments, while the one under "one" is a single segment.
+#underline[gap] #underline[one] \
+#underline(evade: false)[gap] #underline(evade: false)[one]
+
+---
+// Test that the gaps around descenders grow with the thickness of the line.
+#set text(size: 16pt)
+#underline(stroke: 0.5pt)[jumpy] \
+#underline(stroke: 1.5pt)[jumpy] \
+#underline(stroke: 2.5pt)[jumpy]
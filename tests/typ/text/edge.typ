@@ -37,3 +37,23 @@
 ---
 // Error: 24-36 expected "baseline", "descender", "bounds", or length
 #set text(bottom-edge: "cap-height")
+
+---
+// Tight edges follow the ink of the glyphs.
+// Ref: false
+#let height(top, bottom, body) = measure(
+  text(top-edge: top, bottom-edge: bottom, body),
+).height
+#let tight = height.with("bounds", "bounds")
+#let loose = height.with("ascender", "descender")
+
+#context {
+  test(loose("ace"), loose("Apg"))
+  assert(tight("ace") < tight("Ace"))
+  assert(tight("Ace") < tight("Apg"))
+  assert(tight("Apg") < loose("Apg"))
+
+  // A mark is measured where the shaper placed it.
+  let top = height.with("bounds", "baseline")
+  assert(top("Q\u{0303}") > top("\u{0303}"))
+}
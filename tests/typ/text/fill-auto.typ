@@ -0,0 +1,28 @@
+// Test automatic text fill for contrast with the background.
+
+---
+#set text(fill: auto)
+#set page(width: 160pt)
+#box(fill: blue.darken(60%), inset: 4pt)[Dark]
+#box(fill: yellow.lighten(60%), inset: 4pt)[Light]
+#box(inset: 4pt)[None]
+#box(fill: gradient.linear(black, navy), inset: 4pt)[Gradient]
+
+#table(
+  columns: 2,
+  fill: (_, y) => if y == 0 { eastern.darken(30%) },
+  [*Name*], [*Value*],
+  [a], [1],
+)
+
+#block(fill: black, inset: 4pt)[
+  White #circle(fill: white, inset: 2pt)[Black] and white again.
+]
+
+---
+// Explicit fills are kept.
+#box(fill: black, inset: 4pt, text(fill: red)[Red])
+
+---
+// Error: 17-21 expected color, gradient, pattern, or auto, found none
+#set text(fill: none)
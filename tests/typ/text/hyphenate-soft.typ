@@ -0,0 +1,16 @@
+// Test explicit break opportunities.
+
+---
+// A long compound word breaks at one of its soft hyphens and shows exactly
+// one hyphen.
+#set page(width: 80pt)
+Donau-?dampf-?schiffs-?gesellschaft
+
+---
+// A zero-width space allows a break without a hyphen.
+#set page(width: 80pt)
+Donau#sym.zws;dampf#sym.zws;schiffs#sym.zws;gesellschaft
+
+---
+// Unused soft hyphens don't take up space.
+#context test(measure[Ab-?bau].width, measure[Abbau].width)
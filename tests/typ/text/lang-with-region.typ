@@ -4,13 +4,16 @@
 // without any region
 #set text(font: "Noto Serif CJK TC", lang: "zh")
 #outline()
+= 介紹
 
 ---
 // with unknown region configured
 #set text(font: "Noto Serif CJK TC", lang: "zh", region: "XX")
 #outline()
+= 介紹
 
 ---
 // with region configured
 #set text(font: "Noto Serif CJK TC", lang: "zh", region: "TW")
 #outline()
+= 介紹
@@ -0,0 +1,56 @@
+// Test locale-aware display of numbers and dates.
+// Ref: false
+
+---
+// Test numbers in each supported language.
+#test(display-number(1234.5), "1,234.5")
+#test(display-number(1234.5, lang: "en"), "1,234.5")
+#test(display-number(1234.5, lang: "de"), "1.234,5")
+#test(display-number(1234.5, lang: "fr"), "1\u{202F}234,5")
+#test(display-number(-1234567, lang: "de"), "−1.234.567")
+#test(display-number(123, lang: "de"), "123")
+#test(display-number(0.25, lang: "fr"), "0,25")
+#test(display-number(1000000000000, lang: "en"), "1,000,000,000,000")
+
+---
+// Test that the language defaults to the text language in context.
+#set text(lang: "de")
+#test(display-number(1234.5), "1,234.5")
+#context test(display-number(1234.5), "1.234,5")
+#context test(display-number(1234.5, lang: "fr"), "1\u{202F}234,5")
+
+---
+// Test month and weekday names in each supported language.
+#let date = datetime(year: 2024, month: 3, day: 1)
+#let pattern = "[weekday] [weekday repr:short] [day] [month repr:long] [month repr:short] [year]"
+#test(date.display(pattern), "Friday Fri 01 March Mar 2024")
+#test(date.display(pattern, lang: "de"), "Freitag Fr 01 März März 2024")
+#test(date.display(pattern, lang: "fr"), "vendredi ven. 01 mars mars 2024")
+#test(
+  datetime(year: 2024, month: 12, day: 23).display("[weekday], [month repr:long]", lang: "fr"),
+  "lundi, décembre",
+)
+
+---
+// Test that dates follow the text language in context.
+#set text(lang: "de")
+#let date = datetime(year: 2024, month: 10, day: 3, hour: 12, minute: 0, second: 0)
+#context test(date.display("[day]. [month repr:long] [year], [hour]:[minute]"), "03. Oktober 2024, 12:00")
+#context test(date.display("[month repr:short]", lang: "en"), "Oct")
+
+---
+// Test the fallback for languages without localization data.
+// Warning: 7-41 localized formatting is not available for language es
+// Hint: 7-41 falling back to English
+#test(display-number(1234.5, lang: "es"), "1,234.5")
+
+---
+// Purely numerical formats don't need localization data.
+#set text(lang: "es")
+#context test(datetime(year: 2024, month: 3, day: 1).display("[day].[month]."), "01.03.")
+
+---
+#set text(lang: "es")
+// Warning: 15-82 localized formatting is not available for language es
+// Hint: 15-82 falling back to English
+#context test(datetime(year: 2024, month: 3, day: 1).display("[month repr:long]"), "March")
@@ -30,3 +30,10 @@
 ---
 // Error: 2-9 missing argument: words
 #lorem()
+
+---
+// Test seeded blind text.
+// Ref: false
+#test(lorem(20, seed: 1), lorem(20, seed: 1))
+#test(lorem(20, seed: 1) == lorem(20, seed: 2), false)
+#test(lorem(20, seed: 3).split(" ").len(), 20)
@@ -0,0 +1,36 @@
+// Test the background of inline raw text.
+
+---
+#set page(width: 120pt)
+#set raw(fill: luma(230), inset: 2pt, radius: 2pt)
+Call `main()` and `exit(0)` to finish.
+
+// Padded inline raw text is not broken across lines.
+Wrapping `rather long code` here.
+
+```rust
+fn main() {}
+```
+
+---
+// The vertical inset doesn't affect the line height, but the horizontal one
+// takes up space.
+// Ref: false
+#context {
+  let plain = measure[Run `x` now]
+  let padded = measure({
+    set raw(fill: luma(230), inset: (x: 4pt, y: 10pt))
+    [Run `x` now]
+  })
+  test(padded.height, plain.height)
+  test(padded.width, plain.width + 8pt)
+}
+
+---
+// Raw blocks are not affected.
+// Ref: false
+#let code = raw("let x = 1", block: true)
+#context {
+  let plain = measure(code)
+  test(measure({ set raw(fill: red, inset: 5pt); code }), plain)
+}
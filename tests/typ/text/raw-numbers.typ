@@ -0,0 +1,33 @@
+// Test line numbers in raw blocks.
+
+---
+#set raw(numbers: true, tab-size: 4)
+```rust
+fn main() {
+	let x = 1;
+
+}
+
+```
+
+---
+// The gutter grows with the largest line number.
+#let code(n) = raw(range(n).map(str).join("\n"), block: true, numbers: true)
+#context test(
+  measure(code(10)).width - measure(code(9)).width,
+  measure(raw("0")).width,
+)
+#code(10)
+
+---
+// Alignment applies to the lines, not the numbers.
+#set raw(numbers: true, align: center)
+```
+short
+a longer line
+```
+
+---
+// Inline raw text is never numbered.
+#set raw(numbers: true)
+Some `inline` code.
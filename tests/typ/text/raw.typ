@@ -75,5 +75,5 @@ The keyword ```rust let```.
 
 ---
 // Unterminated.
-// Error: 1-2:1 unclosed raw text
+// Error: 1-2 unclosed raw text
 `endless
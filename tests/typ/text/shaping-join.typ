@@ -0,0 +1,20 @@
+// Test that adjacent text with the same style is shaped as one run.
+
+---
+// Kerning and ligatures apply across the boundaries of inline expressions.
+#context {
+  test(measure[AV].width == measure[A].width + measure[V].width, false)
+  test(measure[A#"V"].width, measure[AV].width)
+  test(measure[f#"i"].width, measure[fi].width)
+  test(measure[#("f" + "i")].width, measure[fi].width)
+  test(measure[#text("f")i].width, measure[fi].width)
+}
+
+---
+// Justification stretches the spaces inside inserted text like any other.
+#set page(width: 120pt)
+#set par(justify: true)
+#let word = "interword"
+The #word spaces of this #("justified line") stretch evenly.
+
+The interword spaces of this justified line stretch evenly.
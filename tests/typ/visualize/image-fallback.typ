@@ -0,0 +1,39 @@
+// Test placeholders for images that can't be loaded.
+
+---
+// A missing image with a fallback doesn't stop the rest of the document.
+Before
+// Warning: 8-29 file not found (searched at typ/visualize/path/does/not/exist)
+// Hint: 8-29 the image was replaced by a placeholder
+#image("path/does/not/exist", fallback: true)
+After
+#pagebreak()
+Next page
+
+---
+// The placeholder takes the declared size.
+#set image(fallback: true)
+// Warning: 8-21 file not found (searched at typ/visualize/missing.png)
+// Hint: 8-21 the image was replaced by a placeholder
+#image("missing.png", width: 80%, height: 30pt)
+
+---
+// Decoding errors can fall back, too.
+// Warning: 2-66 failed to decode image (Format error decoding Png: Invalid PNG signature.)
+// Hint: 2-66 the image was replaced by a placeholder
+#image.decode(bytes((1, 2, 3, 4)), format: "png", fallback: true)
+
+---
+// Without a fallback, the error is still reported.
+// Error: 8-21 file not found (searched at typ/visualize/missing.png)
+#image("missing.png", fallback: false)
+
+---
+// Loading errors are reported when the image is laid out, so an image that is
+// never shown doesn't produce one, and a later set rule still applies.
+#let unused = image("missing.png")
+// Warning: 19-32 file not found (searched at typ/visualize/missing.png)
+// Hint: 19-32 the image was replaced by a placeholder
+#let late = image("missing.png")
+#set image(fallback: true)
+#late